@@ -0,0 +1,198 @@
+use blake3::Hasher;
+use common::{
+    error::{Error, ErrorKind::UnrecoverableSecret},
+    generators::Generators,
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, traits::Identity};
+use rayon::prelude::*;
+use std::sync::Arc;
+
+use crate::{
+    bid::{Opening, RecoveryShare, SealedBid},
+    transcript::{Transcript, winner},
+};
+
+/// The bid in `sealed - G * s`, None if it is above `max_bid`
+pub(crate) fn decode(
+    G: &RistrettoPoint,
+    sealed: &RistrettoPoint,
+    secret: &RistrettoPoint,
+    max_bid: u64,
+) -> Option<u64> {
+    let bid = sealed - secret;
+    let mut acc = RistrettoPoint::identity();
+    for value in 0..=max_bid {
+        if acc == bid {
+            return Some(value);
+        }
+        acc += G;
+    }
+    None
+}
+
+/// Collects one sealed-bid auction: every bid during the bidding phase, then an opening or t+1
+/// recovery shares for each valid one
+pub struct Auction {
+    pub t: usize,
+    pub max_bid: u64,
+    /// G, pk0 and the auctioneers' public keys
+    pub generators: Arc<Generators>,
+    /// the registered bidders' public keys, by bidder index
+    pub bidders: Vec<RistrettoPoint>,
+    pub closed: bool,
+    pub bids: Vec<(bool, SealedBid)>,
+    pub openings: Vec<Option<Scalar>>,
+    /// by increasing auctioneer index
+    pub recovery_shares: Vec<Vec<RecoveryShare>>,
+}
+
+impl Auction {
+    pub fn new(
+        t: usize,
+        max_bid: u64,
+        generators: &Arc<Generators>,
+        bidders: &[RistrettoPoint],
+    ) -> Self {
+        Self {
+            t,
+            max_bid,
+            generators: generators.clone(),
+            bidders: bidders.to_vec(),
+            closed: false,
+            bids: vec![],
+            openings: vec![],
+            recovery_shares: vec![],
+        }
+    }
+
+    /// Keeps a bid while the bidding phase is open. A bid not signed by its registered bidder is
+    /// ignored, as is a bidder's second bid and a bid reusing an earlier bid's dealing, which
+    /// would otherwise let a bidder outbid it by shifting its sealed bid.
+    pub fn ingest_bid(&mut self, bid: SealedBid) -> bool {
+        if self.closed
+            || !bid.verify_signature(&self.generators, &self.bidders)
+            || self.bids.iter().any(|(_, ingested)| {
+                ingested.bidder == bid.bidder
+                    || ingested.encrypted_shares.first() == bid.encrypted_shares.first()
+            })
+        {
+            return false;
+        }
+        self.bids.push((false, bid));
+        self.openings.push(None);
+        self.recovery_shares.push(vec![]);
+        true
+    }
+
+    /// Closes the bidding phase and checks every bid's dealing
+    pub fn verify_bids(&mut self) {
+        self.closed = true;
+        self.bids.par_iter_mut().for_each_init(
+            || (Hasher::new(), [0u8; 64]),
+            |(hasher, buf), (status, bid)| {
+                *status = bid.verify(&self.generators, self.t, hasher, buf);
+            },
+        );
+    }
+
+    /// Positions of the valid bids that have not been opened yet, the auctioneers force these open
+    pub fn withheld(&self) -> Vec<usize> {
+        self.bids
+            .iter()
+            .zip(&self.openings)
+            .enumerate()
+            .filter(|(_, ((status, _), opening))| *status && opening.is_none())
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Keeps the opening of a valid bid at `position` only once the bidding phase is closed, and
+    /// only if pk0 * f0 matches its encrypted share
+    pub fn ingest_opening(
+        &mut self,
+        position: usize,
+        f0: Scalar,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let Some((status, bid)) = self.bids.get(position) else {
+            return false;
+        };
+        let opens = self.closed
+            && *status
+            && Opening::Optimistic(f0)
+                .verify(&self.generators, self.t, bid, hasher, buf)
+                .is_some();
+        if opens {
+            self.openings[position] = Some(f0);
+        }
+        opens
+    }
+
+    /// Keeps an auctioneer's share of a valid bid at `position` only once the bidding phase is
+    /// closed, and only if its proof holds
+    pub fn ingest_recovery_share(
+        &mut self,
+        position: usize,
+        share: RecoveryShare,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let (Some((true, bid)), Some(shares)) =
+            (self.bids.get(position), self.recovery_shares.get(position))
+        else {
+            return false;
+        };
+        if !self.closed {
+            return false;
+        }
+        let Err(slot) = shares.binary_search_by_key(&share.index, |share| share.index) else {
+            return false;
+        };
+        let holds = share.verify(&self.generators, bid, hasher, buf);
+        if holds {
+            self.recovery_shares[position].insert(slot, share);
+        }
+        holds
+    }
+
+    /// Opens every valid bid and picks the highest, ties going to the earliest bid. Bids above
+    /// `max_bid` are disqualified.
+    pub fn finalize(&self, hasher: &mut Hasher, buf: &mut [u8; 64]) -> Result<Transcript, Error> {
+        let mut openings = Vec::with_capacity(self.bids.len());
+        let mut values = Vec::with_capacity(self.bids.len());
+        for (((status, bid), f0), shares) in self
+            .bids
+            .iter()
+            .zip(&self.openings)
+            .zip(&self.recovery_shares)
+        {
+            if !status {
+                openings.push(None);
+                values.push(None);
+                continue;
+            }
+            let opening = match f0 {
+                Some(f0) => Opening::Optimistic(*f0),
+                None if shares.len() > self.t => Opening::Forced(shares[..=self.t].to_vec()),
+                None => return Err(UnrecoverableSecret(bid.bidder).into()),
+            };
+            let secret = opening
+                .verify(&self.generators, self.t, bid, hasher, buf)
+                .ok_or_else(|| Error::from(UnrecoverableSecret(bid.bidder)))?;
+            // checked in SealedBid::verify
+            let sealed = bid.sealed.decompress().unwrap();
+            values.push(decode(self.generators.G(), &sealed, &secret, self.max_bid));
+            openings.push(Some(opening));
+        }
+
+        let bids: Vec<SealedBid> = self.bids.iter().map(|(_, bid)| bid.clone()).collect();
+        Ok(Transcript {
+            max_bid: self.max_bid,
+            winner: winner(&bids, &values),
+            bids,
+            openings,
+            values,
+        })
+    }
+}
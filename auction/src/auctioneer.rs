@@ -0,0 +1,52 @@
+use blake3::Hasher;
+use common::{error::Error, generators::Generators};
+use pi_s_ppvss::party::Party;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+use crate::bid::{RecoveryShare, SealedBid};
+
+/// Holds a share of every bid, and decrypts it when a bidder refuses to open
+pub struct Auctioneer {
+    pub party: Party,
+}
+
+impl Auctioneer {
+    pub fn new<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        n: usize,
+        t: usize,
+        index: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        Ok(Self {
+            party: Party::new(generators, rng, n, t, index)?,
+        })
+    }
+
+    /// Decrypts this auctioneer's share of `bid` with a DLEQ proof
+    pub fn recover_share<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        bid: &SealedBid,
+    ) -> Result<RecoveryShare, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        self.party.ingest_encrypted_shares(&bid.encrypted_shares)?;
+        self.party.decrypt_share()?;
+        self.party.dleq_share(rng, hasher, buf)?;
+
+        Ok(RecoveryShare {
+            index: self.party.index,
+            // both set above
+            decrypted_share: self.party.decrypted_share.unwrap().compress(),
+            proof: self.party.share_proof.unwrap(),
+        })
+    }
+}
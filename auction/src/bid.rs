@@ -0,0 +1,184 @@
+use blake3::Hasher;
+use common::{
+    generators::Generators,
+    polynomial::Polynomial,
+    signature::Signature,
+    utils::{batch_decompress_ristretto_points, lagrange_coefficients, verify_decrypted_share},
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use pi_s_ppvss::utils::verify_encrypted_shares_standalone;
+
+/// "auction bid" | session | bidder | encrypted_shares | proof | sealed
+pub fn bid_message(
+    session: &[u8; 32],
+    bidder: usize,
+    encrypted_shares: &[CompressedRistretto],
+    proof: &(Scalar, Polynomial),
+    sealed: &CompressedRistretto,
+) -> Vec<u8> {
+    b"auction bid"
+        .iter()
+        .chain(session)
+        .chain(&(bidder as u64).to_le_bytes())
+        .chain(encrypted_shares.iter().flat_map(|share| share.as_bytes()))
+        .chain(proof.0.as_bytes())
+        .chain(proof.1.coefficients().iter().flat_map(|z| z.as_bytes()))
+        .chain(sealed.as_bytes())
+        .copied()
+        .collect()
+}
+
+/// A bidder's dealing of a random s to the auctioneers, with the bid sealed as G * (s + bid)
+#[derive(Clone)]
+pub struct SealedBid {
+    /// 1-based index of the bidder on the auction's roll
+    pub bidder: usize,
+    /// pk0 * s first, then one per auctioneer
+    pub encrypted_shares: Vec<CompressedRistretto>,
+    pub proof: (Scalar, Polynomial),
+    /// G * (s + bid)
+    pub sealed: CompressedRistretto,
+    /// by the bidder's registered key, over `bid_message`
+    pub signature: Signature,
+}
+
+impl SealedBid {
+    /// Whether the bid is signed by the key registered for its bidder in `bidders`
+    pub fn verify_signature(&self, generators: &Generators, bidders: &[RistrettoPoint]) -> bool {
+        self.bidder
+            .checked_sub(1)
+            .and_then(|i| bidders.get(i))
+            .is_some_and(|public_key| {
+                self.signature.verify(
+                    generators.G_table(),
+                    public_key,
+                    &bid_message(
+                        &generators.session(),
+                        self.bidder,
+                        &self.encrypted_shares,
+                        &self.proof,
+                        &self.sealed,
+                    ),
+                )
+            })
+    }
+
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let (d, z) = &self.proof;
+        if z.len() != t + 1 || self.sealed.decompress().is_none() {
+            return false;
+        }
+        let Ok(encrypted_shares) = batch_decompress_ristretto_points(&self.encrypted_shares) else {
+            return false;
+        };
+
+        verify_encrypted_shares_standalone(
+            &(self.encrypted_shares.clone(), encrypted_shares),
+            &generators.dealing_tables(),
+            (d, z),
+            hasher,
+            buf,
+        )
+        .unwrap_or(false)
+    }
+}
+
+/// An auctioneer's decrypted share of a bid whose opening was withheld
+#[derive(Clone)]
+pub struct RecoveryShare {
+    /// 1-based index of the decrypting auctioneer
+    pub index: usize,
+    pub decrypted_share: CompressedRistretto,
+    pub proof: (Scalar, Scalar),
+}
+
+impl RecoveryShare {
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        bid: &SealedBid,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let public_keys = generators.public_keys();
+        if self.index == 0 || self.index > public_keys.len() {
+            return false;
+        }
+        let (Some(encrypted_share), Some(decrypted_share)) = (
+            bid.encrypted_shares
+                .get(self.index)
+                .and_then(|share| share.decompress()),
+            self.decrypted_share.decompress(),
+        ) else {
+            return false;
+        };
+
+        verify_decrypted_share(
+            generators.G(),
+            &public_keys[self.index - 1],
+            &encrypted_share,
+            &decrypted_share,
+            &self.proof,
+            hasher,
+            buf,
+        )
+    }
+}
+
+/// How a valid bid's G * s is opened
+#[derive(Clone)]
+pub enum Opening {
+    /// the bidder published f0 = s, checked against pk0 * s like
+    /// `Party::reconstruct_secret_optimistic`
+    Optimistic(Scalar),
+    /// the bidder refused, t+1 auctioneers decrypted their shares instead (by increasing index)
+    Forced(Vec<RecoveryShare>),
+}
+
+impl Opening {
+    /// G * s of `bid`, if the opening opens it
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        t: usize,
+        bid: &SealedBid,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Option<RistrettoPoint> {
+        match self {
+            Opening::Optimistic(f0) => {
+                let pk0 = generators.pk0_table().ok()?;
+                let opens = bid.encrypted_shares.first() == Some(&(pk0 * f0).compress());
+
+                opens.then(|| generators.G_table() * f0)
+            }
+            Opening::Forced(shares) => {
+                let increasing = shares.windows(2).all(|pair| pair[0].index < pair[1].index);
+                if shares.len() != t + 1
+                    || !increasing
+                    || !shares
+                        .iter()
+                        .all(|share| share.verify(generators, bid, hasher, buf))
+                {
+                    return None;
+                }
+                let indices: Vec<usize> = shares.iter().map(|share| share.index).collect();
+
+                Some(
+                    lagrange_coefficients(&indices)
+                        .iter()
+                        .zip(shares)
+                        // checked in RecoveryShare::verify
+                        .map(|(lambda, share)| lambda * share.decrypted_share.decompress().unwrap())
+                        .sum(),
+                )
+            }
+        }
+    }
+}
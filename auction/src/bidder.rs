@@ -0,0 +1,94 @@
+use blake3::Hasher;
+use common::{
+    error::{
+        Error,
+        ErrorKind::{BidTooHigh, UninitializedValue},
+    },
+    generators::Generators,
+    random::random_scalar,
+    secret::Secret,
+    signature::SigningKey,
+};
+use curve25519_dalek::{RistrettoPoint, Scalar};
+use pi_s_ppvss::dealer::Dealer;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+use crate::bid::{SealedBid, bid_message};
+
+/// Deals a fresh secret per bid, and opens it once the bidding phase closes
+pub struct Bidder {
+    pub index: usize,
+    /// the key registered for `index` on the auction's roll, signs the bids
+    signing_key: SigningKey,
+    dealer: Dealer,
+    bid: Option<u64>,
+}
+
+impl Bidder {
+    /// `generators` holds G, pk0 and the auctioneers' public keys
+    pub fn new(
+        index: usize,
+        signing_key: SigningKey,
+        n: usize,
+        t: usize,
+        generators: &Arc<Generators>,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            index,
+            signing_key,
+            dealer: Dealer::new(n, t, generators)?,
+            bid: None,
+        })
+    }
+
+    /// Seals `value`, which must not exceed the auction's `max_bid`
+    pub fn bid<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        value: u64,
+        max_bid: u64,
+    ) -> Result<SealedBid, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if value > max_bid {
+            return Err(BidTooHigh(value, max_bid).into());
+        }
+        let secret = Secret::new(random_scalar(rng));
+        let (encrypted_shares, proof) = self.dealer.deal_secret(rng, hasher, buf, secret.expose());
+        self.bid = Some(value);
+
+        let G = self.dealer.generators.G_table();
+        let sealed = (G * &(secret.expose() + Scalar::from(value))).compress();
+        let message = bid_message(
+            &self.dealer.generators.session(),
+            self.index,
+            &encrypted_shares,
+            &proof,
+            &sealed,
+        );
+        Ok(SealedBid {
+            bidder: self.index,
+            encrypted_shares,
+            proof,
+            sealed,
+            signature: self.signing_key.sign(rng, G, &message),
+        })
+    }
+
+    /// The key to register for this bidder
+    pub fn public_key(&self) -> &RistrettoPoint {
+        &self.signing_key.public_key
+    }
+
+    /// Opens the last bid, f0 = s
+    pub fn open(&self) -> Result<Scalar, Error> {
+        match self.bid {
+            Some(_) => Ok(self.dealer.publish_f0()),
+            None => Err(UninitializedValue("bidder.bid").into()),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+#![allow(non_snake_case)]
+
+pub mod auction;
+pub mod auctioneer;
+pub mod bid;
+pub mod bidder;
+pub mod transcript;
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        generators::Generators,
+        key_proof::KeyProof,
+        random::{random_point, random_scalar},
+        signature::SigningKey,
+    };
+    use curve25519_dalek::{RistrettoPoint, Scalar};
+    use std::sync::Arc;
+
+    use crate::{
+        auction::Auction,
+        auctioneer::Auctioneer,
+        bid::{SealedBid, bid_message},
+        bidder::Bidder,
+    };
+
+    /// Signs a tampered bid again with `private_key`
+    fn sign(
+        rng: &mut rand::rngs::ThreadRng,
+        generators: &Generators,
+        private_key: &Scalar,
+        bid: &mut SealedBid,
+    ) {
+        let message = bid_message(
+            &generators.session(),
+            bid.bidder,
+            &bid.encrypted_shares,
+            &bid.proof,
+            &bid.sealed,
+        );
+        bid.signature = SigningKey::from_private_key(private_key, generators.G_table()).sign(
+            rng,
+            generators.G_table(),
+            &message,
+        );
+    }
+
+    #[test]
+    fn auction() {
+        const N: usize = 7;
+        const T: usize = 3;
+        const MAX_BID: u64 = 100;
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+        let generators = Arc::new(Generators::new(&G).with_pk0(&random_point(&mut rng)));
+
+        let mut auctioneers: Vec<Auctioneer> = (1..=N)
+            .map(|i| Auctioneer::new(&generators, &mut rng, N, T, i).unwrap())
+            .collect();
+        let public_keys: Vec<RistrettoPoint> = auctioneers
+            .iter()
+            .map(|auctioneer| auctioneer.party.public_key.1)
+            .collect();
+        let key_proofs: Vec<KeyProof> = auctioneers
+            .iter()
+            .map(|auctioneer| auctioneer.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+
+        let private_keys: Vec<Scalar> = (0..6).map(|_| random_scalar(&mut rng)).collect();
+        let mut bidders: Vec<Bidder> = private_keys
+            .iter()
+            .enumerate()
+            .map(|(i, private_key)| {
+                let signing_key = SigningKey::from_private_key(private_key, generators.G_table());
+                Bidder::new(i + 1, signing_key, N, T, &generators).unwrap()
+            })
+            .collect();
+        let roll: Vec<RistrettoPoint> = bidders.iter().map(|bidder| *bidder.public_key()).collect();
+        let mut auction = Auction::new(T, MAX_BID, &generators, &roll);
+
+        // bidder 6 cannot bid in bidder 2's name
+        let mut forged = bidders[5]
+            .bid(&mut rng, &mut hasher, &mut buf, 10, MAX_BID)
+            .unwrap();
+        forged.bidder = 2;
+        assert!(!auction.ingest_bid(forged.clone()));
+        sign(&mut rng, &generators, &private_keys[5], &mut forged);
+        assert!(!auction.ingest_bid(forged));
+
+        // an honest bidder cannot bid above the maximum
+        assert!(
+            bidders[3]
+                .bid(&mut rng, &mut hasher, &mut buf, 120, MAX_BID)
+                .is_err()
+        );
+
+        for (bidder, value) in bidders.iter_mut().zip([40, 75, 90, 100, 95]) {
+            let mut bid = bidder
+                .bid(&mut rng, &mut hasher, &mut buf, value, MAX_BID)
+                .unwrap();
+            // bidder 4 shifts its sealed bid above the maximum
+            if bid.bidder == 4 {
+                bid.sealed =
+                    (bid.sealed.decompress().unwrap() + G * Scalar::from(20u64)).compress();
+                sign(&mut rng, &generators, &private_keys[3], &mut bid);
+            }
+            // bidder 5's proof does not match its shares
+            if bid.bidder == 5 {
+                bid.proof.0 += Scalar::ONE;
+                sign(&mut rng, &generators, &private_keys[4], &mut bid);
+            }
+            assert!(auction.ingest_bid(bid));
+        }
+        // reusing bidder 1's dealing to outbid it by one is ignored
+        let mut copied = auction.bids[0].1.clone();
+        copied.bidder = 6;
+        copied.sealed = (copied.sealed.decompress().unwrap() + G).compress();
+        sign(&mut rng, &generators, &private_keys[5], &mut copied);
+        assert!(!auction.ingest_bid(copied));
+        assert_eq!(auction.bids.len(), 5);
+        // nothing is opened before the bidding phase closes
+        let f0 = bidders[0].open().unwrap();
+        assert!(!auction.ingest_opening(0, f0, &mut hasher, &mut buf));
+        auction.verify_bids();
+        // nor bid once it has
+        let late = bidders[5]
+            .bid(&mut rng, &mut hasher, &mut buf, 99, MAX_BID)
+            .unwrap();
+        assert!(!auction.ingest_bid(late));
+        assert!(!auction.ingest_opening(5, f0, &mut hasher, &mut buf));
+
+        // bidder 3 refuses to open
+        for (position, bidder) in bidders.iter().enumerate().take(4) {
+            let f0 = bidder.open().unwrap();
+            if position != 2 {
+                assert!(auction.ingest_opening(position, f0, &mut hasher, &mut buf));
+            }
+        }
+        let f0 = bidders[0].open().unwrap();
+        assert!(!auction.ingest_opening(2, f0, &mut hasher, &mut buf));
+        assert_eq!(auction.withheld(), [2]);
+        assert!(auction.finalize(&mut hasher, &mut buf).is_err());
+
+        let bid = auction.bids[2].1.clone();
+        for auctioneer in auctioneers.iter_mut().take(T + 1) {
+            let share = auctioneer
+                .recover_share(&mut rng, &mut hasher, &mut buf, &bid)
+                .unwrap();
+            assert!(auction.ingest_recovery_share(2, share, &mut hasher, &mut buf));
+        }
+        // a share decrypted from another bid does not open this one
+        let wrong = auctioneers[T + 1]
+            .recover_share(&mut rng, &mut hasher, &mut buf, &auction.bids[0].1)
+            .unwrap();
+        assert!(!auction.ingest_recovery_share(2, wrong.clone(), &mut hasher, &mut buf));
+        // nor are out of range positions or invalid bids opened
+        assert!(!auction.ingest_recovery_share(5, wrong, &mut hasher, &mut buf));
+        let f0 = bidders[4].open().unwrap();
+        assert!(!auction.ingest_opening(4, f0, &mut hasher, &mut buf));
+
+        // bidder 4 bid above the maximum, bidder 5's bid is invalid
+        let transcript = auction.finalize(&mut hasher, &mut buf).unwrap();
+        assert_eq!(
+            transcript.values,
+            [Some(40), Some(75), Some(90), None, None]
+        );
+        assert_eq!(transcript.winner, Some((3, 90)));
+        assert!(transcript.verify(&generators, &roll, T, &mut hasher, &mut buf));
+
+        // a bid whose signature does not match its bidder
+        let mut tampered = transcript.clone();
+        tampered.bids.swap(0, 1);
+        tampered.bids[0].bidder = 1;
+        tampered.bids[1].bidder = 2;
+        assert!(!tampered.verify(&generators, &roll, T, &mut hasher, &mut buf));
+        let mut tampered = transcript.clone();
+        tampered.winner = Some((2, 75));
+        assert!(!tampered.verify(&generators, &roll, T, &mut hasher, &mut buf));
+        let mut tampered = transcript.clone();
+        tampered.values[1] = Some(95);
+        assert!(!tampered.verify(&generators, &roll, T, &mut hasher, &mut buf));
+        let mut tampered = transcript;
+        tampered.openings[2] = None;
+        assert!(!tampered.verify(&generators, &roll, T, &mut hasher, &mut buf));
+    }
+}
@@ -0,0 +1,83 @@
+#![allow(non_snake_case)]
+
+use auction::{auction::Auction, auctioneer::Auctioneer, bidder::Bidder};
+use common::{
+    generators::Generators,
+    key_proof::KeyProof,
+    random::{random_point, random_scalar},
+    signature::SigningKey,
+};
+use curve25519_dalek::RistrettoPoint;
+use rand::Rng;
+use std::sync::Arc;
+
+fn main() {
+    const N: usize = 64;
+    const T: usize = 31;
+    const BIDDERS: usize = 16;
+    const MAX_BID: u64 = 1000;
+
+    let mut rng = rand::rng();
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = [0u8; 64];
+
+    let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+    let generators = Arc::new(Generators::new(&G).with_pk0(&random_point(&mut rng)));
+
+    let mut auctioneers: Vec<Auctioneer> = (1..=N)
+        .map(|i| Auctioneer::new(&generators, &mut rng, N, T, i).unwrap())
+        .collect();
+    let public_keys: Vec<RistrettoPoint> = auctioneers
+        .iter()
+        .map(|auctioneer| auctioneer.party.public_key.1)
+        .collect();
+    let key_proofs: Vec<KeyProof> = auctioneers
+        .iter()
+        .map(|auctioneer| auctioneer.party.key_proof)
+        .collect();
+    let generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(&public_keys, &key_proofs)
+            .unwrap(),
+    );
+
+    let mut bidders: Vec<Bidder> = (1..=BIDDERS)
+        .map(|i| {
+            let signing_key = SigningKey::generate(&mut rng, generators.G_table());
+            Bidder::new(i, signing_key, N, T, &generators).unwrap()
+        })
+        .collect();
+    let roll: Vec<RistrettoPoint> = bidders.iter().map(|bidder| *bidder.public_key()).collect();
+    let mut auction = Auction::new(T, MAX_BID, &generators, &roll);
+    for bidder in &mut bidders {
+        let value = rng.random_range(0..=MAX_BID);
+        let bid = bidder
+            .bid(&mut rng, &mut hasher, &mut buf, value, MAX_BID)
+            .unwrap();
+        auction.ingest_bid(bid);
+    }
+    auction.verify_bids();
+
+    // the last bidder refuses to open and is forced open by t+1 auctioneers
+    for (position, bidder) in bidders.iter().enumerate().take(BIDDERS - 1) {
+        let f0 = bidder.open().unwrap();
+        assert!(auction.ingest_opening(position, f0, &mut hasher, &mut buf));
+    }
+    for position in auction.withheld() {
+        let bid = auction.bids[position].1.clone();
+        for auctioneer in auctioneers.iter_mut().take(T + 1) {
+            let share = auctioneer
+                .recover_share(&mut rng, &mut hasher, &mut buf, &bid)
+                .unwrap();
+            auction.ingest_recovery_share(position, share, &mut hasher, &mut buf);
+        }
+    }
+
+    let transcript = auction.finalize(&mut hasher, &mut buf).unwrap();
+    assert!(transcript.verify(&generators, &roll, T, &mut hasher, &mut buf));
+    println!(
+        "bids {:?}, winner {:?}",
+        transcript.values, transcript.winner
+    );
+}
@@ -0,0 +1,76 @@
+use blake3::Hasher;
+use common::generators::Generators;
+use curve25519_dalek::RistrettoPoint;
+use std::collections::HashSet;
+
+use crate::{
+    auction::decode,
+    bid::{Opening, SealedBid},
+};
+
+/// The bidder and value of the highest opened bid, the earliest one on a tie
+pub fn winner(bids: &[SealedBid], values: &[Option<u64>]) -> Option<(usize, u64)> {
+    bids.iter()
+        .zip(values)
+        .filter_map(|(bid, value)| value.map(|value| (bid.bidder, value)))
+        .rev()
+        .max_by_key(|(_, value)| *value)
+}
+
+/// Everything needed to recheck an auction's winner from the auctioneers' and bidders' public keys
+#[derive(Clone)]
+pub struct Transcript {
+    pub max_bid: u64,
+    pub bids: Vec<SealedBid>,
+    /// one per bid, None for the invalid ones
+    pub openings: Vec<Option<Opening>>,
+    /// one per bid, None for the invalid or disqualified ones
+    pub values: Vec<Option<u64>>,
+    pub winner: Option<(usize, u64)>,
+}
+
+impl Transcript {
+    /// Fails if a bid is not signed by its bidder in `bidders`, since `Auction::ingest_bid` never
+    /// takes one
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        bidders: &[RistrettoPoint],
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let mut indices = HashSet::with_capacity(self.bids.len());
+        let mut dealings = HashSet::with_capacity(self.bids.len());
+        if self.openings.len() != self.bids.len()
+            || self.values.len() != self.bids.len()
+            || !self.bids.iter().all(|bid| {
+                bid.verify_signature(generators, bidders)
+                    && indices.insert(bid.bidder)
+                    && dealings.insert(bid.encrypted_shares.first())
+            })
+        {
+            return false;
+        }
+
+        for ((bid, opening), value) in self.bids.iter().zip(&self.openings).zip(&self.values) {
+            match (bid.verify(generators, t, hasher, buf), opening) {
+                (true, Some(opening)) => {
+                    let Some(secret) = opening.verify(generators, t, bid, hasher, buf) else {
+                        return false;
+                    };
+                    // checked in SealedBid::verify
+                    let sealed = bid.sealed.decompress().unwrap();
+                    if decode(generators.G(), &sealed, &secret, self.max_bid) != *value {
+                        return false;
+                    }
+                }
+                (false, None) if value.is_none() => {}
+                // a valid bid left unopened or an invalid one counted
+                _ => return false,
+            }
+        }
+
+        self.winner == winner(&self.bids, &self.values)
+    }
+}
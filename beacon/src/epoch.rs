@@ -0,0 +1,404 @@
+use blake3::Hasher;
+use common::{
+    error::{
+        Error,
+        ErrorKind::{TooFewDealings, UnrecoverableSecret},
+    },
+    generators::Generators,
+    polynomial::Polynomial,
+    signature::Signature,
+    utils::{batch_decompress_ristretto_points, lagrange_coefficients, verify_decrypted_share},
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto, traits::Identity};
+use pi_s_pvss::utils::verify_encrypted_shares_standalone;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+use crate::transcript::{Transcript, beacon_output};
+
+/// A node's dealing for one epoch, signed with its registered key
+#[derive(Clone)]
+pub struct Dealing {
+    /// 1-based index of the dealing node
+    pub dealer: usize,
+    pub encrypted_shares: Vec<CompressedRistretto>,
+    pub proof: (Scalar, Polynomial),
+    /// over `dealing_message`
+    pub signature: Signature,
+}
+
+/// "beacon dealing" | session | epoch | dealer | encrypted_shares | d | z
+pub fn dealing_message(
+    session: &[u8; 32],
+    epoch: u64,
+    dealer: usize,
+    encrypted_shares: &[CompressedRistretto],
+    proof: &(Scalar, Polynomial),
+) -> Vec<u8> {
+    b"beacon dealing"
+        .iter()
+        .chain(session)
+        .chain(&epoch.to_le_bytes())
+        .chain(&(dealer as u64).to_le_bytes())
+        .chain(encrypted_shares.iter().flat_map(|share| share.as_bytes()))
+        .chain(proof.0.as_bytes())
+        .chain(proof.1.coefficients().iter().flat_map(|z| z.as_bytes()))
+        .copied()
+        .collect()
+}
+
+/// "beacon opening" | session | epoch | dealer | f
+pub fn opening_message(session: &[u8; 32], epoch: u64, dealer: usize, f: &Polynomial) -> Vec<u8> {
+    b"beacon opening"
+        .iter()
+        .chain(session)
+        .chain(&epoch.to_le_bytes())
+        .chain(&(dealer as u64).to_le_bytes())
+        .chain(f.coefficients().iter().flat_map(|f| f.as_bytes()))
+        .copied()
+        .collect()
+}
+
+/// The registered key of the node at 1-based `index`
+fn registered_key(generators: &Generators, index: usize) -> Option<&RistrettoPoint> {
+    index
+        .checked_sub(1)
+        .and_then(|i| generators.public_keys().get(i))
+}
+
+impl Dealing {
+    /// Whether the dealer signed this dealing for `epoch` with its registered key
+    pub fn verify_signature(&self, generators: &Generators, epoch: u64) -> bool {
+        registered_key(generators, self.dealer).is_some_and(|public_key| {
+            self.signature.verify(
+                generators.G_table(),
+                public_key,
+                &dealing_message(
+                    &generators.session(),
+                    epoch,
+                    self.dealer,
+                    &self.encrypted_shares,
+                    &self.proof,
+                ),
+            )
+        })
+    }
+
+    /// The signature, and the proof that the shares are encrypted under the registered keys
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        epoch: u64,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let (d, z) = &self.proof;
+        if z.len() != t + 1 || !self.verify_signature(generators, epoch) {
+            return false;
+        }
+        let Ok(encrypted_shares) = batch_decompress_ristretto_points(&self.encrypted_shares) else {
+            return false;
+        };
+
+        verify_encrypted_shares_standalone(
+            &(self.encrypted_shares.clone(), encrypted_shares),
+            generators.public_key_tables(),
+            (d, z),
+            hasher,
+            buf,
+        )
+        .unwrap_or(false)
+    }
+}
+
+/// A dealer's sharing polynomial, published once the dealing phase is closed
+#[derive(Clone)]
+pub struct Opening {
+    pub f: Polynomial,
+    /// over `opening_message`, by the dealer
+    pub signature: Signature,
+}
+
+impl Opening {
+    /// G * s of `dealing`, if its dealer signed this opening for `epoch` and f opens every
+    /// encrypted share
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        epoch: u64,
+        t: usize,
+        dealing: &Dealing,
+    ) -> Option<RistrettoPoint> {
+        let signed = registered_key(generators, dealing.dealer).is_some_and(|public_key| {
+            self.signature.verify(
+                generators.G_table(),
+                public_key,
+                &opening_message(&generators.session(), epoch, dealing.dealer, &self.f),
+            )
+        });
+        if !signed || self.f.len() != t + 1 {
+            return None;
+        }
+        let (_, encrypted_shares) = self.f.evaluate_multiply(generators.public_key_tables(), 1);
+        let opens = encrypted_shares.len() == dealing.encrypted_shares.len()
+            && encrypted_shares
+                .par_iter()
+                .zip(dealing.encrypted_shares.par_iter())
+                .all(|(share, published)| share.compress() == *published);
+
+        opens.then(|| generators.G_table() * &self.f.coefficients()[0])
+    }
+}
+
+/// A node's decrypted share of a dealing whose opening was withheld
+#[derive(Clone)]
+pub struct RecoveryShare {
+    /// 1-based index of the decrypting node
+    pub index: usize,
+    pub decrypted_share: CompressedRistretto,
+    pub proof: (Scalar, Scalar),
+}
+
+impl RecoveryShare {
+    pub fn verify(
+        &self,
+        G: &RistrettoPoint,
+        public_keys: &[RistrettoPoint],
+        dealing: &Dealing,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        if self.index == 0 || self.index > public_keys.len() {
+            return false;
+        }
+        let (Some(encrypted_share), Some(decrypted_share)) = (
+            dealing
+                .encrypted_shares
+                .get(self.index - 1)
+                .and_then(|share| share.decompress()),
+            self.decrypted_share.decompress(),
+        ) else {
+            return false;
+        };
+
+        verify_decrypted_share(
+            G,
+            &public_keys[self.index - 1],
+            &encrypted_share,
+            &decrypted_share,
+            &self.proof,
+            hasher,
+            buf,
+        )
+    }
+}
+
+/// How a valid dealing's secret G * s enters the beacon
+#[derive(Clone)]
+pub enum Contribution {
+    /// the dealer published its polynomial
+    Opened(Opening),
+    /// the dealer withheld it, t+1 nodes decrypted their shares instead (by increasing index)
+    Recovered(Vec<RecoveryShare>),
+}
+
+impl Contribution {
+    /// G * s of `dealing`, if the contribution opens it
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        epoch: u64,
+        t: usize,
+        dealing: &Dealing,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Option<RistrettoPoint> {
+        let (G, public_keys) = (generators.G(), generators.public_keys());
+        match self {
+            Contribution::Opened(opening) => opening.verify(generators, epoch, t, dealing),
+            Contribution::Recovered(shares) => {
+                let increasing = shares.windows(2).all(|pair| pair[0].index < pair[1].index);
+                if shares.len() != t + 1
+                    || !increasing
+                    || !shares
+                        .iter()
+                        .all(|share| share.verify(G, public_keys, dealing, hasher, buf))
+                {
+                    return None;
+                }
+                let indices: Vec<usize> = shares.iter().map(|share| share.index).collect();
+
+                Some(
+                    lagrange_coefficients(&indices)
+                        .iter()
+                        .zip(shares)
+                        // checked in RecoveryShare::verify
+                        .map(|(lambda, share)| lambda * share.decrypted_share.decompress().unwrap())
+                        .sum(),
+                )
+            }
+        }
+    }
+}
+
+/// Collects one epoch of the beacon: every node's dealing, then an opening or t+1 recovery shares
+/// for each valid one
+pub struct Epoch {
+    pub epoch: u64,
+    pub t: usize,
+    /// G and the nodes' registered public keys
+    pub generators: Arc<Generators>,
+    /// set by `verify_dealings`: dealings are only taken before, openings and recovery shares
+    /// only after
+    pub closed: bool,
+    pub dealings: Vec<(bool, Dealing)>,
+    pub openings: Vec<Option<Opening>>,
+    /// by increasing node index
+    pub recovery_shares: Vec<Vec<RecoveryShare>>,
+}
+
+impl Epoch {
+    pub fn new(epoch: u64, t: usize, generators: &Arc<Generators>) -> Self {
+        let n = generators.public_keys().len();
+        Self {
+            epoch,
+            t,
+            generators: generators.clone(),
+            closed: false,
+            dealings: Vec::with_capacity(n),
+            openings: Vec::with_capacity(n),
+            recovery_shares: Vec::with_capacity(n),
+        }
+    }
+
+    /// Keeps a dealing signed by its dealer for this epoch while the dealing phase is open. A
+    /// node's second dealing in the same epoch is ignored.
+    pub fn ingest_dealing(&mut self, dealing: Dealing) -> bool {
+        if self.closed
+            || !dealing.verify_signature(&self.generators, self.epoch)
+            || self
+                .dealings
+                .iter()
+                .any(|(_, ingested)| ingested.dealer == dealing.dealer)
+        {
+            return false;
+        }
+        self.dealings.push((false, dealing));
+        self.openings.push(None);
+        self.recovery_shares.push(vec![]);
+        true
+    }
+
+    /// Closes the dealing phase and checks every dealing's proof
+    pub fn verify_dealings(&mut self) {
+        self.closed = true;
+        let (generators, epoch, t) = (&self.generators, self.epoch, self.t);
+        self.dealings.par_iter_mut().for_each_init(
+            || (Hasher::new(), [0u8; 64]),
+            |(hasher, buf), (status, dealing)| {
+                *status = dealing.verify(generators, epoch, t, hasher, buf);
+            },
+        );
+    }
+
+    /// Positions of the valid dealings whose dealer has not opened yet, the nodes recover these
+    pub fn withheld(&self) -> Vec<usize> {
+        self.dealings
+            .iter()
+            .zip(&self.openings)
+            .enumerate()
+            .filter(|(_, ((status, _), opening))| *status && opening.is_none())
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Keeps the opening of the dealing at `position` only once the dealing phase is closed, and
+    /// only if its dealer signed it and it matches the encrypted shares
+    pub fn ingest_opening(&mut self, position: usize, opening: Opening) -> bool {
+        let Some((_, dealing)) = self.dealings.get(position) else {
+            return false;
+        };
+        let opens = self.closed
+            && opening
+                .verify(&self.generators, self.epoch, self.t, dealing)
+                .is_some();
+        if opens {
+            self.openings[position] = Some(opening);
+        }
+        opens
+    }
+
+    /// Keeps a node's share of the dealing at `position` only if its proof holds
+    pub fn ingest_recovery_share(
+        &mut self,
+        position: usize,
+        share: RecoveryShare,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        if !self.closed || position >= self.dealings.len() {
+            return false;
+        }
+        let shares = &self.recovery_shares[position];
+        let Err(slot) = shares.binary_search_by_key(&share.index, |share| share.index) else {
+            return false;
+        };
+        let holds = share.verify(
+            self.generators.G(),
+            self.generators.public_keys(),
+            &self.dealings[position].1,
+            hasher,
+            buf,
+        );
+        if holds {
+            self.recovery_shares[position].insert(slot, share);
+        }
+        holds
+    }
+
+    /// Combines the secret of every valid dealing into the beacon output. Needs more than t valid
+    /// dealings, so at least one comes from an honest node.
+    pub fn finalize(&self, hasher: &mut Hasher, buf: &mut [u8; 64]) -> Result<Transcript, Error> {
+        let valid = self.dealings.iter().filter(|(status, _)| *status).count();
+        if valid <= self.t {
+            return Err(TooFewDealings(valid, self.t + 1).into());
+        }
+
+        let mut combined = RistrettoPoint::identity();
+        let mut contributions = Vec::with_capacity(self.dealings.len());
+        for (((status, dealing), opening), shares) in self
+            .dealings
+            .iter()
+            .zip(&self.openings)
+            .zip(&self.recovery_shares)
+        {
+            if !status {
+                contributions.push(None);
+                continue;
+            }
+            let contribution = match opening {
+                Some(opening) => Contribution::Opened(opening.clone()),
+                None if shares.len() > self.t => {
+                    Contribution::Recovered(shares[..=self.t].to_vec())
+                }
+                None => return Err(UnrecoverableSecret(dealing.dealer).into()),
+            };
+            combined += contribution
+                .verify(&self.generators, self.epoch, self.t, dealing, hasher, buf)
+                .ok_or_else(|| Error::from(UnrecoverableSecret(dealing.dealer)))?;
+            contributions.push(Some(contribution));
+        }
+
+        Ok(Transcript {
+            epoch: self.epoch,
+            dealings: self
+                .dealings
+                .iter()
+                .map(|(_, dealing)| dealing.clone())
+                .collect(),
+            contributions,
+            output: beacon_output(self.epoch, &combined),
+        })
+    }
+}
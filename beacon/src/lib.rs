@@ -0,0 +1,217 @@
+#![allow(non_snake_case)]
+
+pub mod epoch;
+pub mod node;
+pub mod sortition;
+pub mod transcript;
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        generators::Generators, key_proof::KeyProof, random::random_scalar, signature::SigningKey,
+    };
+    use curve25519_dalek::{RistrettoPoint, Scalar, traits::Identity};
+    use std::sync::Arc;
+
+    use crate::{
+        epoch::{Epoch, Opening, dealing_message},
+        node::Node,
+        sortition::{sample, verify_sample},
+        transcript::beacon_output,
+    };
+
+    #[test]
+    fn beacon() {
+        const N: usize = 7;
+        const T: usize = 3;
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+
+        let generators = Arc::new(Generators::new(&G));
+        let mut nodes: Vec<Node> = (1..=N)
+            .map(|i| Node::new(&generators, &mut rng, N, T, i).unwrap())
+            .collect();
+        let public_keys: Vec<RistrettoPoint> =
+            nodes.iter().map(|node| node.party.public_key.1).collect();
+        let key_proofs: Vec<KeyProof> = nodes.iter().map(|node| node.party.key_proof).collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+        for node in &mut nodes {
+            node.ingest_registered_keys(&generators).unwrap();
+        }
+        let G_table = generators.G_table();
+
+        let mut epoch = Epoch::new(1, T, &generators);
+        // a dealing signed for another epoch, or by another node, does not take the dealer's slot
+        assert!(!epoch.ingest_dealing(nodes[0].deal(&mut rng, &mut hasher, &mut buf, 2).unwrap()));
+        let mut forged = nodes[1].deal(&mut rng, &mut hasher, &mut buf, 1).unwrap();
+        forged.dealer = 1;
+        assert!(!epoch.ingest_dealing(forged));
+        // the last node deals too late
+        for node in &mut nodes[..N - 1] {
+            assert!(epoch.ingest_dealing(node.deal(&mut rng, &mut hasher, &mut buf, 1).unwrap()));
+        }
+        // node 2 signs a proof that does not match its shares
+        let dealing = &mut epoch.dealings[1].1;
+        dealing.proof.0 += Scalar::ONE;
+        dealing.signature = SigningKey::from_private_key(nodes[1].party.private_key(), G_table)
+            .sign(
+                &mut rng,
+                G_table,
+                &dealing_message(
+                    &generators.session(),
+                    1,
+                    2,
+                    &dealing.encrypted_shares,
+                    &dealing.proof,
+                ),
+            );
+
+        let openings: Vec<Opening> = nodes[..N - 1]
+            .iter_mut()
+            .map(|node| node.open(&mut rng).unwrap())
+            .collect();
+        assert!(nodes[0].open(&mut rng).is_err());
+        // nothing is opened while dealings can still come in, and nothing is dealt after
+        assert!(!epoch.ingest_opening(0, openings[0].clone()));
+        epoch.verify_dealings();
+        let late = nodes[N - 1]
+            .deal(&mut rng, &mut hasher, &mut buf, 1)
+            .unwrap();
+        assert!(!epoch.ingest_dealing(late));
+
+        // node 3 withholds its opening
+        let mut expected = RistrettoPoint::identity();
+        for (position, opening) in openings
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| *position != 1)
+        {
+            expected += G * opening.f.coefficients()[0];
+            if position != 2 {
+                assert!(epoch.ingest_opening(position, opening.clone()));
+            }
+        }
+        assert!(!epoch.ingest_opening(2, openings[0].clone()));
+        // node 3's polynomial only counts under its own signature
+        let mut forged = openings[2].clone();
+        forged.signature = openings[0].signature;
+        assert!(!epoch.ingest_opening(2, forged));
+        assert_eq!(epoch.withheld(), [2]);
+        assert!(epoch.finalize(&mut hasher, &mut buf).is_err());
+
+        let dealing = epoch.dealings[2].1.clone();
+        for node in nodes.iter_mut().take(T + 1) {
+            let share = node
+                .recover_share(&mut rng, &mut hasher, &mut buf, &dealing)
+                .unwrap();
+            assert!(epoch.ingest_recovery_share(2, share, &mut hasher, &mut buf));
+        }
+        // a share decrypted from another dealing does not recover this one
+        let mut wrong = nodes[T + 1]
+            .recover_share(&mut rng, &mut hasher, &mut buf, &epoch.dealings[0].1)
+            .unwrap();
+        assert!(!epoch.ingest_recovery_share(2, wrong.clone(), &mut hasher, &mut buf));
+        wrong.index = 1;
+        assert!(!epoch.ingest_recovery_share(2, wrong, &mut hasher, &mut buf));
+
+        let transcript = epoch.finalize(&mut hasher, &mut buf).unwrap();
+        assert_eq!(transcript.output, beacon_output(1, &expected));
+        assert_eq!(
+            transcript.secret(&generators, T, &mut hasher, &mut buf),
+            Some(expected)
+        );
+        assert!(transcript.verify(&generators, T, &mut hasher, &mut buf));
+
+        // anyone holding the transcript can check a selection drawn from its secret
+        let candidates: Vec<[u8; 32]> = public_keys
+            .iter()
+            .map(|public_key| public_key.compress().to_bytes())
+            .collect();
+        let audited = sample(&expected, b"spot-check", &candidates, 3).unwrap();
+        let mut verify =
+            |transcript, context: &[u8], candidates: &[[u8; 32]], selection: &[usize]| {
+                verify_sample(
+                    transcript,
+                    &generators,
+                    T,
+                    context,
+                    candidates,
+                    selection,
+                    &mut hasher,
+                    &mut buf,
+                )
+            };
+        assert!(verify(&transcript, b"spot-check", &candidates, &audited));
+        let mut swapped = audited.clone();
+        swapped.swap(0, 1);
+        assert!(!verify(&transcript, b"spot-check", &candidates, &swapped));
+        assert!(!verify(&transcript, b"audit", &candidates, &audited));
+        assert!(!verify(
+            &transcript,
+            b"spot-check",
+            &candidates[1..],
+            &audited
+        ));
+
+        let mut tampered = transcript.clone();
+        tampered.output[0] ^= 1;
+        assert!(!verify(&tampered, b"spot-check", &candidates, &audited));
+        assert!(!tampered.verify(&generators, T, &mut hasher, &mut buf));
+        // leaving out the recovered dealing would let node 3 bias the output
+        let mut tampered = transcript.clone();
+        tampered.contributions[2] = None;
+        assert!(!tampered.verify(&generators, T, &mut hasher, &mut buf));
+        // nor can a dealing be attributed to another node
+        let mut tampered = transcript.clone();
+        tampered.dealings[0].signature = tampered.dealings[3].signature;
+        assert!(!tampered.verify(&generators, T, &mut hasher, &mut buf));
+    }
+
+    #[test]
+    fn sortition() {
+        let mut rng = rand::rng();
+
+        let secret = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+        let ballots: Vec<String> = (0..20).map(|i| format!("ballot {i}")).collect();
+
+        let audited = sample(&secret, b"audit", &ballots, 5).unwrap();
+        let mut distinct = audited.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert!(distinct.len() == 5 && distinct.iter().all(|i| *i < ballots.len()));
+        assert_eq!(sample(&secret, b"audit", &ballots, 5).unwrap(), audited);
+        // a smaller sample is a prefix of a larger one
+        assert_eq!(
+            sample(&secret, b"audit", &ballots, 3).unwrap(),
+            audited[..3]
+        );
+
+        // the context and every candidate are bound into the draw
+        assert_ne!(
+            sample(&secret, b"spot-check", &ballots, 5).unwrap(),
+            audited
+        );
+        assert_ne!(
+            sample(&secret, b"audit", &ballots[1..], 5).unwrap(),
+            audited
+        );
+        assert!(sample(&secret, b"audit", &ballots, ballots.len() + 1).is_err());
+
+        // every candidate is drawn about equally often
+        let mut counts = [0usize; 4];
+        for _ in 0..4000 {
+            let secret = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+            counts[sample(&secret, b"", &ballots[..4], 1).unwrap()[0]] += 1;
+        }
+        assert!(counts.iter().all(|count| (800..1200).contains(count)));
+    }
+}
@@ -0,0 +1,84 @@
+#![allow(non_snake_case)]
+
+use beacon::{
+    epoch::Epoch,
+    node::Node,
+    sortition::{sample, verify_sample},
+};
+use common::{generators::Generators, key_proof::KeyProof, random::random_scalar};
+use curve25519_dalek::RistrettoPoint;
+use std::sync::Arc;
+
+fn main() {
+    const N: usize = 64;
+    const T: usize = 31;
+
+    let mut rng = rand::rng();
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = [0u8; 64];
+
+    let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+
+    let generators = Arc::new(Generators::new(&G));
+    let mut nodes: Vec<Node> = (1..=N)
+        .map(|i| Node::new(&generators, &mut rng, N, T, i).unwrap())
+        .collect();
+    let public_keys: Vec<RistrettoPoint> =
+        nodes.iter().map(|node| node.party.public_key.1).collect();
+    let key_proofs: Vec<KeyProof> = nodes.iter().map(|node| node.party.key_proof).collect();
+    let generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(&public_keys, &key_proofs)
+            .unwrap(),
+    );
+    for node in &mut nodes {
+        node.ingest_registered_keys(&generators).unwrap();
+    }
+
+    for e in 0..3 {
+        let mut epoch = Epoch::new(e, T, &generators);
+        for node in &mut nodes {
+            epoch.ingest_dealing(node.deal(&mut rng, &mut hasher, &mut buf, e).unwrap());
+        }
+        epoch.verify_dealings();
+
+        // the first node never opens, the others recover its secret
+        for (position, node) in nodes.iter_mut().enumerate().skip(1) {
+            epoch.ingest_opening(position, node.open(&mut rng).unwrap());
+        }
+        for position in epoch.withheld() {
+            let dealing = epoch.dealings[position].1.clone();
+            for node in nodes.iter_mut().skip(1).take(T + 1) {
+                let share = node
+                    .recover_share(&mut rng, &mut hasher, &mut buf, &dealing)
+                    .unwrap();
+                epoch.ingest_recovery_share(position, share, &mut hasher, &mut buf);
+            }
+        }
+
+        let transcript = epoch.finalize(&mut hasher, &mut buf).unwrap();
+        let secret = transcript
+            .secret(&generators, T, &mut hasher, &mut buf)
+            .unwrap();
+        println!("epoch {e}: {:x?}", transcript.output);
+
+        // spot-check 3 of the nodes
+        let candidates: Vec<[u8; 32]> = public_keys
+            .iter()
+            .map(|public_key| public_key.compress().to_bytes())
+            .collect();
+        let audited = sample(&secret, b"spot-check", &candidates, 3).unwrap();
+        assert!(verify_sample(
+            &transcript,
+            &generators,
+            T,
+            b"spot-check",
+            &candidates,
+            &audited,
+            &mut hasher,
+            &mut buf
+        ));
+        println!("epoch {e}: spot-checking nodes {audited:?}");
+    }
+}
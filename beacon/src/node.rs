@@ -0,0 +1,138 @@
+use blake3::Hasher;
+use common::{
+    error::{Error, ErrorKind::UninitializedValue},
+    generators::Generators,
+    polynomial::Polynomial,
+    random::random_scalar,
+    secret::Secret,
+    signature::SigningKey,
+};
+use pi_s_pvss::{dealer::Dealer, party::Party};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+use crate::epoch::{Dealing, Opening, RecoveryShare, dealing_message, opening_message};
+
+/// A beacon participant: deals a fresh secret every epoch and decrypts its share of the dealings
+/// whose opening was withheld
+pub struct Node {
+    pub party: Party,
+    dealer: Option<Dealer>,
+    /// the party's registered key, dealings and openings are signed with it
+    signing_key: SigningKey,
+    /// the epoch and sharing polynomial of the last dealing, until it is opened
+    opening: Option<(u64, Secret<Polynomial>)>,
+}
+
+impl Node {
+    pub fn new<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        n: usize,
+        t: usize,
+        index: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let party = Party::new(generators, rng, n, t, index)?;
+        let signing_key = SigningKey::from_private_key(party.private_key(), generators.G_table());
+        Ok(Self {
+            party,
+            dealer: None,
+            signing_key,
+            opening: None,
+        })
+    }
+
+    /// The generators every node's public key was registered with, own included
+    pub fn ingest_registered_keys(&mut self, generators: &Arc<Generators>) -> Result<(), Error> {
+        self.party.ingest_registered_keys(generators)?;
+        self.dealer = Some(Dealer::new(self.party.n, self.party.t, generators)?);
+        Ok(())
+    }
+
+    /// Deals a fresh secret for `epoch`, keeping its polynomial until `open`
+    pub fn deal<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        epoch: u64,
+    ) -> Result<Dealing, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let dealer = self
+            .dealer
+            .as_mut()
+            .ok_or_else(|| Error::from(UninitializedValue("node.dealer")))?;
+        let secret = Secret::new(random_scalar(rng));
+        let (encrypted_shares, proof, f) =
+            dealer.deal_secret_with_opening(rng, hasher, buf, secret.expose());
+        self.opening = Some((epoch, f));
+
+        let G = self.party.generators.G_table();
+        let message = dealing_message(
+            &self.party.generators.session(),
+            epoch,
+            self.party.index,
+            &encrypted_shares,
+            &proof,
+        );
+        Ok(Dealing {
+            dealer: self.party.index,
+            encrypted_shares,
+            proof,
+            signature: self.signing_key.sign(rng, G, &message),
+        })
+    }
+
+    /// Opens the last dealing once the dealing phase is closed, the polynomial is not kept after
+    pub fn open<R>(&mut self, rng: &mut R) -> Result<Opening, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let (epoch, f) = self
+            .opening
+            .take()
+            .ok_or_else(|| Error::from(UninitializedValue("node.opening")))?;
+        let f = f.expose().clone();
+
+        let G = self.party.generators.G_table();
+        let message = opening_message(
+            &self.party.generators.session(),
+            epoch,
+            self.party.index,
+            &f,
+        );
+        Ok(Opening {
+            signature: self.signing_key.sign(rng, G, &message),
+            f,
+        })
+    }
+
+    /// Decrypts this node's share of `dealing` with a DLEQ proof
+    pub fn recover_share<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        dealing: &Dealing,
+    ) -> Result<RecoveryShare, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        self.party
+            .ingest_encrypted_shares(&dealing.encrypted_shares)?;
+        self.party.decrypt_share()?;
+        self.party.dleq_share(rng, hasher, buf)?;
+
+        Ok(RecoveryShare {
+            index: self.party.index,
+            // both set above
+            decrypted_share: self.party.decrypted_share.unwrap().compress(),
+            proof: self.party.share_proof.unwrap(),
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use blake3::{Hasher, OutputReader};
+use common::{
+    error::{Error, ErrorKind::SampleTooLarge},
+    generators::Generators,
+};
+use curve25519_dalek::RistrettoPoint;
+
+use crate::transcript::Transcript;
+
+/// "sortition" | context | every candidate | G * s, each length-prefixed
+fn stream<T: AsRef<[u8]>>(
+    secret: &RistrettoPoint,
+    context: &[u8],
+    candidates: &[T],
+) -> OutputReader {
+    let mut hasher = Hasher::new();
+    hasher.update(b"sortition");
+    hasher.update(&(context.len() as u64).to_le_bytes());
+    hasher.update(context);
+    hasher.update(&(candidates.len() as u64).to_le_bytes());
+    for candidate in candidates {
+        let candidate = candidate.as_ref();
+        hasher.update(&(candidate.len() as u64).to_le_bytes());
+        hasher.update(candidate);
+    }
+    hasher.update(secret.compress().as_bytes());
+
+    hasher.finalize_xof()
+}
+
+/// Uniform in [0, bound), rejecting the words above the largest multiple of `bound`
+fn uniform(stream: &mut OutputReader, bound: usize) -> usize {
+    let bound = bound as u64;
+    let limit = u64::MAX - u64::MAX % bound;
+    let mut word = [0u8; 8];
+    loop {
+        stream.fill(&mut word);
+        let x = u64::from_le_bytes(word);
+        if x < limit {
+            return (x % bound) as usize;
+        }
+    }
+}
+
+/// Positions of k distinct candidates drawn uniformly at random from a reconstructed secret,
+/// e.g. a beacon epoch's `Transcript::secret`. The candidates and the context are bound into the
+/// draw, and a smaller k yields a prefix of a larger one.
+pub fn sample<T: AsRef<[u8]>>(
+    secret: &RistrettoPoint,
+    context: &[u8],
+    candidates: &[T],
+    k: usize,
+) -> Result<Vec<usize>, Error> {
+    let n = candidates.len();
+    if k > n {
+        return Err(SampleTooLarge(k, n).into());
+    }
+
+    // partial Fisher-Yates shuffle of the first k positions
+    let mut stream = stream(secret, context, candidates);
+    let mut positions: Vec<usize> = (0..n).collect();
+    for i in 0..k {
+        let j = i + uniform(&mut stream, n - i);
+        positions.swap(i, j);
+    }
+    positions.truncate(k);
+
+    Ok(positions)
+}
+
+/// Checks that `selection` is the sample of its size drawn from the secret of `transcript`, which
+/// must verify against the generators the nodes' public keys were registered with
+#[allow(clippy::too_many_arguments)]
+pub fn verify_sample<T: AsRef<[u8]>>(
+    transcript: &Transcript,
+    generators: &Generators,
+    t: usize,
+    context: &[u8],
+    candidates: &[T],
+    selection: &[usize],
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+) -> bool {
+    transcript
+        .secret(generators, t, hasher, buf)
+        .is_some_and(|secret| {
+            sample(&secret, context, candidates, selection.len())
+                .is_ok_and(|sample| sample == selection)
+        })
+}
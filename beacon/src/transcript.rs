@@ -0,0 +1,80 @@
+use blake3::Hasher;
+use common::generators::Generators;
+use curve25519_dalek::{RistrettoPoint, traits::Identity};
+use std::collections::HashSet;
+
+use crate::epoch::{Contribution, Dealing};
+
+/// "beacon" | epoch | sum of the valid dealings' G * s
+pub fn beacon_output(epoch: u64, combined: &RistrettoPoint) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(b"beacon");
+    hasher.update(&epoch.to_le_bytes());
+    hasher.update(combined.compress().as_bytes());
+
+    *hasher.finalize().as_bytes()
+}
+
+/// Everything needed to recompute an epoch's output from the nodes' public keys
+#[derive(Clone)]
+pub struct Transcript {
+    pub epoch: u64,
+    pub dealings: Vec<Dealing>,
+    /// one per dealing, None for the invalid ones
+    pub contributions: Vec<Option<Contribution>>,
+    pub output: [u8; 32],
+}
+
+impl Transcript {
+    /// Checks against the generators the nodes' public keys were registered with
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        self.secret(generators, t, hasher, buf).is_some()
+    }
+
+    /// The sum of every valid dealing's G * s, if the transcript verifies
+    pub fn secret(
+        &self,
+        generators: &Generators,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Option<RistrettoPoint> {
+        let public_keys = generators.public_keys();
+        let mut dealers = HashSet::with_capacity(self.dealings.len());
+        if self.contributions.len() != self.dealings.len()
+            || !self.dealings.iter().all(|dealing| {
+                (1..=public_keys.len()).contains(&dealing.dealer) && dealers.insert(dealing.dealer)
+            })
+        {
+            return None;
+        }
+
+        let mut combined = RistrettoPoint::identity();
+        let mut valid = 0;
+        for (dealing, contribution) in self.dealings.iter().zip(&self.contributions) {
+            match (
+                dealing.verify(generators, self.epoch, t, hasher, buf),
+                contribution,
+            ) {
+                (true, Some(contribution)) => {
+                    match contribution.verify(generators, self.epoch, t, dealing, hasher, buf) {
+                        Some(secret) => combined += secret,
+                        None => return None,
+                    }
+                    valid += 1;
+                }
+                (false, None) => {}
+                // a valid dealing left out or an invalid one counted
+                _ => return None,
+            }
+        }
+
+        (valid > t && self.output == beacon_output(self.epoch, &combined)).then_some(combined)
+    }
+}
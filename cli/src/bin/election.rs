@@ -0,0 +1,62 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+use cli::{
+    election::{Backend, Ballots, ElectionConfig, run_election},
+    encoding::read_toml,
+    error::{Error, ErrorKind::InvalidConfig},
+};
+
+/// Run a complete yes/no election described by a TOML config
+#[derive(Parser)]
+#[command(name = "election")]
+struct Cli {
+    /// Election config: question, candidates, scheme, talliers, threshold and voter roll
+    #[arg(long)]
+    config: PathBuf,
+    /// Ballots to cast, as `[[ballot]]` tables with `voter` and `choice`
+    #[arg(long)]
+    ballots: PathBuf,
+    /// Directory receiving election.json, ballots.json, tally.json and result.json
+    #[arg(long)]
+    out_dir: PathBuf,
+    /// Overrides the scheme set in the config
+    #[arg(long, value_enum)]
+    scheme: Option<Backend>,
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
+    let config: ElectionConfig = read_toml(&cli.config)?;
+    config.validate()?;
+    let ballots: Ballots = read_toml(&cli.ballots)?;
+    let scheme = cli
+        .scheme
+        .or(config.scheme)
+        .ok_or_else(|| Error::from(InvalidConfig("no scheme selected".into())))?;
+
+    let result = run_election(&config, scheme, &ballots, &cli.out_dir, &mut rand::rng())?;
+
+    println!("{}", result.question);
+    for count in &result.counts {
+        println!("  {}: {}", count.candidate, count.votes);
+    }
+    println!(
+        "{} valid, {} rejected ballots",
+        result.valid_ballots, result.rejected_ballots
+    );
+    match &result.winner {
+        Some(winner) => println!("winner: {winner}"),
+        None => println!("tie"),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
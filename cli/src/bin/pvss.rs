@@ -0,0 +1,242 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use cli::{
+    encoding::{read_json, write_json},
+    error::Error,
+    messages::{DealerSecret, Dealing, DecryptedShare, Params, PublicKey, Scheme, SecretKey},
+    pvss,
+};
+
+/// Run the steps of a PVSS scheme by exchanging JSON files
+#[derive(Parser)]
+#[command(name = "pvss")]
+struct Cli {
+    /// PVSS scheme every file of this run belongs to
+    #[arg(long, value_enum)]
+    scheme: Scheme,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Sample the public parameters (generators, n and t)
+    Setup {
+        #[arg(short)]
+        n: usize,
+        #[arg(short)]
+        t: usize,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Generate the key pair of the party at `index`
+    Keygen {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        index: usize,
+        #[arg(long)]
+        secret_key: PathBuf,
+        #[arg(long)]
+        public_key: PathBuf,
+    },
+    /// Deal a random secret to the registered parties
+    Deal {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long, num_args = 1.., required = true)]
+        public_keys: Vec<PathBuf>,
+        #[arg(long)]
+        out: PathBuf,
+        /// Where to keep the dealt secret (published as f(0) for pi_s_ppvss)
+        #[arg(long)]
+        secret_out: Option<PathBuf>,
+    },
+    /// Publicly verify a dealing
+    VerifyDealing {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long, num_args = 1.., required = true)]
+        public_keys: Vec<PathBuf>,
+        #[arg(long)]
+        dealing: PathBuf,
+    },
+    /// Verify a dealing, then decrypt the own share and prove correct decryption
+    DecryptShare {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long, num_args = 1.., required = true)]
+        public_keys: Vec<PathBuf>,
+        #[arg(long)]
+        secret_key: PathBuf,
+        #[arg(long)]
+        dealing: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Publicly verify decrypted shares, printing the valid indices
+    VerifyDecryptions {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long, num_args = 1.., required = true)]
+        public_keys: Vec<PathBuf>,
+        #[arg(long)]
+        dealing: PathBuf,
+        #[arg(long, num_args = 1.., required = true)]
+        shares: Vec<PathBuf>,
+    },
+    /// Reconstruct G * secret from t+1 valid decrypted shares
+    Reconstruct {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long, num_args = 1.., required = true)]
+        public_keys: Vec<PathBuf>,
+        #[arg(long)]
+        dealing: PathBuf,
+        #[arg(long, num_args = 0..)]
+        shares: Vec<PathBuf>,
+        /// The dealer's published f(0), tried before the shares (pi_s_ppvss only)
+        #[arg(long)]
+        f0: Option<PathBuf>,
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+fn read_all<T: serde::de::DeserializeOwned>(paths: &[PathBuf]) -> Result<Vec<T>, Error> {
+    paths.iter().map(|path| read_json(path)).collect()
+}
+
+fn read_params(scheme: Scheme, path: &Path) -> Result<Params, Error> {
+    let params: Params = read_json(path)?;
+    scheme.check(params.scheme)?;
+    Ok(params)
+}
+
+fn run(cli: Cli) -> Result<bool, Error> {
+    let mut rng = rand::rng();
+    let scheme = cli.scheme;
+
+    match cli.command {
+        Command::Setup { n, t, out } => {
+            write_json(&out, &pvss::setup(scheme, n, t, &mut rng)?)?;
+        }
+        Command::Keygen {
+            params,
+            index,
+            secret_key,
+            public_key,
+        } => {
+            let params = read_params(scheme, &params)?;
+            let (sk, pk) = pvss::keygen(&params, index, &mut rng)?;
+            write_json(&secret_key, &sk)?;
+            write_json(&public_key, &pk)?;
+        }
+        Command::Deal {
+            params,
+            public_keys,
+            out,
+            secret_out,
+        } => {
+            let params = read_params(scheme, &params)?;
+            let public_keys: Vec<PublicKey> = read_all(&public_keys)?;
+            let (dealing, secret) = pvss::deal(&params, &public_keys, &mut rng)?;
+            write_json(&out, &dealing)?;
+            if let Some(secret_out) = secret_out {
+                write_json(&secret_out, &secret)?;
+            }
+        }
+        Command::VerifyDealing {
+            params,
+            public_keys,
+            dealing,
+        } => {
+            let params = read_params(scheme, &params)?;
+            let public_keys: Vec<PublicKey> = read_all(&public_keys)?;
+            let dealing: Dealing = read_json(&dealing)?;
+            let valid = pvss::verify_dealing(&params, &public_keys, &dealing)?;
+            println!("{}", if valid { "valid" } else { "invalid" });
+            return Ok(valid);
+        }
+        Command::DecryptShare {
+            params,
+            public_keys,
+            secret_key,
+            dealing,
+            out,
+        } => {
+            let params = read_params(scheme, &params)?;
+            let public_keys: Vec<PublicKey> = read_all(&public_keys)?;
+            let secret_key: SecretKey = read_json(&secret_key)?;
+            let dealing: Dealing = read_json(&dealing)?;
+            let share =
+                pvss::decrypt_share(&params, &public_keys, &secret_key, &dealing, &mut rng)?;
+            write_json(&out, &share)?;
+        }
+        Command::VerifyDecryptions {
+            params,
+            public_keys,
+            dealing,
+            shares,
+        } => {
+            let params = read_params(scheme, &params)?;
+            let public_keys: Vec<PublicKey> = read_all(&public_keys)?;
+            let dealing: Dealing = read_json(&dealing)?;
+            let shares: Vec<DecryptedShare> = read_all(&shares)?;
+            let valid = pvss::verify_decryptions(&params, &public_keys, &dealing, &shares)?;
+            println!(
+                "{}",
+                valid
+                    .iter()
+                    .map(|index| index.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            );
+            return Ok(valid.len() > params.t);
+        }
+        Command::Reconstruct {
+            params,
+            public_keys,
+            dealing,
+            shares,
+            f0,
+            out,
+        } => {
+            let params = read_params(scheme, &params)?;
+            let public_keys: Vec<PublicKey> = read_all(&public_keys)?;
+            let dealing: Dealing = read_json(&dealing)?;
+            let shares: Vec<DecryptedShare> = read_all(&shares)?;
+            let f0: Option<DealerSecret> = f0.map(|f0| read_json(&f0)).transpose()?;
+            let secret = pvss::reconstruct(&params, &public_keys, &dealing, &shares, f0.as_ref())?;
+            if !secret.skipped.is_empty() {
+                eprintln!(
+                    "skipped invalid decrypted shares of parties {}",
+                    secret
+                        .skipped
+                        .iter()
+                        .map(|index| index.to_string())
+                        .collect::<Vec<String>>()
+                        .join(",")
+                );
+            }
+            write_json(&out, &secret)?;
+        }
+    }
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,793 @@
+use std::{collections::HashSet, fs, path::Path, sync::Arc};
+
+use blake3::Hasher;
+use clap::ValueEnum;
+use common::{
+    generators::Generators,
+    key_proof::KeyProof,
+    random::random_point,
+    utils::{decompress_ristretto_point, lagrange_coefficients},
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    encoding::{bytes, option_point, point, points, scalar, scalars, write_json},
+    error::{
+        Error,
+        ErrorKind::{InsufficientShares, InvalidBallot, InvalidConfig, UndecodableTally},
+    },
+    messages::KeyProofRecord,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum Backend {
+    #[serde(rename = "evoting_pi_s_ppvss")]
+    #[value(name = "evoting_pi_s_ppvss")]
+    PiSPpvss,
+    #[serde(rename = "evoting_schoenmakers")]
+    #[value(name = "evoting_schoenmakers")]
+    Schoenmakers,
+}
+
+/// Election description read from TOML
+#[derive(Clone, Debug, Deserialize)]
+pub struct ElectionConfig {
+    pub question: String,
+    /// Exactly two: a ballot for `candidates[1]` encrypts 1, a ballot for `candidates[0]` encrypts 0
+    pub candidates: Vec<String>,
+    pub scheme: Option<Backend>,
+    pub talliers: Vec<String>,
+    pub threshold: usize,
+    pub voters: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Ballots {
+    #[serde(rename = "ballot", default)]
+    pub ballots: Vec<Ballot>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Ballot {
+    pub voter: String,
+    pub choice: String,
+}
+
+impl ElectionConfig {
+    pub fn validate(&self) -> Result<(), Error> {
+        let n = self.talliers.len();
+
+        // ballots are 0/1 votes tallied as a single sum, so only yes/no elections are supported
+        if self.candidates.len() != 2 {
+            Err(InvalidConfig(format!(
+                "exactly two candidates are supported, found {}",
+                self.candidates.len()
+            ))
+            .into())
+        } else if self.candidates[0] == self.candidates[1] {
+            Err(InvalidConfig("the two candidates must be distinct".into()).into())
+        } else if n < 2 || self.threshold != (n - 1) / 2 {
+            Err(InvalidConfig(format!(
+                "{} talliers require a threshold of {}",
+                n,
+                n.saturating_sub(1) / 2
+            ))
+            .into())
+        } else if self.talliers.iter().collect::<HashSet<_>>().len() != n {
+            Err(InvalidConfig("tallier names must be unique".into()).into())
+        } else if self.voters.iter().collect::<HashSet<_>>().len() != self.voters.len() {
+            Err(InvalidConfig("the voter roll lists a voter twice".into()).into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Maps each ballot to its 0/1 choice, allowing one ballot per voter on the roll
+    pub fn choices(&self, ballots: &Ballots) -> Result<Vec<(String, bool)>, Error> {
+        let roll: HashSet<&String> = self.voters.iter().collect();
+        let mut voted: HashSet<&String> = HashSet::new();
+
+        ballots
+            .ballots
+            .iter()
+            .map(|ballot| {
+                if !roll.contains(&ballot.voter) {
+                    Err(InvalidBallot(ballot.voter.clone(), "not on the voter roll".into()).into())
+                } else if !voted.insert(&ballot.voter) {
+                    Err(InvalidBallot(ballot.voter.clone(), "voted twice".into()).into())
+                } else if ballot.choice == self.candidates[1] {
+                    Ok((ballot.voter.clone(), true))
+                } else if ballot.choice == self.candidates[0] {
+                    Ok((ballot.voter.clone(), false))
+                } else {
+                    Err(InvalidBallot(
+                        ballot.voter.clone(),
+                        format!("'{}' is not a candidate", ballot.choice),
+                    )
+                    .into())
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TallierRecord {
+    pub name: String,
+    pub index: usize,
+    #[serde(with = "point")]
+    pub public_key: CompressedRistretto,
+    pub key_proof: KeyProofRecord,
+}
+
+/// election.json: everything needed to verify the published ballots and tally
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ElectionRecord {
+    pub question: String,
+    pub candidates: Vec<String>,
+    pub scheme: Backend,
+    pub n: usize,
+    pub t: usize,
+    #[serde(with = "point")]
+    pub G: CompressedRistretto,
+    #[serde(
+        with = "option_point",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub H: Option<CompressedRistretto>,
+    #[serde(
+        with = "option_point",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pk0: Option<CompressedRistretto>,
+    pub talliers: Vec<TallierRecord>,
+    pub voters: Vec<String>,
+}
+
+/// One entry of ballots.json; `z` is the dealer's proof polynomial (pi_s_ppvss) or response vector (schoenmakers)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BallotRecord {
+    pub voter: String,
+    #[serde(with = "points")]
+    pub encrypted_shares: Vec<CompressedRistretto>,
+    #[serde(with = "scalar")]
+    pub d: Scalar,
+    #[serde(with = "scalars")]
+    pub z: Vec<Scalar>,
+    #[serde(with = "points", default, skip_serializing_if = "Vec::is_empty")]
+    pub commitments: Vec<CompressedRistretto>,
+    #[serde(with = "point")]
+    pub encrypted_vote: CompressedRistretto,
+    #[serde(with = "bytes")]
+    pub vote_proof: Vec<u8>,
+    pub valid: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecryptedShareRecord {
+    pub tallier: String,
+    pub index: usize,
+    #[serde(with = "point")]
+    pub decrypted_share: CompressedRistretto,
+    #[serde(with = "scalar")]
+    pub d: Scalar,
+    #[serde(with = "scalar")]
+    pub z: Scalar,
+}
+
+/// tally.json: the aggregated ciphertexts, the talliers' decryptions and the interpolated G * sum(s)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TallyRecord {
+    #[serde(with = "points")]
+    pub aggregated_encrypted_shares: Vec<CompressedRistretto>,
+    #[serde(with = "point")]
+    pub aggregated_encrypted_vote: CompressedRistretto,
+    pub decrypted_shares: Vec<DecryptedShareRecord>,
+    pub indices: Vec<usize>,
+    #[serde(with = "point")]
+    pub reconstructed_secret: CompressedRistretto,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CandidateCount {
+    pub candidate: String,
+    pub votes: u64,
+}
+
+/// result.json; `winner` is `None` on a tie
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ElectionResult {
+    pub question: String,
+    pub counts: Vec<CandidateCount>,
+    pub valid_ballots: usize,
+    pub rejected_ballots: usize,
+    pub winner: Option<String>,
+}
+
+struct Transcript {
+    election: ElectionRecord,
+    ballots: Vec<BallotRecord>,
+    tally: TallyRecord,
+    G: RistrettoPoint,
+    aggregated_encrypted_vote: RistrettoPoint,
+    reconstructed_secret: RistrettoPoint,
+}
+
+fn tallier_records(
+    config: &ElectionConfig,
+    public_keys: &[CompressedRistretto],
+    key_proofs: &[KeyProof],
+) -> Vec<TallierRecord> {
+    config
+        .talliers
+        .iter()
+        .zip(public_keys.iter().zip(key_proofs))
+        .enumerate()
+        .map(|(i, (name, (public_key, key_proof)))| TallierRecord {
+            name: name.clone(),
+            index: i + 1,
+            public_key: *public_key,
+            key_proof: (*key_proof).into(),
+        })
+        .collect()
+}
+
+/// Lagrange coefficients laid out by share position, for the first t+1 validated shares
+fn sparse_lambdas(
+    validated_shares: &[usize],
+    n: usize,
+    t: usize,
+) -> Result<(Vec<usize>, Vec<Scalar>), Error> {
+    if validated_shares.len() <= t {
+        return Err(InsufficientShares(validated_shares.len(), t + 1).into());
+    }
+    let indices: Vec<usize> = validated_shares.iter().take(t + 1).map(|i| i + 1).collect();
+
+    let mut lambdas = vec![Scalar::ZERO; n];
+    for (index, lambda) in indices.iter().zip(lagrange_coefficients(&indices)) {
+        lambdas[index - 1] = lambda;
+    }
+    Ok((indices, lambdas))
+}
+
+/// Marks a ballot valid on the board only if both its dealing and its vote are
+fn both_valid<S, V>(
+    encrypted_shares: &mut [(bool, S)],
+    encrypted_votes: &mut [(bool, V)],
+) -> Vec<bool> {
+    encrypted_shares
+        .iter_mut()
+        .zip(encrypted_votes.iter_mut())
+        .map(|(shares, vote)| {
+            let valid = shares.0 && vote.0;
+            (shares.0, vote.0) = (valid, valid);
+            valid
+        })
+        .collect()
+}
+
+/// The scheme-specific steps of an election: its second generator, the dealing and proofs on
+/// each ballot, and how the talliers' parties reconstruct. `run_backend` drives the rest.
+trait ElectionBackend {
+    const BACKEND: Backend;
+    type Tallier;
+    type Board;
+
+    /// H and pk0, whichever the scheme uses
+    fn bases<R>(rng: &mut R) -> (Option<RistrettoPoint>, Option<RistrettoPoint>)
+    where
+        R: CryptoRng + RngCore;
+
+    fn talliers<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        n: usize,
+        t: usize,
+    ) -> Vec<Self::Tallier>
+    where
+        R: CryptoRng + RngCore;
+
+    fn tallier_key(tallier: &Self::Tallier) -> ((CompressedRistretto, RistrettoPoint), KeyProof);
+
+    fn board(generators: &Arc<Generators>, m: usize, n: usize, t: usize) -> Self::Board;
+
+    /// Casts a ballot for `choice` onto the board, returning its record
+    #[allow(clippy::too_many_arguments)]
+    fn cast<R>(
+        board: &mut Self::Board,
+        generators: &Arc<Generators>,
+        n: usize,
+        t: usize,
+        choice: bool,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<BallotRecord, Error>
+    where
+        R: CryptoRng + RngCore;
+
+    /// Verifies every ballot; a ballot only counts if both its dealing and its vote proof verify
+    fn verify(board: &mut Self::Board) -> Vec<bool>;
+
+    fn sum_encrypted_shares(board: &Self::Board) -> Vec<CompressedRistretto>;
+
+    fn tally_encrypted_votes(board: &Self::Board) -> RistrettoPoint;
+
+    /// Decrypts the tallier's share of the aggregated dealing with a DLEQ proof, given the other
+    /// talliers' public keys
+    fn decrypt_share<R>(
+        tallier: &mut Self::Tallier,
+        public_keys: &[CompressedRistretto],
+        aggregated_encrypted_shares: &[CompressedRistretto],
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<(usize, CompressedRistretto, (Scalar, Scalar)), Error>
+    where
+        R: CryptoRng + RngCore;
+
+    /// Verifies the other talliers' decrypted shares and interpolates G * sum(s) from the first
+    /// t+1 valid ones
+    fn reconstruct(
+        tallier: &mut Self::Tallier,
+        others: &[CompressedRistretto],
+        proofs: Vec<(Scalar, Scalar)>,
+        n: usize,
+        t: usize,
+    ) -> Result<(Vec<usize>, RistrettoPoint), Error>;
+}
+
+struct PiSPpvssElection;
+
+impl ElectionBackend for PiSPpvssElection {
+    const BACKEND: Backend = Backend::PiSPpvss;
+    type Tallier = evoting_pi_s_ppvss::tallier::Tallier;
+    type Board = evoting_pi_s_ppvss::bulletin_board::BulletinBoard;
+
+    fn bases<R>(rng: &mut R) -> (Option<RistrettoPoint>, Option<RistrettoPoint>)
+    where
+        R: CryptoRng + RngCore,
+    {
+        (None, Some(random_point(rng)))
+    }
+
+    fn talliers<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        n: usize,
+        t: usize,
+    ) -> Vec<Self::Tallier>
+    where
+        R: CryptoRng + RngCore,
+    {
+        Self::Tallier::generate_talliers(generators, rng, n, t)
+    }
+
+    fn tallier_key(tallier: &Self::Tallier) -> ((CompressedRistretto, RistrettoPoint), KeyProof) {
+        (tallier.party.public_key, tallier.party.key_proof)
+    }
+
+    fn board(generators: &Arc<Generators>, m: usize, _n: usize, t: usize) -> Self::Board {
+        Self::Board::new(generators, m, t)
+    }
+
+    fn cast<R>(
+        board: &mut Self::Board,
+        generators: &Arc<Generators>,
+        n: usize,
+        t: usize,
+        choice: bool,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<BallotRecord, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        use evoting_pi_s_ppvss::voter::Voter;
+
+        let (encrypted_shares, (d, z), encrypted_vote, vote_proof) =
+            Voter::new(n, t, generators)?.vote(rng, hasher, buf, choice)?;
+
+        let record = BallotRecord {
+            voter: String::new(),
+            encrypted_shares: encrypted_shares.iter().map(|share| share.0).collect(),
+            d,
+            z: z.coefficients().to_vec(),
+            commitments: vec![],
+            encrypted_vote,
+            vote_proof: vote_proof.to_bytes(),
+            valid: false,
+        };
+        board.ingest_vote(
+            encrypted_shares.iter().map(|share| share.1).collect(),
+            (d, z),
+            decompress_ristretto_point(encrypted_vote)?,
+            vote_proof.decompress(),
+        );
+        Ok(record)
+    }
+
+    fn verify(board: &mut Self::Board) -> Vec<bool> {
+        board.verify_encrypted_shares();
+        board.verify_votes();
+        both_valid(&mut board.encrypted_shares, &mut board.encrypted_votes)
+    }
+
+    fn sum_encrypted_shares(board: &Self::Board) -> Vec<CompressedRistretto> {
+        board.sum_encrypted_shares_compressed()
+    }
+
+    fn tally_encrypted_votes(board: &Self::Board) -> RistrettoPoint {
+        board.tally_encrypted_votes()
+    }
+
+    fn decrypt_share<R>(
+        tallier: &mut Self::Tallier,
+        public_keys: &[CompressedRistretto],
+        aggregated_encrypted_shares: &[CompressedRistretto],
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<(usize, CompressedRistretto, (Scalar, Scalar)), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let party = &mut tallier.party;
+        party.ingest_public_keys(public_keys)?;
+        party.ingest_encrypted_shares(aggregated_encrypted_shares)?;
+        party.decrypt_share()?;
+        party.dleq_share(rng, hasher, buf)?;
+
+        // both set above
+        Ok((
+            party.index,
+            party.decrypted_share.unwrap().compress(),
+            party.share_proof.unwrap(),
+        ))
+    }
+
+    fn reconstruct(
+        tallier: &mut Self::Tallier,
+        others: &[CompressedRistretto],
+        proofs: Vec<(Scalar, Scalar)>,
+        n: usize,
+        t: usize,
+    ) -> Result<(Vec<usize>, RistrettoPoint), Error> {
+        let party = &mut tallier.party;
+        party.ingest_decrypted_shares_and_proofs(others, proofs)?;
+        party.verify_decrypted_shares()?;
+
+        let (indices, lambdas) = sparse_lambdas(&party.validated_shares, n, t)?;
+        Ok((indices, party.reconstruct_secret_pessimistic(&lambdas)?))
+    }
+}
+
+struct SchoenmakersElection;
+
+impl ElectionBackend for SchoenmakersElection {
+    const BACKEND: Backend = Backend::Schoenmakers;
+    type Tallier = evoting_schoenmakers::tallier::Tallier;
+    type Board = evoting_schoenmakers::bulletin_board::BulletinBoard;
+
+    fn bases<R>(rng: &mut R) -> (Option<RistrettoPoint>, Option<RistrettoPoint>)
+    where
+        R: CryptoRng + RngCore,
+    {
+        (Some(random_point(rng)), None)
+    }
+
+    fn talliers<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        n: usize,
+        t: usize,
+    ) -> Vec<Self::Tallier>
+    where
+        R: CryptoRng + RngCore,
+    {
+        Self::Tallier::generate_talliers(generators, rng, n, t)
+    }
+
+    fn tallier_key(tallier: &Self::Tallier) -> ((CompressedRistretto, RistrettoPoint), KeyProof) {
+        (tallier.party.public_key, tallier.party.key_proof)
+    }
+
+    fn board(generators: &Arc<Generators>, m: usize, n: usize, t: usize) -> Self::Board {
+        Self::Board::new(generators, m, n, t)
+    }
+
+    fn cast<R>(
+        board: &mut Self::Board,
+        generators: &Arc<Generators>,
+        n: usize,
+        t: usize,
+        choice: bool,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<BallotRecord, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        use evoting_schoenmakers::voter::Voter;
+
+        let (encrypted_shares, (d, z), dealer_commitments, encrypted_vote, vote_proof) =
+            Voter::new(n, t, generators)?.vote(rng, hasher, buf, choice)?;
+
+        let record = BallotRecord {
+            voter: String::new(),
+            encrypted_shares: encrypted_shares.iter().map(|share| share.0).collect(),
+            d,
+            z: z.clone(),
+            commitments: dealer_commitments
+                .iter()
+                .map(|commitment| commitment.compress())
+                .collect(),
+            encrypted_vote,
+            vote_proof: vote_proof.to_bytes(),
+            valid: false,
+        };
+        board.ingest_vote(
+            encrypted_shares.iter().map(|share| share.1).collect(),
+            (d, z),
+            decompress_ristretto_point(encrypted_vote)?,
+            dealer_commitments,
+            vote_proof.decompress(),
+        );
+        Ok(record)
+    }
+
+    fn verify(board: &mut Self::Board) -> Vec<bool> {
+        board.verify_encrypted_shares();
+        board.verify_votes();
+        both_valid(&mut board.encrypted_shares, &mut board.encrypted_votes)
+    }
+
+    fn sum_encrypted_shares(board: &Self::Board) -> Vec<CompressedRistretto> {
+        board.sum_encrypted_shares_compressed()
+    }
+
+    fn tally_encrypted_votes(board: &Self::Board) -> RistrettoPoint {
+        board.tally_encrypted_votes()
+    }
+
+    fn decrypt_share<R>(
+        tallier: &mut Self::Tallier,
+        public_keys: &[CompressedRistretto],
+        aggregated_encrypted_shares: &[CompressedRistretto],
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<(usize, CompressedRistretto, (Scalar, Scalar)), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let party = &mut tallier.party;
+        party.ingest_public_keys(public_keys)?;
+        party.ingest_encrypted_shares(aggregated_encrypted_shares)?;
+        party.decrypt_share()?;
+        party.dleq_share(rng, hasher, buf)?;
+
+        // both set above
+        Ok((
+            party.index,
+            party.decrypted_share.unwrap().compress(),
+            party.share_proof.unwrap(),
+        ))
+    }
+
+    fn reconstruct(
+        tallier: &mut Self::Tallier,
+        others: &[CompressedRistretto],
+        proofs: Vec<(Scalar, Scalar)>,
+        n: usize,
+        t: usize,
+    ) -> Result<(Vec<usize>, RistrettoPoint), Error> {
+        let party = &mut tallier.party;
+        party.ingest_decrypted_shares_and_proofs(others, proofs)?;
+        party.verify_decrypted_shares()?;
+
+        let (indices, lambdas) = sparse_lambdas(&party.validated_shares, n, t)?;
+        Ok((indices, party.reconstruct_secret(&lambdas)?))
+    }
+}
+
+fn run_backend<B, R>(
+    config: &ElectionConfig,
+    choices: &[(String, bool)],
+    rng: &mut R,
+) -> Result<Transcript, Error>
+where
+    B: ElectionBackend,
+    R: CryptoRng + RngCore,
+{
+    let (n, t, m) = (config.talliers.len(), config.threshold, choices.len());
+    let mut hasher = Hasher::new();
+    let mut buf = [0u8; 64];
+
+    // setup
+    let G = random_point(rng);
+    let (H, pk0) = B::bases(rng);
+
+    let mut generators = Generators::new(&G);
+    if let Some(H) = &H {
+        generators = generators.with_H(H);
+    }
+    if let Some(pk0) = &pk0 {
+        generators = generators.with_pk0(pk0);
+    }
+    let generators = Arc::new(generators);
+
+    let mut talliers = B::talliers(&generators, rng, n, t);
+    let (public_keys, key_proofs): (Vec<(CompressedRistretto, RistrettoPoint)>, Vec<KeyProof>) =
+        talliers.iter().map(B::tallier_key).unzip();
+    let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) =
+        public_keys.into_iter().unzip();
+    let generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(&public_keys.1, &key_proofs)?,
+    );
+
+    let mut bulletin_board = B::board(&generators, m, n, t);
+
+    // ballot casting
+    let mut ballots = Vec::with_capacity(m);
+    for (voter, choice) in choices {
+        let mut ballot = B::cast(
+            &mut bulletin_board,
+            &generators,
+            n,
+            t,
+            *choice,
+            rng,
+            &mut hasher,
+            &mut buf,
+        )?;
+        ballot.voter = voter.clone();
+        ballots.push(ballot);
+    }
+
+    // verification: a ballot only counts if both its dealing and its vote proof verify
+    for (ballot, valid) in ballots.iter_mut().zip(B::verify(&mut bulletin_board)) {
+        ballot.valid = valid;
+    }
+
+    // tally decryption
+    let aggregated_encrypted_shares = B::sum_encrypted_shares(&bulletin_board);
+
+    let mut decrypted_shares = Vec::with_capacity(n);
+    for (tallier, name) in talliers.iter_mut().zip(&config.talliers) {
+        let own_key = B::tallier_key(tallier).0.0;
+        let public_keys: Vec<CompressedRistretto> = public_keys
+            .0
+            .iter()
+            .filter(|pk| own_key != **pk)
+            .copied()
+            .collect();
+
+        let (index, decrypted_share, (d, z)) = B::decrypt_share(
+            tallier,
+            &public_keys,
+            &aggregated_encrypted_shares,
+            rng,
+            &mut hasher,
+            &mut buf,
+        )?;
+        decrypted_shares.push(DecryptedShareRecord {
+            tallier: name.clone(),
+            index,
+            decrypted_share,
+            d,
+            z,
+        });
+    }
+
+    let (others, proofs): (Vec<CompressedRistretto>, Vec<(Scalar, Scalar)>) = decrypted_shares
+        .iter()
+        .skip(1)
+        .map(|share| (share.decrypted_share, (share.d, share.z)))
+        .unzip();
+    let (indices, reconstructed_secret) = B::reconstruct(&mut talliers[0], &others, proofs, n, t)?;
+    let aggregated_encrypted_vote = B::tally_encrypted_votes(&bulletin_board);
+
+    Ok(Transcript {
+        election: ElectionRecord {
+            question: config.question.clone(),
+            candidates: config.candidates.clone(),
+            scheme: B::BACKEND,
+            n,
+            t,
+            G: G.compress(),
+            H: H.map(|H| H.compress()),
+            pk0: pk0.map(|pk0| pk0.compress()),
+            talliers: tallier_records(config, &public_keys.0, &key_proofs),
+            voters: config.voters.clone(),
+        },
+        ballots,
+        tally: TallyRecord {
+            aggregated_encrypted_shares,
+            aggregated_encrypted_vote: aggregated_encrypted_vote.compress(),
+            decrypted_shares,
+            indices,
+            reconstructed_secret: reconstructed_secret.compress(),
+        },
+        G,
+        aggregated_encrypted_vote,
+        reconstructed_secret,
+    })
+}
+
+/// Runs setup, casting, verification and tally decryption, writing every artifact to `out_dir`
+pub fn run_election<R>(
+    config: &ElectionConfig,
+    scheme: Backend,
+    ballots: &Ballots,
+    out_dir: &Path,
+    rng: &mut R,
+) -> Result<ElectionResult, Error>
+where
+    R: CryptoRng + RngCore,
+{
+    config.validate()?;
+    let choices = config.choices(ballots)?;
+
+    let transcript = match scheme {
+        Backend::PiSPpvss => run_backend::<PiSPpvssElection, R>(config, &choices, rng)?,
+        Backend::Schoenmakers => run_backend::<SchoenmakersElection, R>(config, &choices, rng)?,
+    };
+
+    // result publication: G * count = sum(U) - G * sum(s)
+    let valid_ballots = transcript
+        .ballots
+        .iter()
+        .filter(|ballot| ballot.valid)
+        .count();
+    let exp_vote = transcript.aggregated_encrypted_vote - transcript.reconstructed_secret;
+
+    let mut acc = RistrettoPoint::default();
+    let mut yes_votes = None;
+    for i in 0..=valid_ballots as u64 {
+        if acc == exp_vote {
+            yes_votes = Some(i);
+            break;
+        }
+        acc += transcript.G;
+    }
+    let yes_votes = yes_votes.ok_or(Error::from(UndecodableTally))?;
+    let no_votes = valid_ballots as u64 - yes_votes;
+
+    let result = ElectionResult {
+        question: config.question.clone(),
+        counts: vec![
+            CandidateCount {
+                candidate: config.candidates[0].clone(),
+                votes: no_votes,
+            },
+            CandidateCount {
+                candidate: config.candidates[1].clone(),
+                votes: yes_votes,
+            },
+        ],
+        valid_ballots,
+        rejected_ballots: transcript.ballots.len() - valid_ballots,
+        winner: match no_votes.cmp(&yes_votes) {
+            std::cmp::Ordering::Greater => Some(config.candidates[0].clone()),
+            std::cmp::Ordering::Less => Some(config.candidates[1].clone()),
+            std::cmp::Ordering::Equal => None,
+        },
+    };
+
+    fs::create_dir_all(out_dir)?;
+    write_json(&out_dir.join("election.json"), &transcript.election)?;
+    write_json(&out_dir.join("ballots.json"), &transcript.ballots)?;
+    write_json(&out_dir.join("tally.json"), &transcript.tally)?;
+    write_json(&out_dir.join("result.json"), &result)?;
+
+    Ok(result)
+}
@@ -0,0 +1,142 @@
+use std::{fs, path::Path};
+
+use curve25519_dalek::{Scalar, ristretto::CompressedRistretto};
+use serde::{Deserialize, Deserializer, Serializer, de::DeserializeOwned, ser::SerializeSeq};
+
+use crate::error::{Error, ErrorKind::InvalidEncoding};
+
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+pub fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> Result<(), Error> {
+    Ok(fs::write(path, serde_json::to_vec_pretty(value)?)?)
+}
+
+pub fn read_toml<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    Ok(toml::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn decode_32(s: &str) -> Result<[u8; 32], Error> {
+    let bytes = hex::decode(s).map_err(|e| InvalidEncoding(format!("'{s}': {e}")))?;
+
+    bytes
+        .try_into()
+        .map_err(|_| InvalidEncoding(format!("'{s}' is not 32 bytes long")).into())
+}
+
+pub fn decode_point(s: &str) -> Result<CompressedRistretto, Error> {
+    Ok(CompressedRistretto(decode_32(s)?))
+}
+
+pub fn decode_scalar(s: &str) -> Result<Scalar, Error> {
+    Option::from(Scalar::from_canonical_bytes(decode_32(s)?))
+        .ok_or_else(|| InvalidEncoding(format!("'{s}' is not a canonical scalar")).into())
+}
+
+/// Hex (de)serialization of a compressed ristretto point
+pub mod point {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(point: &CompressedRistretto, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(point.as_bytes()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<CompressedRistretto, D::Error> {
+        decode_point(&String::deserialize(d)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Hex (de)serialization of an optional compressed ristretto point
+pub mod option_point {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        point: &Option<CompressedRistretto>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        match point {
+            Some(point) => s.serialize_some(&hex::encode(point.as_bytes())),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Option<CompressedRistretto>, D::Error> {
+        Option::<String>::deserialize(d)?
+            .map(|point| decode_point(&point).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+/// Hex (de)serialization of a list of compressed ristretto points
+pub mod points {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        points: &[CompressedRistretto],
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = s.serialize_seq(Some(points.len()))?;
+        for point in points {
+            seq.serialize_element(&hex::encode(point.as_bytes()))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Vec<CompressedRistretto>, D::Error> {
+        Vec::<String>::deserialize(d)?
+            .iter()
+            .map(|point| decode_point(point).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+/// Hex (de)serialization of a canonical scalar
+pub mod scalar {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(scalar: &Scalar, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(scalar.as_bytes()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Scalar, D::Error> {
+        decode_scalar(&String::deserialize(d)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Hex (de)serialization of a list of canonical scalars
+pub mod scalars {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(scalars: &[Scalar], s: S) -> Result<S::Ok, S::Error> {
+        let mut seq = s.serialize_seq(Some(scalars.len()))?;
+        for scalar in scalars {
+            seq.serialize_element(&hex::encode(scalar.as_bytes()))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Scalar>, D::Error> {
+        Vec::<String>::deserialize(d)?
+            .iter()
+            .map(|scalar| decode_scalar(scalar).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+/// Hex (de)serialization of a byte string
+pub mod bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        hex::decode(String::deserialize(d)?).map_err(serde::de::Error::custom)
+    }
+}
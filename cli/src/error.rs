@@ -0,0 +1,46 @@
+use error_chain::error_chain;
+
+error_chain! {
+    links {
+        Common(common::error::Error, common::error::ErrorKind);
+    }
+    foreign_links {
+        Io(std::io::Error);
+        Json(serde_json::Error);
+        Toml(toml::de::Error);
+    }
+    errors {
+        InvalidEncoding(t: String) {
+            description("Invalid Encoding")
+            display("Invalid Encoding: {}", t)
+        }
+        SchemeMismatch(expected: String, found: String) {
+            description("Scheme Mismatch")
+            display("Scheme Mismatch: expected '{}' but found '{}'", expected, found)
+        }
+        InvalidPartyIndex(index: usize, n: usize) {
+            description("Invalid Party Index")
+            display("Invalid Party Index: {} (valid indices are 1..={})", index, n)
+        }
+        InvalidDealing {
+            description("Invalid Dealing")
+            display("The dealing does not verify against the registered public keys")
+        }
+        InvalidConfig(t: String) {
+            description("Invalid Election Configuration")
+            display("Invalid Election Configuration: {}", t)
+        }
+        InvalidBallot(voter: String, t: String) {
+            description("Invalid Ballot")
+            display("Invalid Ballot from '{}': {}", voter, t)
+        }
+        UndecodableTally {
+            description("Undecodable Tally")
+            display("The decrypted tally is not a count of valid ballots")
+        }
+        InsufficientShares(valid: usize, needed: usize) {
+            description("Insufficient Valid Decrypted Shares")
+            display("Insufficient Valid Decrypted Shares: have {}, need {}", valid, needed)
+        }
+    }
+}
@@ -0,0 +1,142 @@
+#![allow(non_snake_case)]
+
+pub mod election;
+pub mod encoding;
+pub mod error;
+pub mod messages;
+pub mod pvss;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        election::{Backend, Ballots, ElectionConfig, run_election},
+        messages::{Dealing, DecryptedShare, PublicKey, Scheme},
+        pvss,
+    };
+    use common::utils::decompress_ristretto_point;
+    use curve25519_dalek::Scalar;
+
+    #[test]
+    fn end_to_end() {
+        const N: usize = 7;
+        const T: usize = 3;
+
+        let mut rng = rand::rng();
+
+        for scheme in [Scheme::PiSPvss, Scheme::PiSPpvss, Scheme::Schoenmakers] {
+            let params = pvss::setup(scheme, N, T, &mut rng).unwrap();
+
+            let (secret_keys, public_keys): (Vec<_>, Vec<PublicKey>) = (1..=N)
+                .map(|i| pvss::keygen(&params, i, &mut rng).unwrap())
+                .unzip();
+
+            let (dealing, secret) = pvss::deal(&params, &public_keys, &mut rng).unwrap();
+
+            // files are exchanged as json
+            let dealing: Dealing =
+                serde_json::from_str(&serde_json::to_string(&dealing).unwrap()).unwrap();
+
+            assert!(pvss::verify_dealing(&params, &public_keys, &dealing).unwrap());
+
+            let shares: Vec<DecryptedShare> = secret_keys
+                .iter()
+                .map(|sk| {
+                    pvss::decrypt_share(&params, &public_keys, sk, &dealing, &mut rng).unwrap()
+                })
+                .collect();
+
+            assert_eq!(
+                pvss::verify_decryptions(&params, &public_keys, &dealing, &shares).unwrap(),
+                (1..=N).collect::<Vec<usize>>()
+            );
+
+            // any t+1 shares reconstruct the same secret
+            let reconstructed =
+                pvss::reconstruct(&params, &public_keys, &dealing, &shares[N - T - 1..], None)
+                    .unwrap();
+
+            let G = decompress_ristretto_point(params.G).unwrap();
+            assert_eq!(reconstructed.secret, (G * secret.secret).compress());
+            assert!(
+                pvss::reconstruct(&params, &public_keys, &dealing, &shares[..T], None).is_err()
+            );
+
+            // a tampered decrypted share is skipped and reported, the rest still reconstruct
+            let mut tampered = shares[N - T - 2..].to_vec();
+            tampered[0].decrypted_share = shares[0].decrypted_share;
+            let skipped =
+                pvss::reconstruct(&params, &public_keys, &dealing, &tampered, None).unwrap();
+            assert_eq!(skipped.skipped, [N - T - 1]);
+            assert_eq!(skipped.secret, reconstructed.secret);
+            assert!(
+                pvss::reconstruct(&params, &public_keys, &dealing, &tampered[..T + 1], None)
+                    .is_err()
+            );
+            // a tampered dealing is refused
+            let mut tampered = dealing.clone();
+            tampered.d += Scalar::ONE;
+            assert!(
+                pvss::reconstruct(&params, &public_keys, &tampered, &shares[N - T - 1..], None)
+                    .is_err()
+            );
+
+            if scheme == Scheme::PiSPpvss {
+                let optimistic =
+                    pvss::reconstruct(&params, &public_keys, &dealing, &[], Some(&secret)).unwrap();
+                assert_eq!(optimistic.secret, reconstructed.secret);
+            }
+        }
+    }
+
+    #[test]
+    fn election() {
+        let config: ElectionConfig = toml::from_str(
+            r#"
+            question = "Adopt the proposal?"
+            candidates = ["no", "yes"]
+            talliers = ["t1", "t2", "t3", "t4", "t5"]
+            threshold = 2
+            voters = ["alice", "bob", "carol", "dave"]
+            "#,
+        )
+        .unwrap();
+        let ballots: Ballots = toml::from_str(
+            r#"
+            [[ballot]]
+            voter = "alice"
+            choice = "yes"
+            [[ballot]]
+            voter = "bob"
+            choice = "no"
+            [[ballot]]
+            voter = "carol"
+            choice = "yes"
+            "#,
+        )
+        .unwrap();
+
+        let mut rng = rand::rng();
+
+        for scheme in [Backend::PiSPpvss, Backend::Schoenmakers] {
+            let out_dir = std::env::temp_dir().join(format!("election-{scheme:?}"));
+            let result = run_election(&config, scheme, &ballots, &out_dir, &mut rng).unwrap();
+
+            assert_eq!(result.counts[0].votes, 1);
+            assert_eq!(result.counts[1].votes, 2);
+            assert_eq!(result.valid_ballots, 3);
+            assert_eq!(result.winner.as_deref(), Some("yes"));
+            assert!(out_dir.join("tally.json").exists());
+        }
+
+        // only yes/no elections can be tallied
+        let mut three = config.clone();
+        three.candidates.push("abstain".into());
+        assert!(three.validate().is_err());
+
+        // voters off the roll are refused before anything is cast
+        let mut ballots = ballots;
+        ballots.ballots[0].voter = "mallory".into();
+        let out_dir = std::env::temp_dir().join("election-refused");
+        assert!(run_election(&config, Backend::PiSPpvss, &ballots, &out_dir, &mut rng).is_err());
+    }
+}
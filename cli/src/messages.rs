@@ -0,0 +1,168 @@
+use clap::ValueEnum;
+use common::key_proof::KeyProof;
+use curve25519_dalek::{Scalar, ristretto::CompressedRistretto};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::{
+    encoding::{point, points, scalar, scalars},
+    error::{Error, ErrorKind::SchemeMismatch},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum Scheme {
+    #[serde(rename = "pi_s_pvss")]
+    #[value(name = "pi_s_pvss")]
+    PiSPvss,
+    #[serde(rename = "pi_s_ppvss")]
+    #[value(name = "pi_s_ppvss")]
+    PiSPpvss,
+    #[serde(rename = "schoenmakers")]
+    #[value(name = "schoenmakers")]
+    Schoenmakers,
+}
+
+impl Scheme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scheme::PiSPvss => "pi_s_pvss",
+            Scheme::PiSPpvss => "pi_s_ppvss",
+            Scheme::Schoenmakers => "schoenmakers",
+        }
+    }
+
+    pub fn check(&self, other: Scheme) -> Result<(), Error> {
+        if *self == other {
+            Ok(())
+        } else {
+            Err(SchemeMismatch(self.name().into(), other.name().into()).into())
+        }
+    }
+
+    /// Position of party `index`'s share in a dealing (pi_s_ppvss prepends y0)
+    pub fn share_position(&self, index: usize) -> usize {
+        match self {
+            Scheme::PiSPpvss => index,
+            Scheme::PiSPvss | Scheme::Schoenmakers => index - 1,
+        }
+    }
+}
+
+/// Public parameters every participant agrees on before key generation
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Params {
+    pub scheme: Scheme,
+    pub n: usize,
+    pub t: usize,
+    #[serde(with = "point")]
+    pub G: CompressedRistretto,
+    #[serde(with = "point")]
+    pub H: CompressedRistretto,
+    #[serde(with = "point")]
+    pub pk0: CompressedRistretto,
+}
+
+/// Wiped on drop, so neither `Clone` nor `Debug`
+#[derive(Serialize, Deserialize)]
+pub struct SecretKey {
+    pub scheme: Scheme,
+    pub index: usize,
+    #[serde(with = "scalar")]
+    pub private_key: Scalar,
+    #[serde(with = "point")]
+    pub public_key: CompressedRistretto,
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PublicKey {
+    pub scheme: Scheme,
+    pub index: usize,
+    #[serde(with = "point")]
+    pub public_key: CompressedRistretto,
+    pub key_proof: KeyProofRecord,
+}
+
+/// Proof of possession of the private key behind a registered public key
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct KeyProofRecord {
+    #[serde(with = "scalar")]
+    pub c: Scalar,
+    #[serde(with = "scalar")]
+    pub z: Scalar,
+}
+
+impl From<KeyProof> for KeyProofRecord {
+    fn from(proof: KeyProof) -> Self {
+        Self {
+            c: proof.c,
+            z: proof.z,
+        }
+    }
+}
+
+impl From<KeyProofRecord> for KeyProof {
+    fn from(record: KeyProofRecord) -> Self {
+        Self {
+            c: record.c,
+            z: record.z,
+        }
+    }
+}
+
+/// Encrypted shares and the dealer's proof; `commitments` is only used by schoenmakers
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Dealing {
+    pub scheme: Scheme,
+    #[serde(with = "points")]
+    pub encrypted_shares: Vec<CompressedRistretto>,
+    #[serde(with = "scalar")]
+    pub d: Scalar,
+    #[serde(with = "scalars")]
+    pub z: Vec<Scalar>,
+    #[serde(with = "points", default)]
+    pub commitments: Vec<CompressedRistretto>,
+}
+
+/// The dealt secret, published as f(0) for the optimistic pi_s_ppvss reconstruction. Wiped on drop.
+#[derive(Serialize, Deserialize)]
+pub struct DealerSecret {
+    pub scheme: Scheme,
+    #[serde(with = "scalar")]
+    pub secret: Scalar,
+}
+
+impl Drop for DealerSecret {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecryptedShare {
+    pub scheme: Scheme,
+    pub index: usize,
+    #[serde(with = "point")]
+    pub decrypted_share: CompressedRistretto,
+    #[serde(with = "scalar")]
+    pub d: Scalar,
+    #[serde(with = "scalar")]
+    pub z: Scalar,
+}
+
+/// G * secret, with the indices of the decrypted shares it was interpolated from
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReconstructedSecret {
+    pub scheme: Scheme,
+    pub indices: Vec<usize>,
+    /// indices of the decrypted shares that did not verify and were left out
+    #[serde(default)]
+    pub skipped: Vec<usize>,
+    #[serde(with = "point")]
+    pub secret: CompressedRistretto,
+}
@@ -0,0 +1,483 @@
+use blake3::Hasher;
+use common::{
+    error::ErrorKind::{CountMismatch, InvalidPararmeterSet, InvalidProof, UninitializedValue},
+    generators::Generators,
+    key_proof::KeyProof,
+    keystore::Keypair,
+    polynomial::Polynomial,
+    random::{random_point, random_scalar},
+    utils::{
+        batch_decompress_ristretto_points, decompress_ristretto_point, lagrange_coefficients,
+        verify_decrypted_share,
+    },
+};
+use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+use crate::{
+    error::{
+        Error,
+        ErrorKind::{Common, InsufficientShares, InvalidDealing, InvalidPartyIndex},
+    },
+    messages::{
+        DealerSecret, Dealing, DecryptedShare, Params, PublicKey, ReconstructedSecret, Scheme,
+        SecretKey,
+    },
+};
+
+pub fn setup<R>(scheme: Scheme, n: usize, t: usize, rng: &mut R) -> Result<Params, Error>
+where
+    R: CryptoRng + RngCore,
+{
+    if n < 2 || t >= n || t != (n - 1) / 2 {
+        return Err(Common(InvalidPararmeterSet(n, t as isize, 0)).into());
+    }
+    Ok(Params {
+        scheme,
+        n,
+        t,
+        G: random_point(rng).compress(),
+        H: random_point(rng).compress(),
+        pk0: random_point(rng).compress(),
+    })
+}
+
+pub fn keygen<R>(
+    params: &Params,
+    index: usize,
+    rng: &mut R,
+) -> Result<(SecretKey, PublicKey), Error>
+where
+    R: CryptoRng + RngCore,
+{
+    if index == 0 || index > params.n {
+        return Err(InvalidPartyIndex(index, params.n).into());
+    }
+    let generators = scheme_generators(params)?;
+
+    let private_key = random_scalar(rng);
+    let public_key = generators.G_table() * &private_key;
+    let key_proof = KeyProof::prove(
+        rng,
+        generators.G_table(),
+        &generators.session(),
+        index,
+        &private_key,
+        &public_key,
+    );
+
+    Ok((
+        SecretKey {
+            scheme: params.scheme,
+            index,
+            private_key,
+            public_key: public_key.compress(),
+        },
+        PublicKey {
+            scheme: params.scheme,
+            index,
+            public_key: public_key.compress(),
+            key_proof: key_proof.into(),
+        },
+    ))
+}
+
+/// G, plus pk0 for pi_s_ppvss or H for schoenmakers
+fn scheme_generators(params: &Params) -> Result<Generators, Error> {
+    let generators = Generators::new(&decompress_ristretto_point(params.G)?);
+    Ok(match params.scheme {
+        Scheme::PiSPvss => generators,
+        Scheme::PiSPpvss => generators.with_pk0(&decompress_ristretto_point(params.pk0)?),
+        Scheme::Schoenmakers => generators.with_H(&decompress_ristretto_point(params.H)?),
+    })
+}
+
+/// Orders the registered public keys by party index, requiring exactly one key for each of 1..=n,
+/// and registers them with the scheme's generators, rejecting any key without a valid proof of possession
+pub fn register_public_keys(
+    params: &Params,
+    public_keys: &[PublicKey],
+) -> Result<Generators, Error> {
+    if public_keys.len() != params.n {
+        return Err(Common(CountMismatch(
+            params.n,
+            "parties",
+            public_keys.len(),
+            "public keys",
+        ))
+        .into());
+    }
+    let mut ordered: Vec<Option<&PublicKey>> = vec![None; params.n];
+    for pk in public_keys {
+        params.scheme.check(pk.scheme)?;
+        match ordered.get_mut(pk.index.wrapping_sub(1)) {
+            Some(slot @ None) => *slot = Some(pk),
+            _ => return Err(InvalidPartyIndex(pk.index, params.n).into()),
+        }
+    }
+    let (points, proofs): (Vec<CompressedRistretto>, Vec<KeyProof>) = ordered
+        .into_iter()
+        .flatten()
+        .map(|pk| (pk.public_key, KeyProof::from(pk.key_proof)))
+        .unzip();
+
+    Ok(scheme_generators(params)?
+        .with_public_keys(&batch_decompress_ristretto_points(&points)?, &proofs)?)
+}
+
+pub fn deal<R>(
+    params: &Params,
+    public_keys: &[PublicKey],
+    rng: &mut R,
+) -> Result<(Dealing, DealerSecret), Error>
+where
+    R: CryptoRng + RngCore,
+{
+    let generators = Arc::new(register_public_keys(params, public_keys)?);
+    let (n, t) = (params.n, params.t);
+
+    let mut hasher = Hasher::new();
+    let mut buf = [0u8; 64];
+
+    let secret = random_scalar(rng);
+
+    let dealing = match params.scheme {
+        Scheme::PiSPvss => {
+            let mut dealer = pi_s_pvss::dealer::Dealer::new(n, t, &generators)?;
+            let (encrypted_shares, (d, z)) =
+                dealer.deal_secret(rng, &mut hasher, &mut buf, &secret);
+            Dealing {
+                scheme: params.scheme,
+                encrypted_shares,
+                d,
+                z: z.coefficients().to_vec(),
+                commitments: vec![],
+            }
+        }
+        Scheme::PiSPpvss => {
+            let mut dealer = pi_s_ppvss::dealer::Dealer::new(n, t, &generators)?;
+            let (encrypted_shares, (d, z)) =
+                dealer.deal_secret(rng, &mut hasher, &mut buf, &secret);
+            Dealing {
+                scheme: params.scheme,
+                encrypted_shares,
+                d,
+                z: z.coefficients().to_vec(),
+                commitments: vec![],
+            }
+        }
+        Scheme::Schoenmakers => {
+            let mut dealer = schoenmakers::dealer::Dealer::new(n, t, &generators)?;
+            let (encrypted_shares, (d, z), commitments) =
+                dealer.deal_secret(rng, &mut hasher, &mut buf, secret);
+            Dealing {
+                scheme: params.scheme,
+                encrypted_shares,
+                d,
+                z,
+                commitments,
+            }
+        }
+    };
+
+    Ok((
+        dealing,
+        DealerSecret {
+            scheme: params.scheme,
+            secret,
+        },
+    ))
+}
+
+fn check_dealing_shape(params: &Params, dealing: &Dealing) -> Result<(), Error> {
+    params.scheme.check(dealing.scheme)?;
+
+    let (n, t) = (params.n, params.t);
+    let (shares, z, commitments) = match params.scheme {
+        Scheme::PiSPvss => (n, t + 1, 0),
+        Scheme::PiSPpvss => (n + 1, t + 1, 0),
+        Scheme::Schoenmakers => (n, n, t + 1),
+    };
+
+    if dealing.encrypted_shares.len() != shares {
+        Err(Common(CountMismatch(
+            shares,
+            "expected encrypted shares",
+            dealing.encrypted_shares.len(),
+            "encrypted shares",
+        ))
+        .into())
+    } else if dealing.z.len() != z || dealing.commitments.len() != commitments {
+        Err(Common(InvalidProof(format!(
+            "z len: {}, commitments len: {}",
+            dealing.z.len(),
+            dealing.commitments.len()
+        )))
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+pub fn verify_dealing(
+    params: &Params,
+    public_keys: &[PublicKey],
+    dealing: &Dealing,
+) -> Result<bool, Error> {
+    check_dealing_shape(params, dealing)?;
+
+    let mut public_keys = register_public_keys(params, public_keys)?
+        .public_keys()
+        .to_vec();
+    let encrypted_shares = (
+        dealing.encrypted_shares.clone(),
+        batch_decompress_ristretto_points(&dealing.encrypted_shares)?,
+    );
+
+    let mut hasher = Hasher::new();
+    let mut buf = [0u8; 64];
+
+    Ok(match params.scheme {
+        Scheme::PiSPvss => pi_s_pvss::utils::verify_encrypted_shares_standalone(
+            &encrypted_shares,
+            &public_keys,
+            (&dealing.d, &Polynomial::from(dealing.z.clone())),
+            &mut hasher,
+            &mut buf,
+        )?,
+        Scheme::PiSPpvss => {
+            public_keys.insert(0, decompress_ristretto_point(params.pk0)?);
+            pi_s_ppvss::utils::verify_encrypted_shares_standalone(
+                &encrypted_shares,
+                &public_keys,
+                (&dealing.d, &Polynomial::from(dealing.z.clone())),
+                &mut hasher,
+                &mut buf,
+            )?
+        }
+        Scheme::Schoenmakers => schoenmakers::utils::verify_encrypted_shares_standalone(
+            &decompress_ristretto_point(params.H)?,
+            &mut hasher,
+            &mut buf,
+            &encrypted_shares,
+            &public_keys,
+            &batch_decompress_ristretto_points(&dealing.commitments)?,
+            (&dealing.d, &dealing.z),
+            params.n,
+            params.t,
+        )?,
+    })
+}
+
+/// Verifies the dealing as the party owning `secret_key`, then decrypts its share and proves correct decryption
+pub fn decrypt_share<R>(
+    params: &Params,
+    public_keys: &[PublicKey],
+    secret_key: &SecretKey,
+    dealing: &Dealing,
+    rng: &mut R,
+) -> Result<DecryptedShare, Error>
+where
+    R: CryptoRng + RngCore,
+{
+    params.scheme.check(secret_key.scheme)?;
+    check_dealing_shape(params, dealing)?;
+
+    let (n, t, index) = (params.n, params.t, secret_key.index);
+    let generators = Arc::new(register_public_keys(params, public_keys)?);
+    let other_public_keys: Vec<CompressedRistretto> = generators
+        .public_keys()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i + 1 != index)
+        .map(|(_, pk)| pk.compress())
+        .collect();
+
+    let keypair = Keypair::new(index, &secret_key.private_key, secret_key.public_key);
+
+    let mut hasher = Hasher::new();
+    let mut buf = [0u8; 64];
+
+    let (decrypted_share, d_z) = match params.scheme {
+        Scheme::PiSPvss => {
+            let mut party =
+                pi_s_pvss::party::Party::from_keypair(&generators, rng, &keypair, n, t)?;
+
+            party.ingest_registered_keys(&generators)?;
+            party.ingest_encrypted_shares(&dealing.encrypted_shares)?;
+            party.ingest_dealer_proof(dealing.d, Polynomial::from(dealing.z.clone()))?;
+            if !party.verify_encrypted_shares(&mut hasher, &mut buf)? {
+                return Err(InvalidDealing.into());
+            }
+            party.decrypt_share()?;
+            party.dleq_share(rng, &mut hasher, &mut buf)?;
+
+            (party.decrypted_share, party.share_proof)
+        }
+        Scheme::PiSPpvss => {
+            let mut party =
+                pi_s_ppvss::party::Party::from_keypair(&generators, rng, &keypair, n, t)?;
+
+            party.ingest_public_keys(&other_public_keys)?;
+            party.ingest_encrypted_shares(&dealing.encrypted_shares)?;
+            party.ingest_dealer_proof(dealing.d, Polynomial::from(dealing.z.clone()))?;
+            if !party.verify_encrypted_shares(&mut hasher, &mut buf)? {
+                return Err(InvalidDealing.into());
+            }
+            party.decrypt_share()?;
+            party.dleq_share(rng, &mut hasher, &mut buf)?;
+
+            (party.decrypted_share, party.share_proof)
+        }
+        Scheme::Schoenmakers => {
+            let mut party =
+                schoenmakers::party::Party::from_keypair(&generators, rng, &keypair, n, t)?;
+
+            party.ingest_public_keys(&other_public_keys)?;
+            party.ingest_encrypted_shares(&dealing.encrypted_shares)?;
+            party.ingest_dealer_proof(dealing.d, dealing.z.clone())?;
+            party.ingest_commitments(&dealing.commitments)?;
+            if !party.verify_encrypted_shares(&mut hasher, &mut buf)? {
+                return Err(InvalidDealing.into());
+            }
+            party.decrypt_share()?;
+            party.dleq_share(rng, &mut hasher, &mut buf)?;
+
+            (party.decrypted_share, party.share_proof)
+        }
+    };
+    let (decrypted_share, (d, z)) = match (decrypted_share, d_z) {
+        (Some(decrypted_share), Some(proof)) => (decrypted_share, proof),
+        _ => {
+            return Err(Common(UninitializedValue("party.{decrypted_share, share_proof}")).into());
+        }
+    };
+
+    Ok(DecryptedShare {
+        scheme: params.scheme,
+        index,
+        decrypted_share: decrypted_share.compress(),
+        d,
+        z,
+    })
+}
+
+/// Verifies each decrypted share's DLEQ proof, keeping one valid share per index in index order
+fn valid_decrypted_shares(
+    params: &Params,
+    public_keys: &[PublicKey],
+    dealing: &Dealing,
+    decrypted_shares: &[DecryptedShare],
+) -> Result<Vec<(usize, RistrettoPoint)>, Error> {
+    check_dealing_shape(params, dealing)?;
+
+    let G = decompress_ristretto_point(params.G)?;
+    let generators = register_public_keys(params, public_keys)?;
+    let public_keys = generators.public_keys();
+    let encrypted_shares = batch_decompress_ristretto_points(&dealing.encrypted_shares)?;
+
+    let mut hasher = Hasher::new();
+    let mut buf = [0u8; 64];
+
+    let mut valid: Vec<(usize, RistrettoPoint)> = vec![];
+    for share in decrypted_shares {
+        params.scheme.check(share.scheme)?;
+        if share.index == 0 || share.index > params.n {
+            return Err(InvalidPartyIndex(share.index, params.n).into());
+        }
+        let Some(decrypted_share) = share.decrypted_share.decompress() else {
+            continue;
+        };
+        if verify_decrypted_share(
+            &G,
+            &public_keys[share.index - 1],
+            &encrypted_shares[params.scheme.share_position(share.index)],
+            &decrypted_share,
+            &(share.d, share.z),
+            &mut hasher,
+            &mut buf,
+        ) {
+            valid.push((share.index, decrypted_share));
+        }
+    }
+    valid.sort_unstable_by_key(|(index, _)| *index);
+    valid.dedup_by_key(|(index, _)| *index);
+
+    Ok(valid)
+}
+
+/// Returns the sorted indices of the decrypted shares whose DLEQ proofs verify
+pub fn verify_decryptions(
+    params: &Params,
+    public_keys: &[PublicKey],
+    dealing: &Dealing,
+    decrypted_shares: &[DecryptedShare],
+) -> Result<Vec<usize>, Error> {
+    Ok(
+        valid_decrypted_shares(params, public_keys, dealing, decrypted_shares)?
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect(),
+    )
+}
+
+/// Reconstructs G * secret, optimistically from the dealer's published f(0) (pi_s_ppvss only) or else from t+1 decrypted shares.
+/// Fails unless the dealing and every supplied decrypted share verify
+pub fn reconstruct(
+    params: &Params,
+    public_keys: &[PublicKey],
+    dealing: &Dealing,
+    decrypted_shares: &[DecryptedShare],
+    f0: Option<&DealerSecret>,
+) -> Result<ReconstructedSecret, Error> {
+    if !verify_dealing(params, public_keys, dealing)? {
+        return Err(InvalidDealing.into());
+    }
+
+    let G = decompress_ristretto_point(params.G)?;
+
+    if let (Scheme::PiSPpvss, Some(f0)) = (params.scheme, f0) {
+        params.scheme.check(f0.scheme)?;
+        let y0 = decompress_ristretto_point(dealing.encrypted_shares[0])?;
+        if y0 == decompress_ristretto_point(params.pk0)? * f0.secret {
+            return Ok(ReconstructedSecret {
+                scheme: params.scheme,
+                indices: vec![],
+                skipped: vec![],
+                secret: (G * f0.secret).compress(),
+            });
+        }
+    }
+
+    let valid = valid_decrypted_shares(params, public_keys, dealing, decrypted_shares)?;
+    let mut skipped: Vec<usize> = decrypted_shares
+        .iter()
+        .filter(|share| {
+            !valid.iter().any(|(index, valid)| {
+                *index == share.index && valid.compress() == share.decrypted_share
+            })
+        })
+        .map(|share| share.index)
+        .collect();
+    skipped.sort_unstable();
+    skipped.dedup();
+    if valid.len() <= params.t {
+        return Err(InsufficientShares(valid.len(), params.t + 1).into());
+    }
+    let (indices, shares): (Vec<usize>, Vec<RistrettoPoint>) =
+        valid.into_iter().take(params.t + 1).unzip();
+
+    let secret: RistrettoPoint = shares
+        .iter()
+        .zip(lagrange_coefficients(&indices))
+        .map(|(share, lambda)| share * lambda)
+        .sum();
+
+    Ok(ReconstructedSecret {
+        scheme: params.scheme,
+        indices,
+        skipped,
+        secret: secret.compress(),
+    })
+}
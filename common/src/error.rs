@@ -22,5 +22,61 @@ error_chain! {
             description("Invalid Dealer Proof")
             display("Invalid Dealer Proof: {}", t)
         }
+        KeystoreError(t: String) {
+            description("Keystore Error")
+            display("Keystore Error: {}", t)
+        }
+        UndecodableTally {
+            description("Undecodable Tally")
+            display("The decrypted tally is not a count of valid ballots")
+        }
+        InvalidWeight(value: u64, weight: u64) {
+            description("Invalid Weight")
+            display("Cannot cast {} votes with a weight of {}", value, weight)
+        }
+        InvalidSelection(min: u64, max: u64, candidates: usize) {
+            description("Invalid Selection")
+            display("Ballots must approve between {} and {} of {} candidates", min, max, candidates)
+        }
+        KeyMismatch(index: usize) {
+            description("Key Mismatch")
+            display("The private key of party {} does not match its public key", index)
+        }
+        TooFewDealings(valid: usize, needed: usize) {
+            description("Too Few Dealings")
+            display("Only {} dealings are valid, at least {} are needed", valid, needed)
+        }
+        UnrecoverableSecret(dealer: usize) {
+            description("Unrecoverable Secret")
+            display("Dealer {} withheld its opening and too few shares were decrypted", dealer)
+        }
+        TooFewPartialDecryptions(valid: usize, needed: usize) {
+            description("Too Few Partial Decryptions")
+            display("Only {} partial decryptions are valid, at least {} are needed", valid, needed)
+        }
+        TooFewDecryptedShares(valid: usize, needed: usize) {
+            description("Too Few Decrypted Shares")
+            display("Only {} decrypted shares are valid, at least {} are needed", valid, needed)
+        }
+        RecoveredKeyMismatch {
+            description("Recovered Key Mismatch")
+            display("The recovered key does not match the public key published at escrow time")
+        }
+        BidTooHigh(value: u64, max_bid: u64) {
+            description("Bid Too High")
+            display("Cannot bid {} in an auction capped at {}", value, max_bid)
+        }
+        TooEarly(round: u64, release_round: u64) {
+            description("Too Early")
+            display("Round {} is before the release round {}", round, release_round)
+        }
+        UndecryptablePayload {
+            description("Undecryptable Payload")
+            display("The payload does not decrypt under the recovered key")
+        }
+        SampleTooLarge(k: usize, n: usize) {
+            description("Sample Too Large")
+            display("Cannot select {} of {} candidates", k, n)
+        }
     }
 }
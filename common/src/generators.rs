@@ -0,0 +1,170 @@
+use crate::{
+    error::{
+        Error,
+        ErrorKind::{CountMismatch, InvalidProof, UninitializedValue},
+    },
+    key_proof::KeyProof,
+};
+use blake3::Hasher;
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::RistrettoBasepointTable};
+use rayon::prelude::*;
+
+/// A base that can be multiplied by a scalar, either a plain point or a precomputed table
+pub trait MulBase: Sync {
+    fn mul_base(&self, scalar: &Scalar) -> RistrettoPoint;
+}
+
+impl MulBase for RistrettoPoint {
+    fn mul_base(&self, scalar: &Scalar) -> RistrettoPoint {
+        self * scalar
+    }
+}
+
+impl MulBase for RistrettoBasepointTable {
+    fn mul_base(&self, scalar: &Scalar) -> RistrettoPoint {
+        self * scalar
+    }
+}
+
+impl<T: MulBase> MulBase for &T {
+    fn mul_base(&self, scalar: &Scalar) -> RistrettoPoint {
+        (*self).mul_base(scalar)
+    }
+}
+
+/// Precomputed basepoint tables for the fixed bases of an election: G, H or pk0, and the tallier public keys.
+/// Built once and shared (behind an `Arc`) by dealers, parties, voters and the bulletin board.
+#[derive(Clone)]
+pub struct Generators {
+    G: (RistrettoPoint, RistrettoBasepointTable),
+    H: Option<(RistrettoPoint, RistrettoBasepointTable)>,
+    pk0: Option<(RistrettoPoint, RistrettoBasepointTable)>,
+    public_keys: Vec<RistrettoBasepointTable>,
+    public_key_points: Vec<RistrettoPoint>,
+}
+
+impl Generators {
+    pub fn new(G: &RistrettoPoint) -> Self {
+        Self {
+            G: (*G, RistrettoBasepointTable::create(G)),
+            H: None,
+            pk0: None,
+            public_keys: vec![],
+            public_key_points: vec![],
+        }
+    }
+
+    pub fn with_H(mut self, H: &RistrettoPoint) -> Self {
+        self.H = Some((*H, RistrettoBasepointTable::create(H)));
+        self
+    }
+
+    pub fn with_pk0(mut self, pk0: &RistrettoPoint) -> Self {
+        self.pk0 = Some((*pk0, RistrettoBasepointTable::create(pk0)));
+        self
+    }
+
+    /// Registers the parties' public keys (party i + 1 at position i), each of which must come
+    /// with a valid proof of possession for this session
+    pub fn with_public_keys(
+        mut self,
+        public_keys: &[RistrettoPoint],
+        key_proofs: &[KeyProof],
+    ) -> Result<Self, Error> {
+        if public_keys.len() != key_proofs.len() {
+            return Err(CountMismatch(
+                public_keys.len(),
+                "public keys",
+                key_proofs.len(),
+                "key proofs",
+            )
+            .into());
+        }
+        let session = self.session();
+        if let Some(i) = public_keys
+            .par_iter()
+            .zip(key_proofs.par_iter())
+            .enumerate()
+            .find_any(|(i, (public_key, proof))| {
+                !proof.verify(self.G_table(), &session, i + 1, public_key)
+            })
+            .map(|(i, _)| i)
+        {
+            return Err(InvalidProof(format!("key proof of party {}", i + 1)).into());
+        }
+
+        self.public_keys = public_keys
+            .par_iter()
+            .map(RistrettoBasepointTable::create)
+            .collect();
+        self.public_key_points = public_keys.to_vec();
+        Ok(self)
+    }
+
+    /// Identifies the election the generators were set up for; key proofs are bound to it
+    pub fn session(&self) -> [u8; 32] {
+        let mut hasher = Hasher::new();
+        hasher.update(b"session");
+        hasher.update(self.G.0.compress().as_bytes());
+        for (label, base) in [(b"H", &self.H), (b"0", &self.pk0)] {
+            if let Some((point, _)) = base {
+                hasher.update(label);
+                hasher.update(point.compress().as_bytes());
+            }
+        }
+        *hasher.finalize().as_bytes()
+    }
+
+    pub fn G(&self) -> &RistrettoPoint {
+        &self.G.0
+    }
+
+    pub fn G_table(&self) -> &RistrettoBasepointTable {
+        &self.G.1
+    }
+
+    pub fn H(&self) -> Result<&RistrettoPoint, Error> {
+        match &self.H {
+            Some((H, _)) => Ok(H),
+            None => Err(UninitializedValue("generators.H").into()),
+        }
+    }
+
+    pub fn H_table(&self) -> Result<&RistrettoBasepointTable, Error> {
+        match &self.H {
+            Some((_, table)) => Ok(table),
+            None => Err(UninitializedValue("generators.H").into()),
+        }
+    }
+
+    pub fn pk0(&self) -> Result<&RistrettoPoint, Error> {
+        match &self.pk0 {
+            Some((pk0, _)) => Ok(pk0),
+            None => Err(UninitializedValue("generators.pk0").into()),
+        }
+    }
+
+    pub fn pk0_table(&self) -> Result<&RistrettoBasepointTable, Error> {
+        match &self.pk0 {
+            Some((_, table)) => Ok(table),
+            None => Err(UninitializedValue("generators.pk0").into()),
+        }
+    }
+
+    pub fn public_keys(&self) -> &[RistrettoPoint] {
+        &self.public_key_points
+    }
+
+    pub fn public_key_tables(&self) -> &[RistrettoBasepointTable] {
+        &self.public_keys
+    }
+
+    /// pk0 (if set) followed by the public keys, i.e. the bases shares are encrypted under
+    pub fn dealing_tables(&self) -> Vec<&RistrettoBasepointTable> {
+        self.pk0
+            .iter()
+            .map(|(_, table)| table)
+            .chain(self.public_keys.iter())
+            .collect()
+    }
+}
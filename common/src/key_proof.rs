@@ -0,0 +1,95 @@
+use blake3::Hasher;
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::RistrettoBasepointTable};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::{random::random_scalar, secret::Secret};
+
+/// Schnorr proof of knowledge of the private key behind a registered public key.
+/// The challenge is bound to the election session and the party's index, so a proof
+/// cannot be replayed for another slot or another election.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyProof {
+    pub c: Scalar,
+    pub z: Scalar,
+}
+
+fn challenge(
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+    session: &[u8; 32],
+    index: usize,
+    public_key: &RistrettoPoint,
+    commitment: &RistrettoPoint,
+) -> Scalar {
+    hasher.update(b"key proof");
+    hasher.update(session);
+    hasher.update(&(index as u64).to_le_bytes());
+    hasher.update(public_key.compress().as_bytes());
+    hasher.update(commitment.compress().as_bytes());
+
+    hasher.finalize_xof().fill(buf);
+    let c = Scalar::from_bytes_mod_order_wide(buf);
+
+    hasher.reset();
+    buf.zeroize();
+
+    c
+}
+
+impl KeyProof {
+    pub fn prove<R>(
+        rng: &mut R,
+        G: &RistrettoBasepointTable,
+        session: &[u8; 32],
+        index: usize,
+        private_key: &Scalar,
+        public_key: &RistrettoPoint,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut hasher = Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let r = Secret::new(random_scalar(rng));
+        let commitment = G * r.expose();
+
+        let c = challenge(
+            &mut hasher,
+            &mut buf,
+            session,
+            index,
+            public_key,
+            &commitment,
+        );
+
+        Self {
+            c,
+            z: r.expose() + c * private_key,
+        }
+    }
+
+    pub fn verify(
+        &self,
+        G: &RistrettoBasepointTable,
+        session: &[u8; 32],
+        index: usize,
+        public_key: &RistrettoPoint,
+    ) -> bool {
+        let mut hasher = Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let commitment = G * &self.z - public_key * self.c;
+
+        self.c
+            == challenge(
+                &mut hasher,
+                &mut buf,
+                session,
+                index,
+                public_key,
+                &commitment,
+            )
+    }
+}
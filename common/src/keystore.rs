@@ -0,0 +1,196 @@
+use std::{fs, path::Path};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+use curve25519_dalek::{
+    Scalar,
+    ristretto::{CompressedRistretto, RistrettoBasepointTable},
+};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::{
+    error::{Error, ErrorKind::KeystoreError},
+    secret::Secret,
+};
+
+const MAGIC: &[u8; 8] = b"PPVSSKS1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+// magic | m_cost | t_cost | p_cost | salt | nonce | index | public key
+const HEADER_LEN: usize = 8 + 3 * 4 + SALT_LEN + NONCE_LEN + 8 + 32;
+// encrypted private key and the poly1305 tag
+const CIPHERTEXT_LEN: usize = 32 + 16;
+// upper bounds on the kdf parameters, the header is read before it is authenticated
+/// 256 MiB
+pub const MAX_M_COST: u32 = 1 << 18;
+pub const MAX_T_COST: u32 = 16;
+pub const MAX_P_COST: u32 = 16;
+
+/// A party's long-lived keypair, as stored in a keystore
+pub struct Keypair {
+    pub index: usize,
+    pub public_key: CompressedRistretto,
+    private_key: Secret<Scalar>,
+}
+
+impl Keypair {
+    pub fn new(index: usize, private_key: &Scalar, public_key: CompressedRistretto) -> Self {
+        Self {
+            index,
+            public_key,
+            private_key: Secret::new(*private_key),
+        }
+    }
+
+    pub fn private_key(&self) -> &Scalar {
+        self.private_key.expose()
+    }
+}
+
+/// Argon2id cost parameters used to derive the keystore encryption key from the password,
+/// at most `MAX_M_COST`, `MAX_T_COST` and `MAX_P_COST`
+#[derive(Clone, Copy, Debug)]
+pub struct KdfParams {
+    /// memory in KiB
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+fn derive_key(password: &[u8], salt: &[u8], params: &KdfParams) -> Result<Secret<[u8; 32]>, Error> {
+    if params.m_cost > MAX_M_COST || params.t_cost > MAX_T_COST || params.p_cost > MAX_P_COST {
+        return Err(KeystoreError(format!("kdf params out of bounds: {params:?}")).into());
+    }
+    let params = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(32))
+        .map_err(|e| KeystoreError(format!("kdf params: {e}")))?;
+    let mut key = Secret::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password, salt, key.expose_mut())
+        .map_err(|e| KeystoreError(format!("kdf: {e}")))?;
+    Ok(key)
+}
+
+/// Encrypts the keypair under `password`. The index and public key are left in the clear
+/// but authenticated together with the kdf parameters.
+pub fn export_keypair<R>(
+    keypair: &Keypair,
+    password: &[u8],
+    params: &KdfParams,
+    rng: &mut R,
+) -> Result<Vec<u8>, Error>
+where
+    R: CryptoRng + RngCore,
+{
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce);
+
+    let mut out = Vec::with_capacity(HEADER_LEN + CIPHERTEXT_LEN);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&params.m_cost.to_le_bytes());
+    out.extend_from_slice(&params.t_cost.to_le_bytes());
+    out.extend_from_slice(&params.p_cost.to_le_bytes());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&(keypair.index as u64).to_le_bytes());
+    out.extend_from_slice(keypair.public_key.as_bytes());
+
+    let key = derive_key(password, &salt, params)?;
+    let mut plaintext = keypair.private_key().to_bytes();
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key.expose())).encrypt(
+        Nonce::from_slice(&nonce),
+        Payload {
+            msg: &plaintext,
+            aad: &out,
+        },
+    );
+    plaintext.zeroize();
+
+    out.extend_from_slice(&ciphertext.map_err(|_| KeystoreError("encryption failed".into()))?);
+    Ok(out)
+}
+
+/// Decrypts a keystore and checks that its private key belongs to its public key under `G`
+pub fn import_keypair(
+    bytes: &[u8],
+    password: &[u8],
+    G: &RistrettoBasepointTable,
+) -> Result<Keypair, Error> {
+    if bytes.len() != HEADER_LEN + CIPHERTEXT_LEN || &bytes[..8] != MAGIC {
+        return Err(KeystoreError("not a keystore".into()).into());
+    }
+    let (header, ciphertext) = bytes.split_at(HEADER_LEN);
+    let u32_at = |i: usize| u32::from_le_bytes(header[i..i + 4].try_into().unwrap());
+    let params = KdfParams {
+        m_cost: u32_at(8),
+        t_cost: u32_at(12),
+        p_cost: u32_at(16),
+    };
+    let salt = &header[20..20 + SALT_LEN];
+    let nonce = &header[20 + SALT_LEN..20 + SALT_LEN + NONCE_LEN];
+    let rest = &header[20 + SALT_LEN + NONCE_LEN..];
+    let index = u64::from_le_bytes(rest[..8].try_into().unwrap()) as usize;
+    let public_key = CompressedRistretto(rest[8..40].try_into().unwrap());
+
+    let key = derive_key(password, salt, &params)?;
+    let plaintext = Secret::new(
+        ChaCha20Poly1305::new(Key::from_slice(key.expose()))
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| KeystoreError("wrong password or corrupted keystore".into()))?,
+    );
+
+    let private_key: [u8; 32] = plaintext.expose()[..].try_into().unwrap();
+    let private_key = Secret::new(
+        Option::<Scalar>::from(Scalar::from_canonical_bytes(private_key))
+            .ok_or_else(|| KeystoreError("non canonical private key".into()))?,
+    );
+    if (G * private_key.expose()).compress() != public_key {
+        return Err(KeystoreError("the private key does not match the public key".into()).into());
+    }
+
+    Ok(Keypair::new(index, private_key.expose(), public_key))
+}
+
+pub fn write_keystore<R>(
+    path: &Path,
+    keypair: &Keypair,
+    password: &[u8],
+    params: &KdfParams,
+    rng: &mut R,
+) -> Result<(), Error>
+where
+    R: CryptoRng + RngCore,
+{
+    let bytes = export_keypair(keypair, password, params, rng)?;
+    fs::write(path, bytes).map_err(|e| KeystoreError(format!("{}: {e}", path.display())).into())
+}
+
+pub fn read_keystore(
+    path: &Path,
+    password: &[u8],
+    G: &RistrettoBasepointTable,
+) -> Result<Keypair, Error> {
+    let bytes = fs::read(path).map_err(|e| KeystoreError(format!("{}: {e}", path.display())))?;
+    import_keypair(&bytes, password, G)
+}
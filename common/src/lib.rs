@@ -1,4 +1,12 @@
+#![allow(non_snake_case)]
+
 pub mod error;
+pub mod generators;
+pub mod key_proof;
+pub mod keystore;
 pub mod polynomial;
 pub mod random;
+pub mod range_proof;
+pub mod secret;
+pub mod signature;
 pub mod utils;
@@ -2,8 +2,9 @@ use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
 
 use rand::{CryptoRng, RngCore};
 use rayon::prelude::*;
+use zeroize::Zeroize;
 
-use crate::random::random_scalar;
+use crate::{generators::MulBase, random::random_scalar};
 
 #[derive(Clone)]
 pub struct Polynomial {
@@ -15,6 +16,10 @@ impl Polynomial {
         self.coefficients.len()
     }
 
+    pub fn coefficients(&self) -> &[Scalar] {
+        &self.coefficients
+    }
+
     pub fn coef_at(&self, index: usize) -> Option<Scalar> {
         if index < self.coefficients.len() - 1 {
             Some(self.coefficients[index])
@@ -93,9 +98,9 @@ impl Polynomial {
             .sum()
     }
 
-    pub fn evaluate_multiply(
+    pub fn evaluate_multiply<B: MulBase>(
         &self,
-        points: &Vec<RistrettoPoint>,
+        points: &[B],
         x_0: usize,
     ) -> (Vec<Scalar>, Vec<RistrettoPoint>) {
         points
@@ -126,15 +131,15 @@ impl Polynomial {
                         acc_f + coef_f * x_pow
                     });
 
-                (f_val, (f_val * point))
+                (f_val, point.mul_base(&f_val))
             })
             .unzip()
     }
 
-    pub fn evaluate_multiply_two_ppvss(
+    pub fn evaluate_multiply_two_ppvss<B: MulBase>(
         &self,
         other: &Self,
-        points: &Vec<RistrettoPoint>,
+        points: &[B],
     ) -> (Vec<CompressedRistretto>, Vec<CompressedRistretto>) {
         points
             .par_iter()
@@ -172,15 +177,18 @@ impl Polynomial {
                         },
                     );
 
-                ((f_val * point).compress(), (r_val * point).compress())
+                (
+                    point.mul_base(&f_val).compress(),
+                    point.mul_base(&r_val).compress(),
+                )
             })
             .collect()
     }
 
-    pub fn evaluate_multiply_two(
+    pub fn evaluate_multiply_two<B: MulBase>(
         &self,
         other: &Self,
-        points: &Vec<RistrettoPoint>,
+        points: &[B],
     ) -> (Vec<CompressedRistretto>, Vec<CompressedRistretto>) {
         points
             .par_iter()
@@ -212,7 +220,10 @@ impl Polynomial {
                         },
                     );
 
-                ((f_val * point).compress(), (r_val * point).compress())
+                (
+                    point.mul_base(&f_val).compress(),
+                    point.mul_base(&r_val).compress(),
+                )
             })
             .collect()
     }
@@ -238,10 +249,10 @@ impl Polynomial {
         }
     }
 
-    pub fn mul_with_point_compress(&self, point: &RistrettoPoint) -> Vec<CompressedRistretto> {
+    pub fn mul_with_point_compress<B: MulBase>(&self, point: &B) -> Vec<CompressedRistretto> {
         self.coefficients
             .par_iter()
-            .map(|coef| (coef * point).compress())
+            .map(|coef| point.mul_base(coef).compress())
             .collect()
     }
 
@@ -261,6 +272,18 @@ impl Polynomial {
             });
     }
 }
+impl From<Vec<Scalar>> for Polynomial {
+    fn from(coefficients: Vec<Scalar>) -> Self {
+        Polynomial { coefficients }
+    }
+}
+
+impl Zeroize for Polynomial {
+    fn zeroize(&mut self) {
+        self.coefficients.zeroize();
+    }
+}
+
 impl std::fmt::Display for Polynomial {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
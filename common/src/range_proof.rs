@@ -0,0 +1,186 @@
+use blake3::Hasher;
+use curve25519_dalek::{
+    RistrettoPoint, Scalar,
+    ristretto::{CompressedRistretto, RistrettoBasepointTable},
+    traits::Identity,
+};
+use rand::{CryptoRng, RngCore};
+
+use crate::{
+    error::Error, generators::Generators, random::random_scalar, secret::Secret,
+    utils::batch_decompress_ristretto_points,
+};
+
+/// Disjunctive proof that u = G * (s + bit) for a bit in {0, 1}, where c0 = base * s. The
+/// e-voting crates implement it with their vote proofs, over pk0 or H.
+pub trait BitProof: Clone {
+    type Compressed: Clone;
+
+    /// the base of c0, i.e. pk0 or H
+    fn base(generators: &Generators) -> Result<&RistrettoBasepointTable, Error>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn prove<R>(
+        rng: &mut R,
+        generators: &Generators,
+        u: &RistrettoPoint,
+        c0: &RistrettoPoint,
+        s: &Scalar,
+        bit: bool,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Self::Compressed, Error>
+    where
+        R: CryptoRng + RngCore;
+
+    fn verify(
+        &self,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        generators: &Generators,
+        u: &RistrettoPoint,
+        c0: &RistrettoPoint,
+    ) -> bool;
+
+    fn compress(&self) -> Self::Compressed;
+
+    fn decompress(compressed: Self::Compressed) -> Self;
+}
+
+/// number of bits needed to write any value in [0, weight]
+fn bit_length(weight: u64) -> usize {
+    (u64::BITS - weight.leading_zeros()).max(1) as usize
+}
+
+/// Splits `s` into `bits` scalars s_j with sum(2^j * s_j) = s
+fn split_exponent<R>(rng: &mut R, s: &Scalar, bits: usize) -> Secret<Vec<Scalar>>
+where
+    R: CryptoRng + RngCore,
+{
+    let mut splits: Vec<Scalar> = (0..bits - 1).map(|_| random_scalar(rng)).collect();
+    let partial: Scalar = splits
+        .iter()
+        .enumerate()
+        .map(|(j, s_j)| Scalar::from(1u64 << j) * s_j)
+        .sum();
+    splits.push((s - partial) * Scalar::from(1u64 << (bits - 1)).invert());
+    Secret::new(splits)
+}
+
+/// sum(2^j * (c0_j, u_j))
+fn recombine(bits: &[(RistrettoPoint, RistrettoPoint)]) -> (RistrettoPoint, RistrettoPoint) {
+    bits.iter().enumerate().fold(
+        (RistrettoPoint::identity(), RistrettoPoint::identity()),
+        |(c0, u), (j, (c0_j, u_j))| {
+            let power = Scalar::from(1u64 << j);
+            (c0 + c0_j * power, u + u_j * power)
+        },
+    )
+}
+
+/// Proof that an encrypted vote holds a value in [0, w]. Both the value and w - value are split
+/// into bits, each bit is a (c0_j, u_j) pair carrying a `BitProof`.
+#[derive(Clone)]
+pub struct CompressedRangeProof<P: BitProof> {
+    /// the bits of the value, then the bits of w - value
+    bits: Vec<(CompressedRistretto, CompressedRistretto)>,
+    proofs: Vec<P::Compressed>,
+}
+
+impl<P: BitProof> CompressedRangeProof<P> {
+    /// `s` is the exponent of c0, i.e. c0 = base * s and the encrypted vote is G * (s + value)
+    pub fn prove<R>(
+        rng: &mut R,
+        generators: &Generators,
+        weight: u64,
+        value: u64,
+        s: &Scalar,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let base = P::base(generators)?;
+        let length = bit_length(weight);
+
+        let mut bits = Vec::with_capacity(2 * length);
+        let mut proofs = Vec::with_capacity(2 * length);
+        // w - value is encrypted under -s: (-c0, G * w - u)
+        for (plaintext, exponent) in [(value, *s), (weight - value, -s)] {
+            let splits = split_exponent(rng, &exponent, length);
+            for (j, s_j) in splits.expose().iter().enumerate() {
+                let bit = (plaintext >> j) & 1 == 1;
+                let c0_j = base * s_j;
+                let u_j = generators.G_table() * &(s_j + Scalar::from(bit as u64));
+
+                proofs.push(P::prove(
+                    rng, generators, &u_j, &c0_j, s_j, bit, hasher, buf,
+                )?);
+                bits.push((c0_j.compress(), u_j.compress()));
+            }
+        }
+
+        Ok(Self { bits, proofs })
+    }
+
+    pub fn decompress(self) -> Result<RangeProof<P>, Error> {
+        let (c0s, us): (Vec<CompressedRistretto>, Vec<CompressedRistretto>) =
+            self.bits.into_iter().unzip();
+
+        Ok(RangeProof {
+            bits: batch_decompress_ristretto_points(&c0s)?
+                .into_iter()
+                .zip(batch_decompress_ristretto_points(&us)?)
+                .collect(),
+            proofs: self.proofs.into_iter().map(P::decompress).collect(),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct RangeProof<P: BitProof> {
+    bits: Vec<(RistrettoPoint, RistrettoPoint)>,
+    proofs: Vec<P>,
+}
+
+impl<P: BitProof> RangeProof<P> {
+    pub fn verify(
+        &self,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        generators: &Generators,
+        weight: u64,
+        encrypted_vote: &RistrettoPoint,
+        c0: &RistrettoPoint,
+    ) -> bool {
+        let length = bit_length(weight);
+        if self.bits.len() != 2 * length || self.proofs.len() != 2 * length {
+            return false;
+        }
+        let (value_bits, remainder_bits) = self.bits.split_at(length);
+
+        recombine(value_bits) == (*c0, *encrypted_vote)
+            && recombine(remainder_bits)
+                == (
+                    -c0,
+                    generators.G_table() * &Scalar::from(weight) - encrypted_vote,
+                )
+            && self
+                .proofs
+                .iter()
+                .zip(&self.bits)
+                .all(|(proof, (c0_j, u_j))| proof.verify(hasher, buf, generators, u_j, c0_j))
+    }
+
+    pub fn compress(&self) -> CompressedRangeProof<P> {
+        CompressedRangeProof {
+            bits: self
+                .bits
+                .iter()
+                .map(|(c0_j, u_j)| (c0_j.compress(), u_j.compress()))
+                .collect(),
+            proofs: self.proofs.iter().map(P::compress).collect(),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use blake3::Hasher;
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::RistrettoBasepointTable};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::{random::random_scalar, secret::Secret};
+
+/// Schnorr signature over an arbitrary message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub c: Scalar,
+    pub z: Scalar,
+}
+
+fn challenge(public_key: &RistrettoPoint, commitment: &RistrettoPoint, message: &[u8]) -> Scalar {
+    let mut hasher = Hasher::new();
+    let mut buf = [0u8; 64];
+
+    hasher.update(b"signature");
+    hasher.update(public_key.compress().as_bytes());
+    hasher.update(commitment.compress().as_bytes());
+    hasher.update(message);
+
+    hasher.finalize_xof().fill(&mut buf);
+    let c = Scalar::from_bytes_mod_order_wide(&buf);
+    buf.zeroize();
+
+    c
+}
+
+impl Signature {
+    pub fn verify(
+        &self,
+        G: &RistrettoBasepointTable,
+        public_key: &RistrettoPoint,
+        message: &[u8],
+    ) -> bool {
+        let commitment = G * &self.z - public_key * self.c;
+        self.c == challenge(public_key, &commitment, message)
+    }
+}
+
+pub struct SigningKey {
+    private_key: Secret<Scalar>,
+    pub public_key: RistrettoPoint,
+}
+
+impl SigningKey {
+    pub fn generate<R>(rng: &mut R, G: &RistrettoBasepointTable) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let private_key = Secret::new(random_scalar(rng));
+        let public_key = G * private_key.expose();
+        Self {
+            private_key,
+            public_key,
+        }
+    }
+
+    /// Signs with an existing key, e.g. a party's registered one
+    pub fn from_private_key(private_key: &Scalar, G: &RistrettoBasepointTable) -> Self {
+        Self {
+            private_key: Secret::new(*private_key),
+            public_key: G * private_key,
+        }
+    }
+
+    pub fn sign<R>(&self, rng: &mut R, G: &RistrettoBasepointTable, message: &[u8]) -> Signature
+    where
+        R: CryptoRng + RngCore,
+    {
+        let r = Secret::new(random_scalar(rng));
+        let c = challenge(&self.public_key, &(G * r.expose()), message);
+
+        Signature {
+            c,
+            z: r.expose() + c * self.private_key.expose(),
+        }
+    }
+}
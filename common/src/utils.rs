@@ -1,5 +1,7 @@
+use blake3::Hasher;
 use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
 use rayon::prelude::*;
+use zeroize::Zeroize;
 
 use crate::error::{Error, ErrorKind::PointDecompressionError};
 
@@ -40,3 +42,74 @@ pub fn batch_decompress_ristretto_points(
         .map(|compressed_point| decompress_ristretto_point(*compressed_point))
         .collect()
 }
+
+/// Lagrange coefficients at x = 0 for the given (1-based) party indices
+pub fn lagrange_coefficients(indices: &[usize]) -> Vec<Scalar> {
+    indices
+        .par_iter()
+        .map(|i| {
+            let zq_i = Scalar::from(*i as u64);
+            let mut lambda_i = Scalar::ONE;
+            for j in indices {
+                if j != i {
+                    let zq_j = Scalar::from(*j as u64);
+
+                    lambda_i *= zq_j * ((zq_j - zq_i).invert());
+                }
+            }
+            lambda_i
+        })
+        .collect()
+}
+
+/// Checks a party's proof that `decrypted_share` is `encrypted_share` decrypted under `public_key`
+pub fn verify_decrypted_share(
+    G: &RistrettoPoint,
+    public_key: &RistrettoPoint,
+    encrypted_share: &RistrettoPoint,
+    decrypted_share: &RistrettoPoint,
+    proof: &(Scalar, Scalar),
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+) -> bool {
+    let (d, z) = proof;
+
+    hasher.update(public_key.compress().as_bytes());
+    hasher.update(encrypted_share.compress().as_bytes());
+    hasher.update(((G * z) - (public_key * d)).compress().as_bytes());
+    hasher.update(
+        ((decrypted_share * z) - (encrypted_share * d))
+            .compress()
+            .as_bytes(),
+    );
+    hasher.finalize_xof().fill(buf);
+
+    let reconstructed_d = Scalar::from_bytes_mod_order_wide(buf);
+
+    hasher.reset();
+    buf.zeroize();
+
+    *d == reconstructed_d
+}
+
+/// label | context | key hashed to a scalar, which pads a scalar sealed to whoever knows `key`,
+/// e.g. a Diffie-Hellman key
+pub fn seal(
+    label: &[u8],
+    context: &[&[u8]],
+    key: &RistrettoPoint,
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+) -> Scalar {
+    hasher.update(label);
+    context.iter().for_each(|part| _ = hasher.update(part));
+    hasher.update(key.compress().as_bytes());
+
+    hasher.finalize_xof().fill(buf);
+    let seal = Scalar::from_bytes_mod_order_wide(buf);
+
+    hasher.reset();
+    buf.zeroize();
+
+    seal
+}
@@ -0,0 +1,188 @@
+use blake3::Hasher;
+use common::{
+    generators::Generators,
+    signature::Signature,
+    utils::{batch_decompress_ristretto_points, seal, verify_decrypted_share},
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto, traits::Identity};
+use schoenmakers::utils::verify_encrypted_shares_standalone;
+
+/// "dkg dealing" | session | dealer | encrypted shares | proof | commitments | G * e | sealed shares
+pub fn dealing_message(
+    session: &[u8; 32],
+    dealer: usize,
+    encrypted_shares: &[CompressedRistretto],
+    proof: &(Scalar, Vec<Scalar>),
+    commitments: &[CompressedRistretto],
+    ephemeral_key: &CompressedRistretto,
+    sealed_shares: &[Scalar],
+) -> Vec<u8> {
+    b"dkg dealing"
+        .iter()
+        .chain(session)
+        .chain(&(dealer as u64).to_le_bytes())
+        .chain(encrypted_shares.iter().flat_map(|share| share.as_bytes()))
+        .chain(proof.0.as_bytes())
+        .chain(proof.1.iter().flat_map(|z| z.as_bytes()))
+        .chain(commitments.iter().flat_map(|point| point.as_bytes()))
+        .chain(ephemeral_key.as_bytes())
+        .chain(sealed_shares.iter().flat_map(|share| share.as_bytes()))
+        .copied()
+        .collect()
+}
+
+/// A party's schoenmakers dealing of a secret. Next to the encrypted shares pk_i * p(i), every
+/// p(i) is sealed to party i under a Diffie-Hellman key with `ephemeral_key`, so that parties end
+/// up with scalar key shares rather than G * p(i).
+#[derive(Clone)]
+pub struct Dealing {
+    /// 1-based index of the dealing party
+    pub dealer: usize,
+    pub encrypted_shares: Vec<CompressedRistretto>,
+    pub proof: (Scalar, Vec<Scalar>),
+    /// H * p's coefficients
+    pub commitments: Vec<CompressedRistretto>,
+    /// G * e
+    pub ephemeral_key: CompressedRistretto,
+    /// p(i) + seal(pk_i * e), one per party
+    pub sealed_shares: Vec<Scalar>,
+    /// by the dealer's registered key, over everything above
+    pub signature: Signature,
+}
+
+impl Dealing {
+    /// Whether `public_key` signed this dealing for the recipients in `generators`
+    pub fn verify_signature(&self, generators: &Generators, public_key: &RistrettoPoint) -> bool {
+        self.signature.verify(
+            generators.G_table(),
+            public_key,
+            &dealing_message(
+                &generators.session(),
+                self.dealer,
+                &self.encrypted_shares,
+                &self.proof,
+                &self.commitments,
+                &self.ephemeral_key,
+                &self.sealed_shares,
+            ),
+        )
+    }
+
+    /// Checks the encrypted shares against the commitments, the sealed shares are checked by
+    /// their recipients and the dealer's index and signature by the run
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let n = generators.public_keys().len();
+        let (d, z) = &self.proof;
+        if self.encrypted_shares.len() != n
+            || self.sealed_shares.len() != n
+            || z.len() != n
+            || self.commitments.len() != t + 1
+            || self.ephemeral_key.decompress().is_none()
+        {
+            return false;
+        }
+        let (Ok(H), Ok(encrypted_shares), Ok(commitments)) = (
+            generators.H_table(),
+            batch_decompress_ristretto_points(&self.encrypted_shares),
+            batch_decompress_ristretto_points(&self.commitments),
+        ) else {
+            return false;
+        };
+
+        verify_encrypted_shares_standalone(
+            H,
+            hasher,
+            buf,
+            &(self.encrypted_shares.clone(), encrypted_shares),
+            generators.public_key_tables(),
+            &commitments,
+            (d, z),
+            n,
+            t,
+        )
+        .unwrap_or(false)
+    }
+
+    /// Whether the dealt secret is zero, i.e. H * p(0) is the identity
+    pub fn shares_zero(&self) -> bool {
+        self.commitments.first() == Some(&RistrettoPoint::identity().compress())
+    }
+
+    /// Party `index`'s share, if the sealed share opens its encrypted share
+    pub fn unseal(
+        &self,
+        public_key: &RistrettoPoint,
+        index: usize,
+        shared_key: &RistrettoPoint,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Option<Scalar> {
+        let position = index.checked_sub(1)?;
+        let (sealed_share, encrypted_share) = (
+            self.sealed_shares.get(position)?,
+            self.encrypted_shares.get(position)?,
+        );
+        let share = sealed_share
+            - seal(
+                b"dkg seal",
+                &[self.ephemeral_key.as_bytes(), &(index as u64).to_le_bytes()],
+                shared_key,
+                hasher,
+                buf,
+            );
+
+        ((public_key * share).compress() == *encrypted_share).then_some(share)
+    }
+}
+
+/// Party `index` showing that its sealed share of `dealer`'s dealing does not open its encrypted
+/// share: it reveals G * e * sk with a DLEQ proof against its public key
+#[derive(Clone)]
+pub struct Complaint {
+    pub dealer: usize,
+    pub index: usize,
+    pub shared_key: CompressedRistretto,
+    pub proof: (Scalar, Scalar),
+}
+
+impl Complaint {
+    /// True if the complaint holds, i.e. the dealing is faulty
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        dealing: &Dealing,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let public_keys = generators.public_keys();
+        if self.dealer != dealing.dealer || self.index == 0 || self.index > public_keys.len() {
+            return false;
+        }
+        let (Some(ephemeral_key), Some(shared_key)) = (
+            dealing.ephemeral_key.decompress(),
+            self.shared_key.decompress(),
+        ) else {
+            return false;
+        };
+        let public_key = &public_keys[self.index - 1];
+
+        // shared_key = ephemeral_key * sk is proven like a decrypted share, Y = D * sk
+        verify_decrypted_share(
+            generators.G(),
+            public_key,
+            &shared_key,
+            &ephemeral_key,
+            &self.proof,
+            hasher,
+            buf,
+        ) && dealing
+            .unseal(public_key, self.index, &shared_key, hasher, buf)
+            .is_none()
+    }
+}
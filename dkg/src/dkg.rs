@@ -0,0 +1,218 @@
+use blake3::Hasher;
+use common::{
+    error::{Error, ErrorKind::TooFewDealings},
+    generators::Generators,
+};
+use curve25519_dalek::RistrettoPoint;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+use crate::{
+    dealing::{Complaint, Dealing},
+    group_key::GroupKey,
+    transcript::Transcript,
+};
+
+/// What a run of dealings produces
+#[derive(Clone)]
+pub enum Run {
+    /// a fresh key, every party deals a random secret
+    Generate,
+    /// new shares of this key, every party deals a sharing of zero
+    Refresh(GroupKey),
+    /// this key's secret for a new committee, old parties deal their key shares and sign with
+    /// the keys registered in the old committee's generators
+    Reshare(GroupKey, Arc<Generators>),
+}
+
+impl Run {
+    /// The key `dealer` registered, in the old committee when resharing
+    fn dealer_key<'a>(
+        &'a self,
+        generators: &'a Generators,
+        dealer: usize,
+    ) -> Option<&'a RistrettoPoint> {
+        let dealers = match self {
+            Run::Reshare(_, dealers) => dealers,
+            _ => generators,
+        };
+        dealer
+            .checked_sub(1)
+            .and_then(|i| dealers.public_keys().get(i))
+    }
+
+    /// Whether `dealing` is signed by its dealer's registered key
+    pub fn signed(&self, generators: &Generators, dealing: &Dealing) -> bool {
+        self.dealer_key(generators, dealing.dealer)
+            .is_some_and(|public_key| dealing.verify_signature(generators, public_key))
+    }
+
+    /// Whether `dealing` is signed, verifies for the recipients in `generators` and deals what this
+    /// run needs
+    pub fn admits(
+        &self,
+        generators: &Generators,
+        t: usize,
+        dealing: &Dealing,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let dealt = match self {
+            Run::Generate => (1..=generators.public_keys().len()).contains(&dealing.dealer),
+            Run::Refresh(_) => {
+                (1..=generators.public_keys().len()).contains(&dealing.dealer)
+                    && dealing.shares_zero()
+            }
+            // H * p(0) must be the dealer's old verification key H * F(i)
+            Run::Reshare(key, _) => {
+                dealing.dealer > 0
+                    && key
+                        .verification_keys
+                        .get(dealing.dealer - 1)
+                        .map(|verification_key| verification_key.compress())
+                        == dealing.commitments.first().copied()
+            }
+        };
+
+        dealt && self.signed(generators, dealing) && dealing.verify(generators, t, hasher, buf)
+    }
+
+    /// The key for n recipients with threshold t out of the qualified dealings
+    pub fn finish(&self, n: usize, t: usize, qualified: &[&Dealing]) -> Result<GroupKey, Error> {
+        let needed = match self {
+            Run::Generate => t + 1,
+            Run::Refresh(key) | Run::Reshare(key, _) => key.t + 1,
+        };
+        if qualified.len() < needed {
+            return Err(TooFewDealings(qualified.len(), needed).into());
+        }
+
+        match self {
+            Run::Generate => GroupKey::aggregate(n, t, qualified),
+            Run::Refresh(key) => Ok(key.add(&GroupKey::aggregate(n, t, qualified)?)),
+            Run::Reshare(..) => GroupKey::interpolate(n, t, qualified),
+        }
+    }
+}
+
+/// Collects one run of the key generation: every party's dealing, then the complaints against
+/// dealings whose sealed shares do not open
+pub struct Dkg {
+    pub t: usize,
+    /// G, H and the recipients' public keys
+    pub generators: Arc<Generators>,
+    pub run: Run,
+    pub dealings: Vec<(bool, Dealing)>,
+    /// the valid complaints, each disqualified a dealer
+    pub complaints: Vec<Complaint>,
+}
+
+impl Dkg {
+    pub fn new(t: usize, generators: &Arc<Generators>) -> Self {
+        let n = generators.public_keys().len();
+        Self {
+            t,
+            generators: generators.clone(),
+            run: Run::Generate,
+            dealings: Vec::with_capacity(n),
+            complaints: vec![],
+        }
+    }
+
+    /// A proactive refresh of `key`: the parties deal sharings of zero, which are added to the
+    /// key and their key shares
+    pub fn refresh(key: &GroupKey, generators: &Arc<Generators>) -> Self {
+        Self {
+            run: Run::Refresh(key.clone()),
+            ..Self::new(key.t, generators)
+        }
+    }
+
+    /// A handover of `key` from the committee in `dealers` to the one in `generators` with
+    /// threshold t: at least t+1 old parties deal their key shares, which the new parties combine
+    /// with Lagrange weights
+    pub fn reshare(
+        key: &GroupKey,
+        dealers: &Arc<Generators>,
+        t: usize,
+        generators: &Arc<Generators>,
+    ) -> Self {
+        Self {
+            run: Run::Reshare(key.clone(), dealers.clone()),
+            ..Self::new(t, generators)
+        }
+    }
+
+    /// A dealing not signed by its dealer and a party's second dealing are ignored
+    pub fn ingest_dealing(&mut self, dealing: Dealing) -> bool {
+        if !self.run.signed(&self.generators, &dealing)
+            || self
+                .dealings
+                .iter()
+                .any(|(_, ingested)| ingested.dealer == dealing.dealer)
+        {
+            return false;
+        }
+        self.dealings.push((false, dealing));
+        true
+    }
+
+    pub fn verify_dealings(&mut self) {
+        self.dealings.par_iter_mut().for_each_init(
+            || (Hasher::new(), [0u8; 64]),
+            |(hasher, buf), (status, dealing)| {
+                *status = self
+                    .run
+                    .admits(&self.generators, self.t, dealing, hasher, buf);
+            },
+        );
+    }
+
+    /// Disqualifies the complained-about dealer if the complaint holds
+    pub fn ingest_complaint(
+        &mut self,
+        complaint: Complaint,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let Some((status, dealing)) = self
+            .dealings
+            .iter_mut()
+            .find(|(status, dealing)| *status && dealing.dealer == complaint.dealer)
+        else {
+            return false;
+        };
+        let holds = complaint.verify(&self.generators, dealing, hasher, buf);
+        if holds {
+            *status = false;
+            self.complaints.push(complaint);
+        }
+        holds
+    }
+
+    /// Sums the qualified dealings into the group key, adds them to the refreshed key or
+    /// interpolates the reshared one. Needs more than t of them (the old t when refreshing or
+    /// resharing), so at least one comes from an honest party.
+    pub fn finalize(&self) -> Result<GroupKey, Error> {
+        let qualified: Vec<&Dealing> = self
+            .dealings
+            .iter()
+            .filter(|(status, _)| *status)
+            .map(|(_, dealing)| dealing)
+            .collect();
+
+        self.run
+            .finish(self.generators.public_keys().len(), self.t, &qualified)
+    }
+
+    pub fn transcript(&self) -> Transcript {
+        Transcript {
+            dealings: self
+                .dealings
+                .iter()
+                .map(|(_, dealing)| dealing.clone())
+                .collect(),
+            complaints: self.complaints.clone(),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use common::{
+    error::Error,
+    utils::{batch_decompress_ristretto_points, lagrange_coefficients},
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, traits::Identity};
+use rayon::prelude::*;
+
+use crate::dealing::Dealing;
+
+/// H * F(index), from H * F's coefficients
+pub fn evaluate_commitments(commitments: &[RistrettoPoint], index: usize) -> RistrettoPoint {
+    let x = Scalar::from(index as u64);
+    commitments
+        .iter()
+        .rev()
+        .fold(RistrettoPoint::identity(), |acc, commitment| {
+            acc * x + commitment
+        })
+}
+
+/// The joint secret S = sum of the qualified dealers' secrets, shared as F = sum of their
+/// polynomials (weighted when resharing). Everything here is public.
+#[derive(Clone, PartialEq, Eq)]
+pub struct GroupKey {
+    pub t: usize,
+    /// indices of the dealers whose dealings were summed last
+    pub qualified: Vec<usize>,
+    /// H * F's coefficients
+    pub commitments: Vec<RistrettoPoint>,
+    /// pk_i * F(i)
+    pub encrypted_shares: Vec<RistrettoPoint>,
+    /// H * F(i), one per party
+    pub verification_keys: Vec<RistrettoPoint>,
+    /// H * S
+    pub public_key: RistrettoPoint,
+}
+
+impl GroupKey {
+    /// Sums verified dealings for n parties homomorphically
+    pub fn aggregate(n: usize, t: usize, dealings: &[&Dealing]) -> Result<Self, Error> {
+        Self::weighted_sum(n, t, dealings, &vec![Scalar::ONE; dealings.len()])
+    }
+
+    /// Weighs verified dealings of old key shares with their dealers' Lagrange coefficients, so
+    /// F(0) is the old secret
+    pub fn interpolate(n: usize, t: usize, dealings: &[&Dealing]) -> Result<Self, Error> {
+        let indices: Vec<usize> = dealings.iter().map(|dealing| dealing.dealer).collect();
+        Self::weighted_sum(n, t, dealings, &lagrange_coefficients(&indices))
+    }
+
+    fn weighted_sum(
+        n: usize,
+        t: usize,
+        dealings: &[&Dealing],
+        weights: &[Scalar],
+    ) -> Result<Self, Error> {
+        let mut commitments = vec![RistrettoPoint::identity(); t + 1];
+        let mut encrypted_shares = vec![RistrettoPoint::identity(); n];
+        for (dealing, weight) in dealings.iter().zip(weights) {
+            commitments
+                .par_iter_mut()
+                .zip(batch_decompress_ristretto_points(&dealing.commitments)?)
+                .for_each(|(sum, commitment)| *sum += commitment * weight);
+            encrypted_shares
+                .par_iter_mut()
+                .zip(batch_decompress_ristretto_points(
+                    &dealing.encrypted_shares,
+                )?)
+                .for_each(|(sum, encrypted_share)| *sum += encrypted_share * weight);
+        }
+        let verification_keys = (1..=n)
+            .into_par_iter()
+            .map(|i| evaluate_commitments(&commitments, i))
+            .collect();
+
+        Ok(Self {
+            t,
+            qualified: dealings.iter().map(|dealing| dealing.dealer).collect(),
+            public_key: commitments[0],
+            commitments,
+            encrypted_shares,
+            verification_keys,
+        })
+    }
+
+    /// This key refreshed with `zero`, the aggregate of sharings of zero
+    pub fn add(&self, zero: &GroupKey) -> Self {
+        let sum = |a: &[RistrettoPoint], b: &[RistrettoPoint]| -> Vec<RistrettoPoint> {
+            a.iter().zip(b).map(|(a, b)| a + b).collect()
+        };
+
+        Self {
+            t: self.t,
+            qualified: zero.qualified.clone(),
+            commitments: sum(&self.commitments, &zero.commitments),
+            encrypted_shares: sum(&self.encrypted_shares, &zero.encrypted_shares),
+            verification_keys: sum(&self.verification_keys, &zero.verification_keys),
+            public_key: self.public_key + zero.public_key,
+        }
+    }
+}
@@ -0,0 +1,449 @@
+#![allow(non_snake_case)]
+
+pub mod dealing;
+pub mod dkg;
+pub mod group_key;
+pub mod participant;
+pub mod threshold_elgamal;
+pub mod transcript;
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        generators::Generators, key_proof::KeyProof, random::random_scalar, signature::SigningKey,
+        utils::lagrange_coefficients,
+    };
+    use curve25519_dalek::{RistrettoPoint, Scalar};
+    use std::sync::Arc;
+
+    use crate::{
+        dealing::{Dealing, dealing_message},
+        dkg::Dkg,
+        group_key::GroupKey,
+        participant::Participant,
+        threshold_elgamal::Ciphertext,
+    };
+
+    const N: usize = 7;
+    const T: usize = 3;
+
+    /// N participants holding the generators with everyone's public key
+    fn setup(rng: &mut rand::rngs::ThreadRng) -> (Arc<Generators>, Vec<Participant>) {
+        let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(rng));
+        let H: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(rng));
+        committee(rng, &Arc::new(Generators::new(&G).with_H(&H)), N, T)
+    }
+
+    fn committee(
+        rng: &mut rand::rngs::ThreadRng,
+        generators: &Arc<Generators>,
+        n: usize,
+        t: usize,
+    ) -> (Arc<Generators>, Vec<Participant>) {
+        let mut participants: Vec<Participant> = (1..=n)
+            .map(|i| Participant::new(generators, rng, n, t, i).unwrap())
+            .collect();
+        let public_keys: Vec<RistrettoPoint> = participants
+            .iter()
+            .map(|participant| participant.party.public_key.1)
+            .collect();
+        let key_proofs: Vec<KeyProof> = participants
+            .iter()
+            .map(|participant| participant.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (**generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+        for participant in &mut participants {
+            participant.ingest_generators(&generators).unwrap();
+        }
+
+        (generators, participants)
+    }
+
+    /// Signs a tampered dealing again with `participant`'s registered key
+    fn sign(
+        rng: &mut rand::rngs::ThreadRng,
+        generators: &Generators,
+        participant: &Participant,
+        dealing: &mut Dealing,
+    ) {
+        let signing_key =
+            SigningKey::from_private_key(participant.party.private_key(), generators.G_table());
+        let message = dealing_message(
+            &generators.session(),
+            dealing.dealer,
+            &dealing.encrypted_shares,
+            &dealing.proof,
+            &dealing.commitments,
+            &dealing.ephemeral_key,
+            &dealing.sealed_shares,
+        );
+        dealing.signature = signing_key.sign(rng, generators.G_table(), &message);
+    }
+
+    fn honest_dkg(
+        rng: &mut rand::rngs::ThreadRng,
+        generators: &Arc<Generators>,
+        participants: &mut [Participant],
+    ) -> GroupKey {
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let mut dkg = Dkg::new(T, generators);
+        for participant in participants.iter_mut() {
+            dkg.ingest_dealing(participant.deal(rng, &mut hasher, &mut buf).unwrap());
+        }
+        dkg.verify_dealings();
+        for participant in participants.iter_mut() {
+            for (_, dealing) in &dkg.dealings {
+                let complaint = participant
+                    .check_dealing(rng, &mut hasher, &mut buf, dealing)
+                    .unwrap();
+                assert!(complaint.is_none());
+            }
+        }
+
+        let key = dkg.finalize().unwrap();
+        for participant in participants.iter_mut() {
+            participant.combine(&key).unwrap();
+        }
+        key
+    }
+
+    #[test]
+    fn dkg() {
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let (generators, mut participants) = setup(&mut rng);
+        let H = *generators.H().unwrap();
+        let public_keys = generators.public_keys().to_vec();
+
+        let mut dkg = Dkg::new(T, &generators);
+        let mut dealings: Vec<Dealing> = vec![];
+        for participant in &mut participants {
+            dealings.push(participant.deal(&mut rng, &mut hasher, &mut buf).unwrap());
+        }
+        // seals a wrong share for party 2, caught by its complaint
+        dealings[0].sealed_shares[1] += Scalar::ONE;
+        // proof no longer matches, caught by verification
+        dealings[6].encrypted_shares.swap(0, 1);
+        // tampered dealings are only ingested once their dealers sign them again
+        assert!(!dkg.ingest_dealing(dealings[0].clone()));
+        sign(&mut rng, &generators, &participants[0], &mut dealings[0]);
+        sign(&mut rng, &generators, &participants[6], &mut dealings[6]);
+        // nor can a party deal in another's name
+        let mut forged = dealings[1].clone();
+        forged.dealer = 3;
+        sign(&mut rng, &generators, &participants[1], &mut forged);
+        assert!(!dkg.ingest_dealing(forged));
+        for dealing in dealings {
+            assert!(dkg.ingest_dealing(dealing));
+        }
+        dkg.verify_dealings();
+        assert_eq!(
+            dkg.dealings
+                .iter()
+                .map(|(status, _)| *status)
+                .collect::<Vec<bool>>(),
+            [true, true, true, true, true, true, false]
+        );
+
+        for participant in &mut participants {
+            let dealings: Vec<_> = dkg
+                .dealings
+                .iter()
+                .filter(|(status, _)| *status)
+                .map(|(_, dealing)| dealing.clone())
+                .collect();
+            for dealing in &dealings {
+                if let Some(complaint) = participant
+                    .check_dealing(&mut rng, &mut hasher, &mut buf, dealing)
+                    .unwrap()
+                {
+                    assert_eq!((complaint.dealer, complaint.index), (1, 2));
+                    assert!(dkg.ingest_complaint(complaint, &mut hasher, &mut buf));
+                }
+            }
+        }
+        // an honest dealer cannot be framed
+        let mut complaint = participants[0]
+            .check_dealing(&mut rng, &mut hasher, &mut buf, &dkg.dealings[0].1)
+            .unwrap();
+        assert!(complaint.is_none());
+        dkg.dealings[1].1.sealed_shares[0] += Scalar::ONE;
+        complaint = participants[0]
+            .check_dealing(&mut rng, &mut hasher, &mut buf, &dkg.dealings[1].1)
+            .unwrap();
+        dkg.dealings[1].1.sealed_shares[0] -= Scalar::ONE;
+        assert!(!dkg.ingest_complaint(complaint.unwrap(), &mut hasher, &mut buf));
+
+        let key = dkg.finalize().unwrap();
+        assert_eq!(key.qualified, [2, 3, 4, 5, 6]);
+        assert_eq!(dkg.complaints.len(), 1);
+        let transcript = dkg.transcript();
+        assert!(transcript.verify(&generators, &key, &mut hasher, &mut buf));
+        // a dealing whose signature does not match no longer qualifies
+        let mut forged = transcript.clone();
+        forged.dealings[2].signature = forged.dealings[3].signature;
+        assert!(!forged.verify(&generators, &key, &mut hasher, &mut buf));
+
+        let mut key_shares = vec![];
+        for participant in &mut participants {
+            participant.combine(&key).unwrap();
+            let key_share = *participant.key_share().unwrap();
+            let i = participant.party.index;
+            assert_eq!(H * key_share, key.verification_keys[i - 1]);
+            assert_eq!(public_keys[i - 1] * key_share, key.encrypted_shares[i - 1]);
+            key_shares.push(key_share);
+        }
+
+        // any t+1 key shares open the group key
+        for indices in [[1, 2, 3, 4], [2, 4, 6, 7]] {
+            let S: Scalar = lagrange_coefficients(&indices)
+                .iter()
+                .zip(indices)
+                .map(|(lambda, i)| lambda * key_shares[i - 1])
+                .sum();
+            assert_eq!(H * S, key.public_key);
+        }
+    }
+
+    #[test]
+    fn threshold_elgamal() {
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let (generators, mut participants) = setup(&mut rng);
+        let key = honest_dkg(&mut rng, &generators, &mut participants);
+
+        let message = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+        let ciphertext = Ciphertext::encrypt(&mut rng, &generators, &key, &message).unwrap();
+
+        let mut partials: Vec<_> = [1, 2, 4, 6, 7]
+            .iter()
+            .map(|i| {
+                participants[i - 1]
+                    .decrypt_partial(&mut rng, &mut hasher, &mut buf, &ciphertext)
+                    .unwrap()
+            })
+            .collect();
+        // a wrong share and a repeated tallier are both skipped
+        partials[0].share = (ciphertext.c1 * random_scalar(&mut rng)).compress();
+        partials.insert(2, partials[1].clone());
+
+        assert!(!partials[0].verify(&generators, &key, &ciphertext, &mut hasher, &mut buf));
+        assert_eq!(
+            ciphertext
+                .decrypt(&generators, &key, &partials, &mut hasher, &mut buf)
+                .unwrap(),
+            message
+        );
+        assert!(
+            ciphertext
+                .decrypt(&generators, &key, &partials[..4], &mut hasher, &mut buf)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn refresh() {
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let (generators, mut participants) = setup(&mut rng);
+        let key = honest_dkg(&mut rng, &generators, &mut participants);
+        let H = *generators.H().unwrap();
+        let old_shares: Vec<Scalar> = participants
+            .iter()
+            .map(|participant| *participant.key_share().unwrap())
+            .collect();
+
+        let mut refresh = Dkg::refresh(&key, &generators);
+        for participant in &mut participants {
+            let dealing = match participant.party.index {
+                // a sharing of anything but zero would change the secret
+                3 => participant.deal(&mut rng, &mut hasher, &mut buf),
+                _ => participant.deal_zero(&mut rng, &mut hasher, &mut buf),
+            };
+            refresh.ingest_dealing(dealing.unwrap());
+        }
+        refresh.verify_dealings();
+        assert_eq!(
+            refresh
+                .dealings
+                .iter()
+                .map(|(status, _)| *status)
+                .collect::<Vec<bool>>(),
+            [true, true, false, true, true, true, true]
+        );
+        for participant in &mut participants {
+            for (_, dealing) in refresh.dealings.iter().filter(|(status, _)| *status) {
+                let complaint = participant
+                    .check_dealing(&mut rng, &mut hasher, &mut buf, dealing)
+                    .unwrap();
+                assert!(complaint.is_none());
+            }
+        }
+
+        let refreshed = refresh.finalize().unwrap();
+        assert_eq!(refreshed.public_key, key.public_key);
+        assert_ne!(refreshed.verification_keys, key.verification_keys);
+
+        let mut new_shares = vec![];
+        for participant in &mut participants {
+            participant.refresh(&refreshed).unwrap();
+            let key_share = *participant.key_share().unwrap();
+            let i = participant.party.index;
+            assert_eq!(H * key_share, refreshed.verification_keys[i - 1]);
+            assert_ne!(key_share, old_shares[i - 1]);
+            new_shares.push(key_share);
+        }
+
+        // new shares open the same key, old and new shares do not mix
+        let indices = [1, 2, 3, 4];
+        let lambdas = lagrange_coefficients(&indices);
+        let open = |shares: [Scalar; 4]| -> RistrettoPoint {
+            H * lambdas
+                .iter()
+                .zip(shares)
+                .map(|(lambda, share)| lambda * share)
+                .sum::<Scalar>()
+        };
+        assert_eq!(
+            open([new_shares[0], new_shares[1], new_shares[2], new_shares[3]]),
+            key.public_key
+        );
+        assert_ne!(
+            open([old_shares[0], old_shares[1], new_shares[2], new_shares[3]]),
+            key.public_key
+        );
+
+        let transcript = refresh.transcript();
+        assert!(transcript.verify_refresh(&generators, &key, &refreshed, &mut hasher, &mut buf));
+        assert!(!transcript.verify_refresh(&generators, &key, &key, &mut hasher, &mut buf));
+        let mut forged = transcript.clone();
+        forged.dealings.push(transcript.dealings[0].clone());
+        assert!(!forged.verify_refresh(&generators, &key, &refreshed, &mut hasher, &mut buf));
+    }
+
+    #[test]
+    fn reshare() {
+        const N_NEW: usize = 5;
+        const T_NEW: usize = 2;
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let (generators, mut participants) = setup(&mut rng);
+        let key = honest_dkg(&mut rng, &generators, &mut participants);
+        let H = *generators.H().unwrap();
+        let (new_generators, mut new_participants) = committee(
+            &mut rng,
+            &Arc::new(Generators::new(generators.G()).with_H(&H)),
+            N_NEW,
+            T_NEW,
+        );
+
+        // parties 6 and 7 are gone, party 2 deals party 3's share
+        let mut reshare = Dkg::reshare(&key, &generators, T_NEW, &new_generators);
+        let mut dealings: Vec<_> = participants[..5]
+            .iter()
+            .map(|participant| {
+                participant
+                    .reshare(&mut rng, &mut hasher, &mut buf, &new_generators, T_NEW)
+                    .unwrap()
+            })
+            .collect();
+        dealings[1] = dealings[2].clone();
+        dealings[1].dealer = 2;
+        sign(
+            &mut rng,
+            &new_generators,
+            &participants[1],
+            &mut dealings[1],
+        );
+        for dealing in dealings {
+            assert!(reshare.ingest_dealing(dealing));
+        }
+        reshare.verify_dealings();
+        assert_eq!(
+            reshare
+                .dealings
+                .iter()
+                .map(|(status, _)| *status)
+                .collect::<Vec<bool>>(),
+            [true, false, true, true, true]
+        );
+        for participant in &mut new_participants {
+            for (_, dealing) in reshare.dealings.iter().filter(|(status, _)| *status) {
+                let complaint = participant
+                    .check_dealing(&mut rng, &mut hasher, &mut buf, dealing)
+                    .unwrap();
+                assert!(complaint.is_none());
+            }
+        }
+
+        let reshared = reshare.finalize().unwrap();
+        assert_eq!(reshared.t, T_NEW);
+        assert_eq!(reshared.qualified, [1, 3, 4, 5]);
+        assert_eq!(reshared.public_key, key.public_key);
+
+        let mut key_shares = vec![];
+        for participant in &mut new_participants {
+            participant.combine_reshared(&reshared).unwrap();
+            let key_share = *participant.key_share().unwrap();
+            assert_eq!(
+                H * key_share,
+                reshared.verification_keys[participant.party.index - 1]
+            );
+            key_shares.push(key_share);
+        }
+        for indices in [[1, 2, 3], [2, 4, 5]] {
+            let S: Scalar = lagrange_coefficients(&indices)
+                .iter()
+                .zip(indices)
+                .map(|(lambda, i)| lambda * key_shares[i - 1])
+                .sum();
+            assert_eq!(H * S, key.public_key);
+        }
+
+        let transcript = reshare.transcript();
+        assert!(transcript.verify_reshare(
+            &generators,
+            &new_generators,
+            &key,
+            &reshared,
+            &mut hasher,
+            &mut buf
+        ));
+        // the dealings are signed by the old committee, not the new one
+        assert!(!transcript.verify_reshare(
+            &new_generators,
+            &new_generators,
+            &key,
+            &reshared,
+            &mut hasher,
+            &mut buf
+        ));
+        // t old key shares do not pin down the secret
+        let mut short = transcript.clone();
+        short.dealings.truncate(3);
+        assert!(!short.verify_reshare(
+            &generators,
+            &new_generators,
+            &key,
+            &reshared,
+            &mut hasher,
+            &mut buf
+        ));
+    }
+}
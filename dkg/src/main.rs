@@ -0,0 +1,97 @@
+#![allow(non_snake_case)]
+
+use common::{generators::Generators, key_proof::KeyProof, random::random_scalar};
+use curve25519_dalek::RistrettoPoint;
+use dkg::{dkg::Dkg, participant::Participant, threshold_elgamal::Ciphertext};
+use std::sync::Arc;
+
+fn main() {
+    const N: usize = 64;
+    const T: usize = 31;
+
+    let mut rng = rand::rng();
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = [0u8; 64];
+
+    let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+    let H: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+    let generators = Arc::new(Generators::new(&G).with_H(&H));
+
+    let mut participants: Vec<Participant> = (1..=N)
+        .map(|i| Participant::new(&generators, &mut rng, N, T, i).unwrap())
+        .collect();
+    let public_keys: Vec<RistrettoPoint> = participants
+        .iter()
+        .map(|participant| participant.party.public_key.1)
+        .collect();
+    let key_proofs: Vec<KeyProof> = participants
+        .iter()
+        .map(|participant| participant.party.key_proof)
+        .collect();
+    let generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(&public_keys, &key_proofs)
+            .unwrap(),
+    );
+    for participant in &mut participants {
+        participant.ingest_generators(&generators).unwrap();
+    }
+
+    let mut dkg = Dkg::new(T, &generators);
+    for participant in &mut participants {
+        dkg.ingest_dealing(participant.deal(&mut rng, &mut hasher, &mut buf).unwrap());
+    }
+    dkg.verify_dealings();
+
+    let dealings: Vec<_> = dkg
+        .dealings
+        .iter()
+        .filter(|(status, _)| *status)
+        .map(|(_, dealing)| dealing.clone())
+        .collect();
+    for participant in &mut participants {
+        for dealing in &dealings {
+            if let Some(complaint) = participant
+                .check_dealing(&mut rng, &mut hasher, &mut buf, dealing)
+                .unwrap()
+            {
+                dkg.ingest_complaint(complaint, &mut hasher, &mut buf);
+            }
+        }
+    }
+
+    let key = dkg.finalize().unwrap();
+    for participant in &mut participants {
+        participant.combine(&key).unwrap();
+        assert_eq!(
+            H * participant.key_share().unwrap(),
+            key.verification_keys[participant.party.index - 1]
+        );
+    }
+    println!(
+        "{} of {} dealings qualified, group key {:?}",
+        key.qualified.len(),
+        N,
+        key.public_key.compress()
+    );
+
+    // the first t+1 talliers decrypt a message sent to the group key
+    let message = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+    let ciphertext = Ciphertext::encrypt(&mut rng, &generators, &key, &message).unwrap();
+    let partials: Vec<_> = participants
+        .iter()
+        .take(T + 1)
+        .map(|participant| {
+            participant
+                .decrypt_partial(&mut rng, &mut hasher, &mut buf, &ciphertext)
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        ciphertext
+            .decrypt(&generators, &key, &partials, &mut hasher, &mut buf)
+            .unwrap(),
+        message
+    );
+}
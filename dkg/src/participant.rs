@@ -0,0 +1,340 @@
+use blake3::Hasher;
+use common::{
+    error::{
+        Error,
+        ErrorKind::{PointDecompressionError, UninitializedValue},
+    },
+    generators::Generators,
+    random::random_scalar,
+    secret::Secret,
+    signature::SigningKey,
+    utils::{lagrange_coefficients, seal},
+};
+use curve25519_dalek::Scalar;
+use rand::{CryptoRng, RngCore};
+use schoenmakers::{dealer::Dealer, party::Party};
+use std::sync::Arc;
+use zeroize::Zeroize;
+
+use crate::{
+    dealing::{Complaint, Dealing, dealing_message},
+    group_key::GroupKey,
+    threshold_elgamal::{Ciphertext, PartialDecryption, challenge},
+};
+
+/// A key generation participant: deals a random secret and sums its shares of the qualified
+/// dealings into a key share
+pub struct Participant {
+    pub party: Party,
+    /// the party's registered key, signs its dealings
+    signing_key: SigningKey,
+    dealer: Option<Dealer>,
+    /// p_j(index) of every dealing checked so far, by dealer
+    shares: Vec<(usize, Secret<Scalar>)>,
+    key_share: Option<Secret<Scalar>>,
+}
+
+impl Participant {
+    pub fn new<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        n: usize,
+        t: usize,
+        index: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let party = Party::new(generators, rng, n, t, index)?;
+        let signing_key = SigningKey::from_private_key(party.private_key(), generators.G_table());
+        Ok(Self {
+            party,
+            signing_key,
+            dealer: None,
+            shares: vec![],
+            key_share: None,
+        })
+    }
+
+    /// The generators with all n public keys registered, own included
+    pub fn ingest_generators(&mut self, generators: &Arc<Generators>) -> Result<(), Error> {
+        self.dealer = Some(Dealer::new(self.party.n, self.party.t, generators)?);
+        Ok(())
+    }
+
+    pub fn deal<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Dealing, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let secret = Secret::new(random_scalar(rng));
+        let dealer = self
+            .dealer
+            .as_mut()
+            .ok_or_else(|| Error::from(UninitializedValue("participant.dealer")))?;
+        Self::deal_secret(
+            dealer,
+            &self.signing_key,
+            self.party.index,
+            rng,
+            hasher,
+            buf,
+            secret.expose(),
+        )
+    }
+
+    /// A sharing of zero, for refreshing the key shares
+    pub fn deal_zero<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Dealing, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let dealer = self
+            .dealer
+            .as_mut()
+            .ok_or_else(|| Error::from(UninitializedValue("participant.dealer")))?;
+        Self::deal_secret(
+            dealer,
+            &self.signing_key,
+            self.party.index,
+            rng,
+            hasher,
+            buf,
+            &Scalar::ZERO,
+        )
+    }
+
+    /// Deals this party's key share to a new committee with threshold t, given the generators
+    /// with the new parties' public keys
+    pub fn reshare<R>(
+        &self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        generators: &Arc<Generators>,
+        t: usize,
+    ) -> Result<Dealing, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut dealer = Dealer::new(generators.public_keys().len(), t, generators)?;
+        Self::deal_secret(
+            &mut dealer,
+            &self.signing_key,
+            self.party.index,
+            rng,
+            hasher,
+            buf,
+            self.key_share()?,
+        )
+    }
+
+    fn deal_secret<R>(
+        dealer: &mut Dealer,
+        signing_key: &SigningKey,
+        index: usize,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        secret: &Scalar,
+    ) -> Result<Dealing, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let (encrypted_shares, proof, commitments, shares) =
+            dealer.deal_secret_with_shares(rng, hasher, buf, *secret);
+
+        let e = Secret::new(random_scalar(rng));
+        let G = dealer.generators.G_table();
+        let ephemeral_key = (G * e.expose()).compress();
+        let sealed_shares: Vec<Scalar> = dealer
+            .generators
+            .public_keys()
+            .iter()
+            .zip(shares.expose())
+            .enumerate()
+            .map(|(i, (public_key, share))| {
+                let shared_key = public_key * e.expose();
+                share
+                    + seal(
+                        b"dkg seal",
+                        &[ephemeral_key.as_bytes(), &(i as u64 + 1).to_le_bytes()],
+                        &shared_key,
+                        hasher,
+                        buf,
+                    )
+            })
+            .collect();
+
+        let message = dealing_message(
+            &dealer.generators.session(),
+            index,
+            &encrypted_shares,
+            &proof,
+            &commitments,
+            &ephemeral_key,
+            &sealed_shares,
+        );
+        Ok(Dealing {
+            dealer: index,
+            encrypted_shares,
+            proof,
+            commitments,
+            ephemeral_key,
+            sealed_shares,
+            signature: signing_key.sign(rng, G, &message),
+        })
+    }
+
+    /// Unseals this party's share of a verified dealing, or returns a complaint against its dealer
+    pub fn check_dealing<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        dealing: &Dealing,
+    ) -> Result<Option<Complaint>, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let ephemeral_key = dealing.ephemeral_key.decompress().ok_or_else(|| {
+            Error::from(PointDecompressionError("dealing.ephemeral_key".to_string()))
+        })?;
+        let shared_key = ephemeral_key * self.party.private_key();
+
+        if let Some(share) = dealing.unseal(
+            &self.party.public_key.1,
+            self.party.index,
+            &shared_key,
+            hasher,
+            buf,
+        ) {
+            self.shares.retain(|(dealer, _)| *dealer != dealing.dealer);
+            self.shares.push((dealing.dealer, Secret::new(share)));
+            return Ok(None);
+        }
+
+        let r = Secret::new(random_scalar(rng));
+
+        hasher.update(self.party.public_key.0.as_bytes());
+        hasher.update(shared_key.compress().as_bytes());
+
+        hasher.update(
+            (self.party.generators.G_table() * r.expose())
+                .compress()
+                .as_bytes(),
+        );
+        hasher.update((ephemeral_key * r.expose()).compress().as_bytes());
+
+        hasher.finalize_xof().fill(buf);
+
+        let d = Scalar::from_bytes_mod_order_wide(buf);
+        let z = r.expose() + d * self.party.private_key();
+
+        hasher.reset();
+        buf.zeroize();
+
+        Ok(Some(Complaint {
+            dealer: dealing.dealer,
+            index: self.party.index,
+            shared_key: shared_key.compress(),
+            proof: (d, z),
+        }))
+    }
+
+    /// Weighs this party's shares of the qualified dealings and drops the checked shares
+    fn sum_shares(
+        &mut self,
+        qualified: &[usize],
+        weights: &[Scalar],
+    ) -> Result<Secret<Scalar>, Error> {
+        let mut sum = Secret::new(Scalar::ZERO);
+        for (dealer, weight) in qualified.iter().zip(weights) {
+            let (_, share) = self
+                .shares
+                .iter()
+                .find(|(checked, _)| checked == dealer)
+                .ok_or_else(|| Error::from(UninitializedValue("participant.shares")))?;
+            *sum.expose_mut() += share.expose() * weight;
+        }
+        self.shares.clear();
+        Ok(sum)
+    }
+
+    /// Sums this party's shares of the qualified dealings into its key share F(index)
+    pub fn combine(&mut self, key: &GroupKey) -> Result<(), Error> {
+        let ones = vec![Scalar::ONE; key.qualified.len()];
+        self.key_share = Some(self.sum_shares(&key.qualified, &ones)?);
+        Ok(())
+    }
+
+    /// Combines this party's shares of the old parties' key shares, weighted by their Lagrange
+    /// coefficients, into its key share of the reshared key
+    pub fn combine_reshared(&mut self, key: &GroupKey) -> Result<(), Error> {
+        let lambdas = lagrange_coefficients(&key.qualified);
+        self.key_share = Some(self.sum_shares(&key.qualified, &lambdas)?);
+        Ok(())
+    }
+
+    /// Adds this party's shares of the qualified sharings of zero to its key share, the old key
+    /// share is overwritten
+    pub fn refresh(&mut self, key: &GroupKey) -> Result<(), Error> {
+        let ones = vec![Scalar::ONE; key.qualified.len()];
+        let zero = self.sum_shares(&key.qualified, &ones)?;
+        match &mut self.key_share {
+            Some(key_share) => {
+                *key_share.expose_mut() += zero.expose();
+                Ok(())
+            }
+            None => Err(UninitializedValue("participant.key_share").into()),
+        }
+    }
+
+    pub fn key_share(&self) -> Result<&Scalar, Error> {
+        match &self.key_share {
+            Some(key_share) => Ok(key_share.expose()),
+            None => Err(UninitializedValue("participant.key_share").into()),
+        }
+    }
+
+    /// c1 * F(index) with a DLEQ proof that it uses the key share behind this party's
+    /// verification key
+    pub fn decrypt_partial<R>(
+        &self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        ciphertext: &Ciphertext,
+    ) -> Result<PartialDecryption, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let key_share = self.key_share()?;
+        let H = self.party.generators.H_table()?;
+        let share = ciphertext.c1 * key_share;
+
+        let r = Secret::new(random_scalar(rng));
+        let d = challenge(
+            hasher,
+            buf,
+            &(H * key_share),
+            &ciphertext.c1,
+            &share,
+            (&(H * r.expose()), &(ciphertext.c1 * r.expose())),
+        );
+
+        Ok(PartialDecryption {
+            index: self.party.index,
+            share: share.compress(),
+            proof: (d, r.expose() + d * key_share),
+        })
+    }
+}
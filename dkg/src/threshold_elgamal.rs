@@ -0,0 +1,139 @@
+use blake3::Hasher;
+use common::{
+    error::{Error, ErrorKind::TooFewPartialDecryptions},
+    generators::Generators,
+    random::random_scalar,
+    secret::Secret,
+    utils::lagrange_coefficients,
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::group_key::GroupKey;
+
+/// verification key | c1 | partial decryption | H * r | c1 * r
+pub(crate) fn challenge(
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+    verification_key: &RistrettoPoint,
+    c1: &RistrettoPoint,
+    share: &RistrettoPoint,
+    commitments: (&RistrettoPoint, &RistrettoPoint),
+) -> Scalar {
+    hasher.update(verification_key.compress().as_bytes());
+    hasher.update(c1.compress().as_bytes());
+    hasher.update(share.compress().as_bytes());
+
+    hasher.update(commitments.0.compress().as_bytes());
+    hasher.update(commitments.1.compress().as_bytes());
+
+    hasher.finalize_xof().fill(buf);
+    let d = Scalar::from_bytes_mod_order_wide(buf);
+
+    hasher.reset();
+    buf.zeroize();
+
+    d
+}
+
+/// ElGamal encryption of a point under the group key: (H * r, M + (H * S) * r)
+#[derive(Clone, Copy)]
+pub struct Ciphertext {
+    pub c1: RistrettoPoint,
+    pub c2: RistrettoPoint,
+}
+
+impl Ciphertext {
+    pub fn encrypt<R>(
+        rng: &mut R,
+        generators: &Generators,
+        key: &GroupKey,
+        message: &RistrettoPoint,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let r = Secret::new(random_scalar(rng));
+        Ok(Self {
+            c1: generators.H_table()? * r.expose(),
+            c2: message + key.public_key * r.expose(),
+        })
+    }
+
+    /// Combines the first t+1 valid partial decryptions of distinct talliers into the message
+    pub fn decrypt(
+        &self,
+        generators: &Generators,
+        key: &GroupKey,
+        partials: &[PartialDecryption],
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<RistrettoPoint, Error> {
+        let mut valid: Vec<(usize, RistrettoPoint)> = Vec::with_capacity(key.t + 1);
+        for partial in partials {
+            if valid.len() > key.t {
+                break;
+            }
+            if valid.iter().all(|(index, _)| *index != partial.index)
+                && partial.verify(generators, key, self, hasher, buf)
+            {
+                // checked in PartialDecryption::verify
+                valid.push((partial.index, partial.share.decompress().unwrap()));
+            }
+        }
+        if valid.len() <= key.t {
+            return Err(TooFewPartialDecryptions(valid.len(), key.t + 1).into());
+        }
+
+        let indices: Vec<usize> = valid.iter().map(|(index, _)| *index).collect();
+        let mask: RistrettoPoint = lagrange_coefficients(&indices)
+            .iter()
+            .zip(&valid)
+            .map(|(lambda, (_, share))| lambda * share)
+            .sum();
+
+        Ok(self.c2 - mask)
+    }
+}
+
+/// A tallier's c1 * F(i), with a DLEQ proof against its verification key H * F(i)
+#[derive(Clone)]
+pub struct PartialDecryption {
+    /// 1-based index of the tallier
+    pub index: usize,
+    pub share: CompressedRistretto,
+    pub proof: (Scalar, Scalar),
+}
+
+impl PartialDecryption {
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        key: &GroupKey,
+        ciphertext: &Ciphertext,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        if self.index == 0 || self.index > key.verification_keys.len() {
+            return false;
+        }
+        let (Ok(H), Some(share)) = (generators.H_table(), self.share.decompress()) else {
+            return false;
+        };
+        let verification_key = &key.verification_keys[self.index - 1];
+        let (d, z) = &self.proof;
+
+        *d == challenge(
+            hasher,
+            buf,
+            verification_key,
+            &ciphertext.c1,
+            &share,
+            (
+                &(H * z - verification_key * d),
+                &(ciphertext.c1 * z - share * d),
+            ),
+        )
+    }
+}
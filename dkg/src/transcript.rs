@@ -0,0 +1,106 @@
+use blake3::Hasher;
+use common::generators::Generators;
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    dealing::{Complaint, Dealing},
+    dkg::Run,
+    group_key::GroupKey,
+};
+
+/// Everything needed to recompute a key generation, refresh or resharing from the parties'
+/// public keys
+#[derive(Clone)]
+pub struct Transcript {
+    pub dealings: Vec<Dealing>,
+    pub complaints: Vec<Complaint>,
+}
+
+impl Transcript {
+    /// The key the run ends with, None if a dealer dealt twice, a complaint does not hold or too
+    /// few dealings qualify. Unsigned dealings do not qualify.
+    fn recompute(
+        &self,
+        generators: &Generators,
+        t: usize,
+        run: &Run,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Option<GroupKey> {
+        let mut dealers = HashSet::with_capacity(self.dealings.len());
+        if !self
+            .dealings
+            .iter()
+            .all(|dealing| dealers.insert(dealing.dealer))
+        {
+            return None;
+        }
+
+        let mut qualified: Vec<&Dealing> = self
+            .dealings
+            .iter()
+            .filter(|dealing| run.admits(generators, t, dealing, hasher, buf))
+            .collect();
+        for complaint in &self.complaints {
+            let position = qualified
+                .iter()
+                .position(|dealing| dealing.dealer == complaint.dealer)?;
+            if !complaint.verify(generators, qualified[position], hasher, buf) {
+                return None;
+            }
+            qualified.remove(position);
+        }
+
+        run.finish(generators.public_keys().len(), t, &qualified)
+            .ok()
+    }
+
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        key: &GroupKey,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        self.recompute(generators, key.t, &Run::Generate, hasher, buf)
+            .is_some_and(|recomputed| recomputed == *key)
+    }
+
+    /// Checks that `refreshed` is `key` plus sharings of zero, so the secret is unchanged
+    pub fn verify_refresh(
+        &self,
+        generators: &Generators,
+        key: &GroupKey,
+        refreshed: &GroupKey,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        refreshed.public_key == key.public_key
+            && self
+                .recompute(generators, key.t, &Run::Refresh(key.clone()), hasher, buf)
+                .is_some_and(|recomputed| recomputed == *refreshed)
+    }
+
+    /// Checks that `reshared`, for the committee in `generators`, interpolates dealings of `key`'s
+    /// shares signed by the old committee in `dealers`, so it shares the same secret
+    pub fn verify_reshare(
+        &self,
+        dealers: &Arc<Generators>,
+        generators: &Generators,
+        key: &GroupKey,
+        reshared: &GroupKey,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        reshared.public_key == key.public_key
+            && self
+                .recompute(
+                    generators,
+                    reshared.t,
+                    &Run::Reshare(key.clone(), dealers.clone()),
+                    hasher,
+                    buf,
+                )
+                .is_some_and(|recomputed| recomputed == *reshared)
+    }
+}
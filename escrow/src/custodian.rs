@@ -0,0 +1,53 @@
+use blake3::Hasher;
+use common::{error::Error, generators::Generators};
+use rand::{CryptoRng, RngCore};
+use schoenmakers::party::Party;
+use std::sync::Arc;
+
+use crate::record::{EscrowRecord, RecoveryShare};
+
+/// Holds one share of every record escrowed to it, and decrypts it when the owner asks to recover
+pub struct Custodian {
+    pub party: Party,
+}
+
+impl Custodian {
+    pub fn new<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        n: usize,
+        t: usize,
+        index: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        Ok(Self {
+            party: Party::new(generators, rng, n, t, index)?,
+        })
+    }
+
+    /// Decrypts this custodian's share of `record` with a DLEQ proof
+    pub fn recover_share<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        record: &EscrowRecord,
+    ) -> Result<RecoveryShare, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        self.party
+            .ingest_encrypted_shares(&record.encrypted_shares)?;
+        self.party.decrypt_share()?;
+        self.party.dleq_share(rng, hasher, buf)?;
+
+        Ok(RecoveryShare {
+            index: self.party.index,
+            // both set above
+            decrypted_share: self.party.decrypted_share.unwrap().compress(),
+            proof: self.party.share_proof.unwrap(),
+        })
+    }
+}
@@ -0,0 +1,106 @@
+#![allow(non_snake_case)]
+
+pub mod custodian;
+pub mod record;
+
+#[cfg(test)]
+mod tests {
+    use common::{generators::Generators, key_proof::KeyProof, random::random_scalar};
+    use curve25519_dalek::{RistrettoPoint, Scalar};
+    use std::sync::Arc;
+
+    use crate::{custodian::Custodian, record::EscrowRecord};
+
+    const N: usize = 7;
+    const T: usize = 3;
+
+    #[test]
+    fn escrow() {
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+        let H: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+        let generators = Arc::new(Generators::new(&G).with_H(&H));
+
+        let mut custodians: Vec<Custodian> = (1..=N)
+            .map(|i| Custodian::new(&generators, &mut rng, N, T, i).unwrap())
+            .collect();
+        let public_keys: Vec<RistrettoPoint> = custodians
+            .iter()
+            .map(|custodian| custodian.party.public_key.1)
+            .collect();
+        let key_proofs: Vec<KeyProof> = custodians
+            .iter()
+            .map(|custodian| custodian.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+
+        let signing_key = random_scalar(&mut rng);
+        let record = EscrowRecord::new(
+            &mut rng,
+            &mut hasher,
+            &mut buf,
+            &generators,
+            T,
+            &signing_key,
+        )
+        .unwrap();
+        assert_eq!(record.owner_key, (G * signing_key).compress());
+        assert!(record.verify(&generators, &mut hasher, &mut buf));
+
+        // the proof no longer matches the shares
+        let mut tampered = record.clone();
+        tampered.encrypted_shares.swap(0, 1);
+        assert!(!tampered.verify(&generators, &mut hasher, &mut buf));
+        // escrowed to other custodians than the registered ones
+        let mut tampered = record.clone();
+        tampered.custodians.swap(0, 1);
+        assert!(!tampered.verify(&generators, &mut hasher, &mut buf));
+
+        let mut shares: Vec<_> = custodians
+            .iter_mut()
+            .take(T + 1)
+            .map(|custodian| {
+                custodian
+                    .recover_share(&mut rng, &mut hasher, &mut buf, &record)
+                    .unwrap()
+            })
+            .collect();
+        // a repeated share, one claimed by the wrong custodian and one with a broken proof are
+        // skipped
+        let mut wrong = shares[1].clone();
+        wrong.index = T + 2;
+        let mut forged = shares[2].clone();
+        forged.decrypted_share = (forged.decrypted_share.decompress().unwrap() + G).compress();
+        shares.insert(0, shares[0].clone());
+        shares.insert(1, wrong);
+        shares.insert(2, forged);
+
+        // a single valid share among the first t+1
+        assert!(
+            record
+                .recover(&generators, &shares[..T + 1], &mut hasher, &mut buf)
+                .is_err()
+        );
+        let recovered = record
+            .recover(&generators, &shares, &mut hasher, &mut buf)
+            .unwrap();
+        assert_eq!(*recovered.expose(), signing_key);
+
+        // a wrongly sealed key is caught against the published public key
+        let mut tampered = record.clone();
+        tampered.sealed_key += Scalar::ONE;
+        assert!(
+            tampered
+                .recover(&generators, &shares, &mut hasher, &mut buf)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,66 @@
+#![allow(non_snake_case)]
+
+use common::{generators::Generators, key_proof::KeyProof, random::random_scalar};
+use curve25519_dalek::RistrettoPoint;
+use escrow::{custodian::Custodian, record::EscrowRecord};
+use std::sync::Arc;
+
+fn main() {
+    const N: usize = 64;
+    const T: usize = 31;
+
+    let mut rng = rand::rng();
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = [0u8; 64];
+
+    let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+    let H: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+    let generators = Arc::new(Generators::new(&G).with_H(&H));
+
+    let mut custodians: Vec<Custodian> = (1..=N)
+        .map(|i| Custodian::new(&generators, &mut rng, N, T, i).unwrap())
+        .collect();
+    let public_keys: Vec<RistrettoPoint> = custodians
+        .iter()
+        .map(|custodian| custodian.party.public_key.1)
+        .collect();
+    let key_proofs: Vec<KeyProof> = custodians
+        .iter()
+        .map(|custodian| custodian.party.key_proof)
+        .collect();
+    let generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(&public_keys, &key_proofs)
+            .unwrap(),
+    );
+
+    let signing_key = random_scalar(&mut rng);
+    let record = EscrowRecord::new(
+        &mut rng,
+        &mut hasher,
+        &mut buf,
+        &generators,
+        T,
+        &signing_key,
+    )
+    .unwrap();
+    assert!(record.verify(&generators, &mut hasher, &mut buf));
+    println!("escrowed {:?} to {} custodians", record.owner_key, N);
+
+    // the last t+1 custodians recover the key
+    let shares: Vec<_> = custodians
+        .iter_mut()
+        .rev()
+        .take(T + 1)
+        .map(|custodian| {
+            custodian
+                .recover_share(&mut rng, &mut hasher, &mut buf, &record)
+                .unwrap()
+        })
+        .collect();
+    let recovered = record
+        .recover(&generators, &shares, &mut hasher, &mut buf)
+        .unwrap();
+    assert_eq!(*recovered.expose(), signing_key);
+}
@@ -0,0 +1,212 @@
+use blake3::Hasher;
+use common::{
+    error::{
+        Error,
+        ErrorKind::{RecoveredKeyMismatch, TooFewDecryptedShares},
+    },
+    generators::Generators,
+    random::random_scalar,
+    secret::Secret,
+    utils::{
+        batch_decompress_ristretto_points, lagrange_coefficients, seal, verify_decrypted_share,
+    },
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use rand::{CryptoRng, RngCore};
+use schoenmakers::{dealer::Dealer, utils::verify_encrypted_shares_standalone};
+use std::sync::Arc;
+
+/// A signing key backed up to custodians: a schoenmakers dealing of a random s, and the key sealed
+/// under G * s. Any t+1 custodians can recover G * s from their decrypted shares, but never hand
+/// out a share of the key itself.
+#[derive(Clone)]
+pub struct EscrowRecord {
+    /// G * sk, published at escrow time
+    pub owner_key: CompressedRistretto,
+    pub t: usize,
+    /// the custodians' public keys, in index order
+    pub custodians: Vec<CompressedRistretto>,
+    pub encrypted_shares: Vec<CompressedRistretto>,
+    pub proof: (Scalar, Vec<Scalar>),
+    /// H * p's coefficients, H * s first
+    pub commitments: Vec<CompressedRistretto>,
+    /// sk + seal(G * s)
+    pub sealed_key: Scalar,
+}
+
+impl EscrowRecord {
+    /// Escrows `signing_key` to the custodians registered in `generators`
+    pub fn new<R>(
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        generators: &Arc<Generators>,
+        t: usize,
+        signing_key: &Scalar,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let custodians = generators.public_keys();
+        let mut dealer = Dealer::new(custodians.len(), t, generators)?;
+
+        let secret = Secret::new(random_scalar(rng));
+        let (encrypted_shares, proof, commitments) =
+            dealer.deal_secret(rng, hasher, buf, *secret.expose());
+
+        let G = generators.G_table();
+        let owner_key = (G * signing_key).compress();
+        let seal = Secret::new(seal(
+            b"escrow seal",
+            &[owner_key.as_bytes()],
+            &(G * secret.expose()),
+            hasher,
+            buf,
+        ));
+
+        Ok(Self {
+            owner_key,
+            t,
+            custodians: custodians
+                .iter()
+                .map(|custodian| custodian.compress())
+                .collect(),
+            encrypted_shares,
+            proof,
+            commitments,
+            sealed_key: signing_key + seal.expose(),
+        })
+    }
+
+    /// Audits the record against the custodians registered in `generators`: the encrypted shares
+    /// must match the commitments, so that any t+1 custodians recover the same G * s. The sealed
+    /// key is a hash away from G * s and cannot be audited ahead of time, `recover` checks it
+    /// against `owner_key`.
+    pub fn verify(&self, generators: &Generators, hasher: &mut Hasher, buf: &mut [u8; 64]) -> bool {
+        let n = generators.public_keys().len();
+        let (d, z) = &self.proof;
+        if self.custodians.len() != n
+            || self.encrypted_shares.len() != n
+            || z.len() != n
+            || self.commitments.len() != self.t + 1
+            || self.t >= n
+            || self.owner_key.decompress().is_none()
+            || !self
+                .custodians
+                .iter()
+                .zip(generators.public_keys())
+                .all(|(custodian, public_key)| *custodian == public_key.compress())
+        {
+            return false;
+        }
+        let (Ok(H), Ok(encrypted_shares), Ok(commitments)) = (
+            generators.H_table(),
+            batch_decompress_ristretto_points(&self.encrypted_shares),
+            batch_decompress_ristretto_points(&self.commitments),
+        ) else {
+            return false;
+        };
+
+        verify_encrypted_shares_standalone(
+            H,
+            hasher,
+            buf,
+            &(self.encrypted_shares.clone(), encrypted_shares),
+            generators.public_key_tables(),
+            &commitments,
+            (d, z),
+            n,
+            self.t,
+        )
+        .unwrap_or(false)
+    }
+
+    /// Interpolates G * s from the first t+1 valid shares of distinct custodians and unseals the
+    /// signing key, which must match `owner_key`
+    pub fn recover(
+        &self,
+        generators: &Generators,
+        shares: &[RecoveryShare],
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Secret<Scalar>, Error> {
+        let mut valid: Vec<(usize, RistrettoPoint)> = Vec::with_capacity(self.t + 1);
+        for share in shares {
+            if valid.len() > self.t {
+                break;
+            }
+            if valid.iter().all(|(index, _)| *index != share.index)
+                && share.verify(generators, self, hasher, buf)
+            {
+                // checked in RecoveryShare::verify
+                valid.push((share.index, share.decrypted_share.decompress().unwrap()));
+            }
+        }
+        if valid.len() <= self.t {
+            return Err(TooFewDecryptedShares(valid.len(), self.t + 1).into());
+        }
+
+        let indices: Vec<usize> = valid.iter().map(|(index, _)| *index).collect();
+        let secret: RistrettoPoint = lagrange_coefficients(&indices)
+            .iter()
+            .zip(&valid)
+            .map(|(lambda, (_, share))| lambda * share)
+            .sum();
+
+        let signing_key = Secret::new(
+            self.sealed_key
+                - seal(
+                    b"escrow seal",
+                    &[self.owner_key.as_bytes()],
+                    &secret,
+                    hasher,
+                    buf,
+                ),
+        );
+        if (generators.G_table() * signing_key.expose()).compress() != self.owner_key {
+            return Err(RecoveredKeyMismatch.into());
+        }
+        Ok(signing_key)
+    }
+}
+
+/// A custodian's decrypted share of an escrow record, with a DLEQ proof against its public key
+#[derive(Clone)]
+pub struct RecoveryShare {
+    /// 1-based index of the custodian
+    pub index: usize,
+    pub decrypted_share: CompressedRistretto,
+    pub proof: (Scalar, Scalar),
+}
+
+impl RecoveryShare {
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        record: &EscrowRecord,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let Some((public_key, encrypted_share)) = self.index.checked_sub(1).and_then(|i| {
+            Some((
+                generators.public_keys().get(i)?,
+                record.encrypted_shares.get(i)?.decompress()?,
+            ))
+        }) else {
+            return false;
+        };
+        let Some(decrypted_share) = self.decrypted_share.decompress() else {
+            return false;
+        };
+
+        verify_decrypted_share(
+            generators.G(),
+            public_key,
+            &encrypted_share,
+            &decrypted_share,
+            &self.proof,
+            hasher,
+            buf,
+        )
+    }
+}
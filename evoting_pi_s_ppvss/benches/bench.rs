@@ -1,5 +1,7 @@
 use common::{
     error::ErrorKind::PointDecompressionError,
+    generators::Generators,
+    key_proof::KeyProof,
     random::{random_point, random_scalar},
     utils::precompute_lambda,
 };
@@ -8,6 +10,7 @@ use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto, scalar::S
 use evoting_pi_s_ppvss::{bulletin_board::BulletinBoard, tallier::Tallier, voter::Voter};
 use pi_s_ppvss::party::generate_parties;
 use rayon::prelude::*;
+use std::sync::Arc;
 
 // const PARAMSET: [(usize, usize, usize); 4] =
 // [(128, 9, 4), (128, 17, 8), (256, 256, 127), (512, 512, 255)];
@@ -33,19 +36,30 @@ fn tallying(c: &mut Criterion) {
         // Sample random point
         let pk0 = random_point(&mut rng);
 
-        let mut talliers = Tallier::generate_talliers(&G, &mut rng, n, t, &pk0);
+        let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+        let mut talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
         let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) = talliers
             .iter()
             .map(|tallier| tallier.party.public_key)
             .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys.1, &key_proofs)
+                .unwrap(),
+        );
 
-        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&G, &pk0, &public_keys.1, m);
+        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&generators, m, t);
 
-        let mut voters = Voter::generate_voters(m, n, t, &public_keys.0, &pk0);
+        let mut voters = Voter::generate_voters(m, n, t, &generators);
 
         voters.iter_mut().enumerate().for_each(|(i, voter)| {
             let (encrypted_shares, (d, z), encrypted_vote, vote_proof) = voter
-                .vote(&G, &mut rng, &mut hasher, &mut buf, i < t_count)
+                .vote(&mut rng, &mut hasher, &mut buf, i < t_count)
                 .unwrap();
 
             let decompressed_encrypted_shares: Vec<RistrettoPoint> = encrypted_shares
@@ -76,7 +90,7 @@ fn tallying(c: &mut Criterion) {
         let mut decrypted_share_proofs: Vec<(Scalar, Scalar)> = Vec::with_capacity(n);
 
         {
-            let mut tallier0 = talliers[0].clone();
+            let tallier0 = &mut talliers[0];
 
             let public_keys: Vec<CompressedRistretto> = public_keys
                 .0
@@ -112,7 +126,7 @@ fn tallying(c: &mut Criterion) {
                             tallier0.party.decrypt_share().unwrap();
                             tallier0
                                 .party
-                                .dleq_share(&G, &mut rng, &mut hasher, &mut buf)
+                                .dleq_share(&mut rng, &mut hasher, &mut buf)
                                 .unwrap();
                         },
                         BatchSize::PerIteration,
@@ -139,7 +153,7 @@ fn tallying(c: &mut Criterion) {
             tallier.party.decrypt_share().unwrap();
             tallier
                 .party
-                .dleq_share(&G, &mut rng, &mut hasher, &mut buf)
+                .dleq_share(&mut rng, &mut hasher, &mut buf)
                 .unwrap();
 
             decrypted_shares.push(tallier.party.decrypted_share.unwrap().compress());
@@ -163,7 +177,7 @@ fn tallying(c: &mut Criterion) {
             ),
             |b| {
                 b.iter(|| {
-                    assert!(talliers[0].party.verify_decrypted_shares(&G).unwrap());
+                    assert!(talliers[0].party.verify_decrypted_shares().unwrap());
 
                     let accumulated_encrypted_votes = bulletin_board.tally_encrypted_votes();
 
@@ -201,19 +215,29 @@ fn ballot_verification(c: &mut Criterion) {
         // Sample random point
         let pk0 = random_point(&mut rng);
 
-        let talliers = Tallier::generate_talliers(&G, &mut rng, n, t, &pk0);
+        let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+        let talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
         let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) = talliers
             .iter()
             .map(|tallier| tallier.party.public_key)
             .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys.1, &key_proofs)
+                .unwrap(),
+        );
 
-        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&G, &pk0, &public_keys.1, 1);
+        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&generators, 1, t);
 
-        let mut voter = Voter::new(n, t, &public_keys.0, &pk0).unwrap();
+        let mut voter = Voter::new(n, t, &generators).unwrap();
 
-        let (encrypted_shares, (d, z), encrypted_vote, vote_proof) = voter
-            .vote(&G, &mut rng, &mut hasher, &mut buf, false)
-            .unwrap();
+        let (encrypted_shares, (d, z), encrypted_vote, vote_proof) =
+            voter.vote(&mut rng, &mut hasher, &mut buf, false).unwrap();
 
         let decompressed_encrypted_shares: Vec<RistrettoPoint> = encrypted_shares
             .par_iter()
@@ -250,11 +274,19 @@ fn cast_ballot(c: &mut Criterion) {
 
         let pk0 = random_point(&mut rng);
 
-        let parties = generate_parties(&G, &mut rng, n, t, &pk0);
-        let public_keys: Vec<CompressedRistretto> =
-            parties.iter().map(|party| party.public_key.0).collect();
+        let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+        let parties = generate_parties(&generators, &mut rng, n, t);
+        let public_keys: Vec<RistrettoPoint> =
+            parties.iter().map(|party| party.public_key.1).collect();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
 
-        let mut voter = Voter::new(n, t, &public_keys, &pk0).unwrap();
+        let mut voter = Voter::new(n, t, &generators).unwrap();
 
         c.bench_function(
             &format!("(n: {}, t: {}) | Pi_S | Voter: Cast Vote", n, t),
@@ -277,10 +309,10 @@ fn cast_ballot(c: &mut Criterion) {
 
                         let y0 = encrypted_shares[0].decompress().unwrap();
 
-                        voter.generate_vote(&G, &s, false);
+                        voter.generate_vote(&s, false);
 
                         voter
-                            .dleq_vote(&mut rng, &G, &y0, &s, &mut hasher2, &mut buf2)
+                            .dleq_vote(&mut rng, &y0, &s, &mut hasher2, &mut buf2)
                             .unwrap()
                     },
                     BatchSize::PerIteration,
@@ -290,6 +322,75 @@ fn cast_ballot(c: &mut Criterion) {
     }
 }
 
+fn summing(c: &mut Criterion) {
+    for (m, n, t) in PARAMSET {
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf: [u8; 64] = [0u8; 64];
+
+        let G: RistrettoPoint = random_point(&mut rng);
+        let pk0 = random_point(&mut rng);
+
+        let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+        let talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
+        let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) = talliers
+            .iter()
+            .map(|tallier| tallier.party.public_key)
+            .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys.1, &key_proofs)
+                .unwrap(),
+        );
+
+        // the sum does not depend on the ballot contents, so one ballot is posted m times
+        let (encrypted_shares, (d, z), encrypted_vote, vote_proof) = Voter::new(n, t, &generators)
+            .unwrap()
+            .vote(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap();
+        let encrypted_shares: Vec<RistrettoPoint> = encrypted_shares
+            .iter()
+            .map(|enc_share| enc_share.1)
+            .collect();
+        let encrypted_vote = encrypted_vote.decompress().unwrap();
+
+        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&generators, m, t);
+        for _ in 0..m {
+            bulletin_board.ingest_vote(
+                encrypted_shares.clone(),
+                (d, z.clone()),
+                encrypted_vote,
+                vote_proof.clone().decompress(),
+            );
+        }
+        bulletin_board
+            .encrypted_shares
+            .iter_mut()
+            .for_each(|(status, _)| *status = true);
+
+        c.bench_function(
+            &format!(
+                "(m: {}, n: {}, t: {}) | Pi_S | Bulletin Board: Sum Encrypted Shares",
+                m, n, t
+            ),
+            |b| b.iter(|| bulletin_board.sum_encrypted_shares()),
+        );
+
+        c.bench_function(
+            &format!(
+                "(m: {}, n: {}, t: {}) | Pi_S | Bulletin Board: Sum and Compress Encrypted Shares",
+                m, n, t
+            ),
+            |b| b.iter(|| bulletin_board.sum_encrypted_shares_compressed()),
+        );
+    }
+}
+
 fn ristretto_point_bench(c: &mut Criterion) {
     let mut rng = rand::rng();
     let x = random_scalar(&mut rng);
@@ -312,7 +413,7 @@ fn ristretto_point_bench(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, cast_ballot, ballot_verification, tallying);
+criterion_group!(benches, cast_ballot, ballot_verification, tallying, summing);
 // criterion_group!(benches, cast_ballot, ballot_verification);
 // criterion_group!(benches, tallying);
 
@@ -1,13 +1,35 @@
+#![allow(non_snake_case)]
+
+pub mod approval;
 pub mod bulletin_board;
+pub mod rerandomizer;
 pub mod tallier;
+pub mod tally_accumulator;
+pub mod tally_result;
 pub mod voter;
 
 #[cfg(test)]
 mod test {
-    use crate::{bulletin_board::BulletinBoard, tallier::Tallier, voter::Voter};
-    use common::{random::random_point, utils::precompute_lambda};
+    use crate::{
+        approval::{ApprovalBallot, ApprovalBoard},
+        bulletin_board::BulletinBoard,
+        rerandomizer::Rerandomizer,
+        tallier::Tallier,
+        tally_accumulator::TallyAccumulator,
+        tally_result::TallyResult,
+        voter::Voter,
+    };
+    use common::{
+        generators::Generators,
+        key_proof::KeyProof,
+        polynomial::Polynomial,
+        random::random_point,
+        signature::SigningKey,
+        utils::{batch_decompress_ristretto_points, precompute_lambda},
+    };
     use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
     use rayon::prelude::*;
+    use std::sync::Arc;
 
     #[test]
     fn test_verif() {
@@ -31,19 +53,31 @@ mod test {
         // Sample random point
         let pk0 = random_point(&mut rng);
 
-        let mut talliers = Tallier::generate_talliers(&G, &mut rng, n, t, &pk0);
+        let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+        let mut talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
         let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) = talliers
             .iter()
             .map(|tallier| tallier.party.public_key)
             .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys.1, &key_proofs)
+                .unwrap(),
+        );
 
-        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&G, &pk0, &public_keys.1, m);
+        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&generators, m, t);
+        let mut tally_accumulator = TallyAccumulator::new(&generators, t);
 
-        let mut voters = Voter::generate_voters(m, n, t, &public_keys.0, &pk0);
+        let mut voters = Voter::generate_voters(m, n, t, &generators);
 
         voters.iter_mut().enumerate().for_each(|(i, voter)| {
             let (encrypted_shares, (d, z), encrypted_vote, vote_proof) = voter
-                .vote(&G, &mut rng, &mut hasher, &mut buf, i < t_count)
+                .vote(&mut rng, &mut hasher, &mut buf, i < t_count)
                 .unwrap();
 
             let decompressed_encrypted_shares: Vec<RistrettoPoint> = encrypted_shares
@@ -51,6 +85,62 @@ mod test {
                 .map(|enc_share| enc_share.1)
                 .collect();
 
+            if i == 0 {
+                // a ballot with a broken share proof or vote proof leaves the tally untouched
+                let before = (
+                    tally_accumulator.sum_encrypted_shares(),
+                    tally_accumulator.tally_encrypted_votes(),
+                    tally_accumulator.count_valid_votes(),
+                    tally_accumulator.digest(),
+                );
+                assert!(!tally_accumulator.ingest_vote(
+                    &mut hasher,
+                    &mut buf,
+                    decompressed_encrypted_shares.clone(),
+                    (d + Scalar::ONE, z.clone()),
+                    encrypted_vote.decompress().unwrap(),
+                    vote_proof.clone().decompress(),
+                ));
+                assert!(!tally_accumulator.ingest_vote(
+                    &mut hasher,
+                    &mut buf,
+                    decompressed_encrypted_shares.clone(),
+                    (d, z.clone()),
+                    encrypted_vote.decompress().unwrap() + decompressed_encrypted_shares[1],
+                    vote_proof.clone().decompress(),
+                ));
+                // a zero top coefficient keeps the proof sound but exceeds degree t
+                let mut padded = z.coefficients().to_vec();
+                padded.push(Scalar::ZERO);
+                assert!(!tally_accumulator.ingest_vote(
+                    &mut hasher,
+                    &mut buf,
+                    decompressed_encrypted_shares.clone(),
+                    (d, Polynomial::from(padded)),
+                    encrypted_vote.decompress().unwrap(),
+                    vote_proof.clone().decompress(),
+                ));
+                assert_eq!(
+                    (
+                        tally_accumulator.sum_encrypted_shares(),
+                        tally_accumulator.tally_encrypted_votes(),
+                        tally_accumulator.count_valid_votes(),
+                        tally_accumulator.digest(),
+                    ),
+                    before
+                );
+                assert_eq!(tally_accumulator.count_rejected_votes(), 3);
+            }
+
+            assert!(tally_accumulator.ingest_vote(
+                &mut hasher,
+                &mut buf,
+                decompressed_encrypted_shares.clone(),
+                (d, z.clone()),
+                encrypted_vote.decompress().unwrap(),
+                vote_proof.clone().decompress(),
+            ));
+
             bulletin_board.ingest_vote(
                 decompressed_encrypted_shares,
                 (d, z),
@@ -61,6 +151,24 @@ mod test {
 
         // voting complete
 
+        // a short ballot or an over-degree proof is rejected instead of panicking the board
+        let mut malformed = bulletin_board.clone();
+        malformed.encrypted_shares[0].1.pop();
+        let mut padded = malformed.encrypted_share_proofs[1]
+            .1
+            .coefficients()
+            .to_vec();
+        padded.push(Scalar::ZERO);
+        malformed.encrypted_share_proofs[1].1 = Polynomial::from(padded);
+        malformed.verify_encrypted_shares();
+        let statuses: Vec<bool> = malformed
+            .encrypted_shares
+            .iter()
+            .take(3)
+            .map(|(status, _)| *status)
+            .collect();
+        assert_eq!(statuses, [false, false, true]);
+
         let mut decrypted_shares: Vec<CompressedRistretto> = Vec::with_capacity(n);
         let mut decrypted_share_proofs: Vec<(Scalar, Scalar)> = Vec::with_capacity(n);
 
@@ -94,7 +202,7 @@ mod test {
             tallier.party.decrypt_share().unwrap();
             tallier
                 .party
-                .dleq_share(&G, &mut rng, &mut hasher, &mut buf)
+                .dleq_share(&mut rng, &mut hasher, &mut buf)
                 .unwrap();
 
             decrypted_shares.push(tallier.party.decrypted_share.unwrap().compress());
@@ -115,7 +223,7 @@ mod test {
                 .ingest_decrypted_shares_and_proofs(&dec_shares, share_proofs)
                 .unwrap();
 
-            assert!(tallier.party.verify_decrypted_shares(&G).unwrap());
+            assert!(tallier.party.verify_decrypted_shares().unwrap());
 
             reconstructed_secrets.push(
                 tallier
@@ -131,6 +239,21 @@ mod test {
             .unwrap()
             .tally_encrypted_votes();
 
+        // the streaming tally agrees with the bulletin board without retaining the ballots
+        let bb = talliers[0].bulletin_board.as_ref().unwrap();
+        assert_eq!(
+            tally_accumulator.tally_encrypted_votes(),
+            accumulated_encrypted_votes
+        );
+        assert_eq!(
+            tally_accumulator.sum_encrypted_shares(),
+            bb.sum_encrypted_shares()
+        );
+        assert_eq!(
+            tally_accumulator.count_valid_votes(),
+            bb.count_valid_votes()
+        );
+
         let recon_secret = reconstructed_secrets[0];
 
         let exp_vote = accumulated_encrypted_votes - recon_secret;
@@ -149,6 +272,26 @@ mod test {
             }
         }
         assert_eq!(decrypted_vote, t_count as f64);
+
+        // the published result checks out against the board alone
+        let bb = talliers[0].bulletin_board.as_ref().unwrap();
+        let mut tally_result = TallyResult::new(bb, &talliers[0]).unwrap();
+        assert_eq!(tally_result.count, t_count as u64);
+        assert!(tally_result.verify(bb).unwrap());
+
+        tally_result.count += 1;
+        assert!(!tally_result.verify(bb).unwrap());
+        tally_result.count -= 1;
+        tally_result.decrypted_shares.swap(0, 1);
+        assert!(!tally_result.verify(bb).unwrap());
+        tally_result.decrypted_shares.swap(0, 1);
+
+        // a result interpolated from fewer than t+1 shares is refused outright
+        tally_result.indices.pop();
+        tally_result.decrypted_shares.pop();
+        tally_result.share_proofs.pop();
+        assert!(tally_result.verify(bb).is_err());
+
         if decrypted_vote == ((m as f64) / 2.0) {
             println!("Tie");
         } else if decrypted_vote > ((m as f64) / 2.0) {
@@ -159,4 +302,453 @@ mod test {
 
         println!("{decrypted_vote}");
     }
+
+    #[test]
+    fn weighted_votes() {
+        let (n, t) = (5, 2);
+        // the last voter is on the roll with weight 5 but casts 8 votes
+        let weights = [1u64, 3, 5, 8, 5];
+        let claims = [(1u64, 1u64), (3, 2), (5, 0), (8, 8), (8, 8)];
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let generators =
+            Arc::new(Generators::new(&random_point(&mut rng)).with_pk0(&random_point(&mut rng)));
+        let talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
+        let public_keys: Vec<RistrettoPoint> = talliers
+            .iter()
+            .map(|tallier| tallier.party.public_key.1)
+            .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+
+        let credentials: Vec<SigningKey> = weights
+            .iter()
+            .map(|_| SigningKey::generate(&mut rng, generators.G_table()))
+            .collect();
+        let roll: Vec<(RistrettoPoint, u64)> = credentials
+            .iter()
+            .map(|credential| credential.public_key)
+            .zip(weights)
+            .collect();
+        let mut voters: Vec<Voter> = Voter::generate_voters(roll.len(), n, t, &generators)
+            .into_iter()
+            .zip(credentials)
+            .map(|(voter, credential)| voter.with_credential(credential))
+            .collect();
+        let mut bulletin_board =
+            BulletinBoard::new(&generators, roll.len(), t).with_voter_roll(&roll);
+        let mut secrets = Scalar::ZERO;
+
+        // the roll is keyed by credential, so the ballots' order on the board does not matter
+        for (voter, (weight, value)) in voters.iter_mut().zip(claims).rev() {
+            let ((encrypted_shares, dealer_proof, encrypted_vote, range_proof), credential) = voter
+                .vote_weighted(&mut rng, &mut hasher, &mut buf, weight, value)
+                .unwrap();
+            secrets += voter.dealer.publish_f0();
+
+            bulletin_board.ingest_weighted_vote(
+                encrypted_shares.iter().map(|share| share.1).collect(),
+                dealer_proof,
+                encrypted_vote.decompress().unwrap(),
+                range_proof.decompress().unwrap(),
+                credential,
+            );
+        }
+        assert!(
+            voters[0]
+                .vote_weighted(&mut rng, &mut hasher, &mut buf, 3, 4)
+                .is_err()
+        );
+
+        bulletin_board.verify_encrypted_shares();
+        bulletin_board.verify_votes();
+        let statuses: Vec<bool> = bulletin_board
+            .encrypted_votes
+            .iter()
+            .map(|(status, _)| *status)
+            .collect();
+        assert_eq!(statuses, [false, true, true, true, true]);
+        assert_eq!(bulletin_board.max_tally(), 17);
+
+        // only the valid ballots count: 1 + 2 + 0 + 8
+        secrets -= voters[4].dealer.publish_f0();
+        assert_eq!(
+            bulletin_board.tally_encrypted_votes(),
+            generators.G() * (secrets + Scalar::from(11u64))
+        );
+    }
+
+    /// Talliers with registered keys, each holding the others' public keys
+    fn election(n: usize, t: usize) -> (Arc<Generators>, Vec<Tallier>) {
+        let mut rng = rand::rng();
+
+        let generators =
+            Arc::new(Generators::new(&random_point(&mut rng)).with_pk0(&random_point(&mut rng)));
+        let mut talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
+        let public_keys: Vec<CompressedRistretto> = talliers
+            .iter()
+            .map(|tallier| tallier.party.public_key.0)
+            .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(
+                    &batch_decompress_ristretto_points(&public_keys).unwrap(),
+                    &key_proofs,
+                )
+                .unwrap(),
+        );
+        for tallier in &mut talliers {
+            let public_keys: Vec<CompressedRistretto> = public_keys
+                .iter()
+                .filter(|pk| &tallier.party.public_key.0 != *pk)
+                .copied()
+                .collect();
+            tallier.party.ingest_public_keys(&public_keys).unwrap();
+        }
+
+        (generators, talliers)
+    }
+
+    /// Runs the talliers over a single-choice board and returns the verified count
+    fn decrypt_tally(talliers: &mut [Tallier], bulletin_board: &BulletinBoard) -> u64 {
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let encrypted_shares = bulletin_board.sum_encrypted_shares_compressed();
+        let (decrypted_shares, share_proofs): (Vec<CompressedRistretto>, Vec<(Scalar, Scalar)>) =
+            talliers
+                .iter_mut()
+                .map(|tallier| {
+                    tallier
+                        .party
+                        .ingest_encrypted_shares(&encrypted_shares)
+                        .unwrap();
+                    tallier.party.decrypt_share().unwrap();
+                    tallier
+                        .party
+                        .dleq_share(&mut rng, &mut hasher, &mut buf)
+                        .unwrap();
+                    (
+                        tallier.party.decrypted_share.unwrap().compress(),
+                        tallier.party.share_proof.unwrap(),
+                    )
+                })
+                .unzip();
+
+        let tallier = &mut talliers[0];
+        tallier
+            .party
+            .ingest_decrypted_shares_and_proofs(&decrypted_shares[1..], share_proofs[1..].to_vec())
+            .unwrap();
+        assert!(tallier.party.verify_decrypted_shares().unwrap());
+
+        let tally_result = TallyResult::new(bulletin_board, tallier).unwrap();
+        assert!(tally_result.verify(bulletin_board).unwrap());
+        tally_result.count
+    }
+
+    #[test]
+    fn approval_ballots() {
+        let (n, t) = (5, 2);
+        let (c, min, max) = (3, 1, 2);
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let (generators, mut talliers) = election(n, t);
+
+        let approvals = [
+            [true, false, true],
+            [false, false, true],
+            [true, true, false],
+            [false, true, false],
+        ];
+        let mut voters = Voter::generate_voters(approvals.len(), n, t, &generators);
+        let mut ballots: Vec<ApprovalBallot> = voters
+            .iter_mut()
+            .zip(approvals)
+            .map(|(voter, approvals)| {
+                voter
+                    .vote_approval(&mut rng, &mut hasher, &mut buf, &approvals, min, max)
+                    .unwrap()
+            })
+            .collect();
+        assert!(
+            voters[0]
+                .vote_approval(&mut rng, &mut hasher, &mut buf, &[true; 3], min, max)
+                .is_err()
+        );
+
+        // the last ballot borrows another ballot's sum proof
+        ballots[3].sum_proof = ballots[0].sum_proof.clone();
+
+        let mut approval_board =
+            ApprovalBoard::new(&generators, ballots.len(), t, c, min, max).unwrap();
+        for ballot in ballots {
+            approval_board.ingest_ballot(ballot).unwrap();
+        }
+        approval_board.verify_ballots();
+        assert_eq!(approval_board.count_valid_ballots(), 3);
+
+        let tallies: Vec<u64> = approval_board
+            .candidates
+            .iter()
+            .map(|bulletin_board| decrypt_tally(&mut talliers, bulletin_board))
+            .collect();
+        assert_eq!(tallies, [2, 1, 2]);
+    }
+
+    #[test]
+    fn revoting() {
+        let (n, t) = (5, 2);
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let (generators, mut talliers) = election(n, t);
+        let G_table = generators.G_table();
+
+        let credentials: Vec<SigningKey> = (0..5)
+            .map(|_| SigningKey::generate(&mut rng, G_table))
+            .collect();
+        // voter 4 is not on the roll
+        let roll: Vec<RistrettoPoint> = credentials[..4]
+            .iter()
+            .map(|credential| credential.public_key)
+            .collect();
+        let mut voters: Vec<Voter> = Voter::generate_voters(5, n, t, &generators)
+            .into_iter()
+            .zip(credentials)
+            .map(|(voter, credential)| voter.with_credential(credential))
+            .collect();
+        let mut bulletin_board = BulletinBoard::new(&generators, 7, t).with_credential_roll(&roll);
+
+        // voter 0 is coerced into voting yes and overrides it with a no
+        let (coerced, coerced_credential) = voters[0]
+            .vote_signed(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap();
+        let (revote, revote_credential) = voters[0]
+            .vote_signed(&mut rng, &mut hasher, &mut buf, false)
+            .unwrap();
+        let (other, other_credential) = voters[1]
+            .vote_signed(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap();
+        // signed mode refuses unsigned ballots
+        let anonymous = voters[2]
+            .vote(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap();
+        // someone else tries to override voter 0
+        let (forged, mut forged_credential) = voters[3]
+            .vote_signed(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap();
+        forged_credential.public_key = coerced_credential.public_key;
+        forged_credential.sequence = 5;
+        let (outsider, outsider_credential) = voters[4]
+            .vote_signed(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap();
+
+        let ballots = [
+            (coerced.clone(), Some(coerced_credential.clone())),
+            (revote, Some(revote_credential)),
+            (other, Some(other_credential)),
+            (anonymous, None),
+            // replaying the coerced ballot does not bring it back
+            (coerced, Some(coerced_credential)),
+            (forged, Some(forged_credential)),
+            (outsider, Some(outsider_credential)),
+        ];
+        for ((encrypted_shares, dealer_proof, encrypted_vote, vote_proof), credential) in ballots {
+            let encrypted_shares = encrypted_shares.iter().map(|share| share.1).collect();
+            let encrypted_vote = encrypted_vote.decompress().unwrap();
+            match credential {
+                Some(credential) => bulletin_board.ingest_signed_vote(
+                    encrypted_shares,
+                    dealer_proof,
+                    encrypted_vote,
+                    vote_proof.decompress(),
+                    credential,
+                ),
+                None => bulletin_board.ingest_vote(
+                    encrypted_shares,
+                    dealer_proof,
+                    encrypted_vote,
+                    vote_proof.decompress(),
+                ),
+            }
+        }
+        bulletin_board.verify_encrypted_shares();
+        bulletin_board.verify_votes();
+
+        let statuses: Vec<bool> = bulletin_board
+            .encrypted_votes
+            .iter()
+            .map(|(status, _)| *status)
+            .collect();
+        assert_eq!(statuses, [true, true, true, false, true, false, false]);
+        assert_eq!(
+            bulletin_board.superseded(),
+            [true, false, false, false, true, true, true]
+        );
+        assert_eq!(bulletin_board.count_valid_votes(), 2);
+        assert_eq!(decrypt_tally(&mut talliers, &bulletin_board), 1);
+    }
+
+    #[test]
+    fn cast_or_audit() {
+        let (n, t) = (5, 2);
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let (generators, mut talliers) = election(n, t);
+        let mut voter = Voter::new(n, t, &generators).unwrap();
+        let mut bulletin_board = BulletinBoard::new(&generators, 1, t);
+
+        // the voter challenges the first ballot, it is spoiled and never cast
+        let audit = voter
+            .prepare(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap()
+            .audit();
+        assert!(
+            audit
+                .verify(&generators, n, t, &mut hasher, &mut buf)
+                .unwrap()
+        );
+
+        // a client that encrypted another choice than the one shown is caught
+        let mut lying = voter
+            .prepare(&mut rng, &mut hasher, &mut buf, false)
+            .unwrap()
+            .audit();
+        lying.choice = true;
+        assert!(
+            !lying
+                .verify(&generators, n, t, &mut hasher, &mut buf)
+                .unwrap()
+        );
+        let mut reseeded = voter
+            .prepare(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap()
+            .audit();
+        reseeded.seed = audit.seed;
+        assert!(
+            !reseeded
+                .verify(&generators, n, t, &mut hasher, &mut buf)
+                .unwrap()
+        );
+
+        let prepared = voter
+            .prepare(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap();
+        let (encrypted_shares, dealer_proof, encrypted_vote, vote_proof) = prepared.cast();
+        bulletin_board.ingest_vote(
+            encrypted_shares.iter().map(|share| share.1).collect(),
+            dealer_proof,
+            encrypted_vote.decompress().unwrap(),
+            vote_proof.decompress(),
+        );
+        bulletin_board.verify_encrypted_shares();
+        bulletin_board.verify_votes();
+
+        assert_eq!(bulletin_board.count_valid_votes(), 1);
+        assert_eq!(decrypt_tally(&mut talliers, &bulletin_board), 1);
+    }
+
+    #[test]
+    fn receipt_free() {
+        let (n, t) = (5, 2);
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let (generators, mut talliers) = election(n, t);
+        let G_table = generators.G_table();
+        let rerandomizer =
+            Rerandomizer::new(t, &generators, SigningKey::generate(&mut rng, G_table)).unwrap();
+        let mut bulletin_board =
+            BulletinBoard::new(&generators, 3, t).receipt_free(*rerandomizer.public_key());
+
+        let mut voters: Vec<Voter> = Voter::generate_voters(3, n, t, &generators)
+            .into_iter()
+            .map(|voter| voter.with_credential(SigningKey::generate(&mut rng, G_table)))
+            .collect();
+
+        for (voter, choice) in voters.iter_mut().zip([true, false]) {
+            let (commitment, pending) = voter.commit_ballot(&mut rng, choice).unwrap();
+            let (rerandomization, challenge) = rerandomizer
+                .challenge(&mut rng, &commitment, &mut hasher, &mut buf)
+                .unwrap();
+
+            // a rerandomizer flipping the vote can't convince the voter
+            assert!(!challenge.proof.verify(
+                &generators,
+                t,
+                &commitment,
+                &challenge.encrypted_shares,
+                &(challenge.encrypted_vote + generators.G()),
+                &mut hasher,
+                &mut buf,
+            ));
+
+            let response = pending
+                .respond(&generators, &challenge, &mut hasher, &mut buf)
+                .unwrap();
+            let ((encrypted_shares, dealer_proof, encrypted_vote, vote_proof), signature) =
+                rerandomizer
+                    .finish(&mut rng, rerandomization, response, &mut hasher, &mut buf)
+                    .unwrap();
+            // the voter's s no longer opens the tallied ballot
+            assert_ne!(encrypted_vote, commitment.encrypted_vote.compress());
+
+            bulletin_board.ingest_rerandomized_vote(
+                encrypted_shares.iter().map(|share| share.1).collect(),
+                dealer_proof,
+                encrypted_vote.decompress().unwrap(),
+                vote_proof.decompress(),
+                signature,
+            );
+        }
+        // cast directly, it still carries a receipt
+        let (encrypted_shares, dealer_proof, encrypted_vote, vote_proof) = voters[2]
+            .vote(&mut rng, &mut hasher, &mut buf, true)
+            .unwrap();
+        bulletin_board.ingest_vote(
+            encrypted_shares.iter().map(|share| share.1).collect(),
+            dealer_proof,
+            encrypted_vote.decompress().unwrap(),
+            vote_proof.decompress(),
+        );
+
+        bulletin_board.verify_encrypted_shares();
+        bulletin_board.verify_votes();
+
+        let statuses: Vec<bool> = bulletin_board
+            .encrypted_votes
+            .iter()
+            .map(|(status, _)| *status)
+            .collect();
+        assert_eq!(statuses, [true, true, false]);
+        assert_eq!(decrypt_tally(&mut talliers, &bulletin_board), 1);
+    }
 }
@@ -0,0 +1,515 @@
+use blake3::Hasher;
+use common::{
+    error::{
+        Error,
+        ErrorKind::{CountMismatch, InvalidProof},
+    },
+    generators::Generators,
+    polynomial::Polynomial,
+    random::random_scalar,
+    secret::Secret,
+    signature::{Signature, SigningKey},
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use pi_s_ppvss::utils::verify_encrypted_shares_standalone;
+use rand::{CryptoRng, RngCore};
+use rayon::prelude::*;
+use std::sync::Arc;
+use zeroize::Zeroize;
+
+use crate::voter::{Ballot, CompressedVoteProof};
+
+/// "rerandomized" | session | encrypted_vote | encrypted_shares
+pub fn rerandomized_message(
+    session: &[u8; 32],
+    encrypted_vote: &CompressedRistretto,
+    encrypted_shares: &[CompressedRistretto],
+) -> Vec<u8> {
+    b"rerandomized"
+        .iter()
+        .chain(session)
+        .chain(encrypted_vote.as_bytes())
+        .chain(encrypted_shares.iter().flat_map(|share| share.as_bytes()))
+        .copied()
+        .collect()
+}
+
+/// First move of a ballot cast through a `Rerandomizer`: the encrypted shares and vote with the
+/// commitments of both proofs
+#[derive(Clone)]
+pub struct BallotCommitment {
+    /// the voter's credential, the rerandomizer proves its work to this key only
+    pub public_key: RistrettoPoint,
+    pub encrypted_shares: Vec<RistrettoPoint>,
+    pub encrypted_vote: RistrettoPoint,
+    /// pk_i * r(i) of the dealing proof
+    pub share_commitments: Vec<RistrettoPoint>,
+    /// a0, a1, b0, b1 of the vote proof
+    pub vote_commitments: [RistrettoPoint; 4],
+}
+
+/// What the voter keeps between `Voter::commit_ballot` and `PendingBallot::respond`
+pub struct PendingBallot {
+    pub(crate) commitment: BallotCommitment,
+    /// f(0) = s
+    pub(crate) f: Secret<Polynomial>,
+    pub(crate) r: Secret<Polynomial>,
+    pub(crate) choice: bool,
+    pub(crate) vote_proof: CompressedVoteProof,
+}
+
+impl PendingBallot {
+    /// Third move: answers the rerandomizer's blinded challenges, once its proof shows the
+    /// rerandomized ballot holds the same vote
+    pub fn respond(
+        mut self,
+        generators: &Generators,
+        challenge: &RerandomizedChallenge,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<BallotResponse, Error> {
+        let t = self.f.expose().len() - 1;
+        if !challenge.proof.verify(
+            generators,
+            t,
+            &self.commitment,
+            &challenge.encrypted_shares,
+            &challenge.encrypted_vote,
+            hasher,
+            buf,
+        ) {
+            return Err(InvalidProof("rerandomization".to_string()).into());
+        }
+
+        let mut z = self.f.expose().clone();
+        z.mul_sum(&challenge.d, self.r.expose());
+        self.vote_proof.respond(
+            &challenge.c,
+            &self.f.expose().coefficients()[0],
+            self.choice,
+        );
+
+        Ok(BallotResponse {
+            z,
+            d0: self.vote_proof.d0,
+            d1: self.vote_proof.d1,
+            r0: self.vote_proof.r0,
+            r1: self.vote_proof.r1,
+        })
+    }
+}
+
+/// Second move: the rerandomized ballot and the blinded challenges the voter answers
+pub struct RerandomizedChallenge {
+    pub encrypted_shares: Vec<RistrettoPoint>,
+    pub encrypted_vote: RistrettoPoint,
+    /// of the dealing proof
+    pub d: Scalar,
+    /// of the vote proof
+    pub c: Scalar,
+    pub proof: RerandomizationProof,
+}
+
+pub struct BallotResponse {
+    pub z: Polynomial,
+    pub d0: Scalar,
+    pub d1: Scalar,
+    pub r0: Scalar,
+    pub r1: Scalar,
+}
+
+/// Proof to the voter that the rerandomized ballot adds a dealing of some g to theirs, i.e.
+/// shares + pk_i * g(i) and encrypted_vote + G * g(0), or that the prover knows the voter's
+/// key. The voter can simulate it, so it convinces no one else.
+#[derive(Clone)]
+pub struct RerandomizationProof {
+    /// challenges of the rerandomization and the key branch
+    e: [Scalar; 2],
+    z: Polynomial,
+    z_key: Scalar,
+}
+
+/// "rerandomization" | session | public_key | deltas | delta_vote | commitments
+fn rerandomization_challenge(
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+    session: &[u8; 32],
+    public_key: &RistrettoPoint,
+    deltas: &[RistrettoPoint],
+    delta_vote: &RistrettoPoint,
+    commitments: &[RistrettoPoint],
+) -> Scalar {
+    hasher.update(b"rerandomization");
+    hasher.update(session);
+    hasher.update(public_key.compress().as_bytes());
+    deltas
+        .iter()
+        .chain([delta_vote])
+        .chain(commitments)
+        .for_each(|point| {
+            hasher.update(point.compress().as_bytes());
+        });
+
+    hasher.finalize_xof().fill(buf);
+    let e = Scalar::from_bytes_mod_order_wide(buf);
+
+    buf.zeroize();
+    hasher.reset();
+
+    e
+}
+
+/// shares - original shares and encrypted_vote - original vote
+fn deltas(
+    commitment: &BallotCommitment,
+    encrypted_shares: &[RistrettoPoint],
+    encrypted_vote: &RistrettoPoint,
+) -> (Vec<RistrettoPoint>, RistrettoPoint) {
+    (
+        encrypted_shares
+            .par_iter()
+            .zip(commitment.encrypted_shares.par_iter())
+            .map(|(share, original)| share - original)
+            .collect(),
+        encrypted_vote - commitment.encrypted_vote,
+    )
+}
+
+impl RerandomizationProof {
+    #[allow(clippy::too_many_arguments)]
+    fn prove<R>(
+        rng: &mut R,
+        generators: &Generators,
+        g: &Polynomial,
+        commitment: &BallotCommitment,
+        encrypted_shares: &[RistrettoPoint],
+        encrypted_vote: &RistrettoPoint,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let G_table = generators.G_table();
+        let rho = Secret::new(Polynomial::sample(g.len() - 1, rng));
+        // the key branch is simulated
+        let (e_key, z_key) = (random_scalar(rng), random_scalar(rng));
+
+        let (_, mut commitments) = rho
+            .expose()
+            .evaluate_multiply(&generators.dealing_tables(), 0);
+        commitments.push(G_table * &rho.expose().coefficients()[0]);
+        commitments.push(G_table * &z_key - commitment.public_key * e_key);
+
+        let (deltas, delta_vote) = deltas(commitment, encrypted_shares, encrypted_vote);
+        let e = rerandomization_challenge(
+            hasher,
+            buf,
+            &generators.session(),
+            &commitment.public_key,
+            &deltas,
+            &delta_vote,
+            &commitments,
+        );
+
+        let e_g = e - e_key;
+        let mut z = g.clone();
+        z.mul_sum(&e_g, rho.expose());
+
+        Self {
+            e: [e_g, e_key],
+            z,
+            z_key,
+        }
+    }
+
+    /// `t` is the degree of the voter's dealing, g must not exceed it
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        t: usize,
+        commitment: &BallotCommitment,
+        encrypted_shares: &[RistrettoPoint],
+        encrypted_vote: &RistrettoPoint,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let dealing_tables = generators.dealing_tables();
+        if self.z.len() != t + 1
+            || encrypted_shares.len() != dealing_tables.len()
+            || commitment.encrypted_shares.len() != dealing_tables.len()
+        {
+            return false;
+        }
+        let G_table = generators.G_table();
+        let [e_g, e_key] = self.e;
+
+        let (deltas, delta_vote) = deltas(commitment, encrypted_shares, encrypted_vote);
+        let (_, mut commitments) = self.z.evaluate_multiply(&dealing_tables, 0);
+        commitments
+            .par_iter_mut()
+            .zip(deltas.par_iter())
+            .for_each(|(commitment, delta)| *commitment -= delta * e_g);
+        commitments.push(G_table * &self.z.coefficients()[0] - delta_vote * e_g);
+        commitments.push(G_table * &self.z_key - commitment.public_key * e_key);
+
+        e_g + e_key
+            == rerandomization_challenge(
+                hasher,
+                buf,
+                &generators.session(),
+                &commitment.public_key,
+                &deltas,
+                &delta_vote,
+                &commitments,
+            )
+    }
+}
+
+/// The rerandomizer's state between `Rerandomizer::challenge` and `Rerandomizer::finish`
+pub struct Rerandomization {
+    /// the fresh dealing added to the voter's, g(0) is added to s
+    g: Secret<Polynomial>,
+    /// blinds the dealing proof
+    alpha: Secret<Polynomial>,
+    beta: Scalar,
+    /// blind the two branches of the vote proof
+    alphas: [Scalar; 2],
+    betas: [Scalar; 2],
+    encrypted_shares: Vec<RistrettoPoint>,
+    encrypted_vote: RistrettoPoint,
+    vote_commitments: [RistrettoPoint; 4],
+    d: Scalar,
+}
+
+/// Rerandomizes ballots on their way to the bulletin board, so the voter's s no longer opens the
+/// tallied ballot and can't serve as a receipt. The ballot's proofs are diverted from the voter's:
+/// the voter answers blinded challenges without learning the new randomness.
+pub struct Rerandomizer {
+    pub t: usize,
+    /// G, pk0 and the talliers' public keys
+    pub generators: Arc<Generators>,
+    /// signs every ballot it rerandomized
+    key: SigningKey,
+}
+
+impl Rerandomizer {
+    pub fn new(t: usize, generators: &Arc<Generators>, key: SigningKey) -> Result<Self, Error> {
+        generators.pk0()?;
+
+        Ok(Self {
+            t,
+            generators: generators.clone(),
+            key,
+        })
+    }
+
+    pub fn public_key(&self) -> &RistrettoPoint {
+        &self.key.public_key
+    }
+
+    /// Second move: adds a fresh dealing of g to the voter's, with g(0) added to the encrypted
+    /// vote, and blinds both proofs' commitments
+    pub fn challenge<R>(
+        &self,
+        rng: &mut R,
+        commitment: &BallotCommitment,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<(Rerandomization, RerandomizedChallenge), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let pk0 = self.generators.pk0_table()?;
+        let (G, G_table) = (self.generators.G(), self.generators.G_table());
+        let dealing_tables = self.generators.dealing_tables();
+        for (len, name) in [
+            (commitment.encrypted_shares.len(), "encrypted shares"),
+            (commitment.share_commitments.len(), "share commitments"),
+        ] {
+            if len != dealing_tables.len() {
+                return Err(CountMismatch(dealing_tables.len(), "dealing keys", len, name).into());
+            }
+        }
+
+        let g = Secret::new(Polynomial::sample(self.t, rng));
+        let alpha = Secret::new(Polynomial::sample(self.t, rng));
+        let beta = random_scalar(rng);
+
+        let (_, deltas) = g.expose().evaluate_multiply(&dealing_tables, 0);
+        let (_, blinds) = alpha.expose().evaluate_multiply(&dealing_tables, 0);
+        let encrypted_shares: Vec<RistrettoPoint> = commitment
+            .encrypted_shares
+            .par_iter()
+            .zip(deltas.par_iter())
+            .map(|(share, delta)| share + delta)
+            .collect();
+        let encrypted_vote = commitment.encrypted_vote + G_table * &g.expose().coefficients()[0];
+
+        // the dealing proof's challenge is bound to the rerandomized shares
+        let share_commitments: Vec<CompressedRistretto> = commitment
+            .share_commitments
+            .par_iter()
+            .zip(blinds.par_iter())
+            .zip(encrypted_shares.par_iter())
+            .map(|((share_commitment, blind), share)| {
+                (share_commitment + blind - share * beta).compress()
+            })
+            .collect();
+        encrypted_shares
+            .iter()
+            .map(|share| share.compress())
+            .chain(share_commitments)
+            .for_each(|x| {
+                hasher.update(x.as_bytes());
+            });
+        hasher.finalize_xof().fill(buf);
+        let d = Scalar::from_bytes_mod_order_wide(buf);
+        buf.zeroize();
+        hasher.reset();
+
+        // same for the vote proof, each branch is blinded on its own
+        let alphas = [random_scalar(rng), random_scalar(rng)];
+        let betas = [random_scalar(rng), random_scalar(rng)];
+        let y0 = encrypted_shares[0];
+        let [a0, a1, b0, b1] = commitment.vote_commitments;
+        let vote_commitments = [
+            a0 + pk0 * &alphas[0] + y0 * betas[0],
+            a1 + pk0 * &alphas[1] + y0 * betas[1],
+            b0 + G_table * &alphas[0] + encrypted_vote * betas[0],
+            b1 + G_table * &alphas[1] + (encrypted_vote - G) * betas[1],
+        ];
+        hasher.update(encrypted_vote.compress().as_bytes());
+        hasher.update(y0.compress().as_bytes());
+        [0, 2, 1, 3].iter().for_each(|j| {
+            hasher.update(vote_commitments[*j].compress().as_bytes());
+        });
+        hasher.finalize_xof().fill(buf);
+        let c = Scalar::from_bytes_mod_order_wide(buf);
+        buf.zeroize();
+        hasher.reset();
+
+        let proof = RerandomizationProof::prove(
+            rng,
+            &self.generators,
+            g.expose(),
+            commitment,
+            &encrypted_shares,
+            &encrypted_vote,
+            hasher,
+            buf,
+        );
+
+        Ok((
+            Rerandomization {
+                g,
+                alpha,
+                beta,
+                alphas,
+                betas,
+                encrypted_shares: encrypted_shares.clone(),
+                encrypted_vote,
+                vote_commitments,
+                d,
+            },
+            RerandomizedChallenge {
+                encrypted_shares,
+                encrypted_vote,
+                d: d - beta,
+                c: c - betas[0] - betas[1],
+                proof,
+            },
+        ))
+    }
+
+    /// Last move: unblinds the voter's responses into proofs for the rerandomized ballot and signs
+    /// it for the bulletin board
+    pub fn finish<R>(
+        &self,
+        rng: &mut R,
+        rerandomization: Rerandomization,
+        response: BallotResponse,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<(Ballot, Signature), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let Rerandomization {
+            g,
+            alpha,
+            beta,
+            alphas,
+            betas,
+            encrypted_shares,
+            encrypted_vote,
+            vote_commitments,
+            d,
+        } = rerandomization;
+        let g0 = g.expose().coefficients()[0];
+
+        // z' = z + alpha + (d - beta) * g
+        let mut z = g.expose().clone();
+        z.mul_sum(&(d - beta), &response.z.sum(alpha.expose()));
+
+        let [a0, a1, b0, b1] = vote_commitments.map(|point| point.compress());
+        let vote_proof = CompressedVoteProof {
+            a0,
+            a1,
+            b0,
+            b1,
+            c: Scalar::ZERO,
+            d0: response.d0 + betas[0],
+            d1: response.d1 + betas[1],
+            r0: response.r0 + alphas[0] - g0 * response.d0,
+            r1: response.r1 + alphas[1] - g0 * response.d1,
+        };
+
+        let compressed_shares: Vec<CompressedRistretto> = encrypted_shares
+            .par_iter()
+            .map(|share| share.compress())
+            .collect();
+        let dealing_holds = verify_encrypted_shares_standalone(
+            &(compressed_shares.clone(), encrypted_shares.clone()),
+            &self.generators.dealing_tables(),
+            (&d, &z),
+            hasher,
+            buf,
+        )?;
+        let vote_holds = vote_proof.clone().decompress().verify(
+            hasher,
+            buf,
+            &self.generators,
+            &encrypted_vote,
+            &encrypted_shares[0],
+        );
+        if !dealing_holds || !vote_holds {
+            return Err(InvalidProof("voter response".to_string()).into());
+        }
+
+        let encrypted_vote = encrypted_vote.compress();
+        let signature = self.key.sign(
+            rng,
+            self.generators.G_table(),
+            &rerandomized_message(
+                &self.generators.session(),
+                &encrypted_vote,
+                &compressed_shares,
+            ),
+        );
+
+        Ok((
+            (
+                compressed_shares
+                    .into_iter()
+                    .zip(encrypted_shares)
+                    .collect(),
+                (d, z),
+                encrypted_vote,
+                vote_proof,
+            ),
+            signature,
+        ))
+    }
+}
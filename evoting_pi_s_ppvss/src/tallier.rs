@@ -1,14 +1,14 @@
 use common::{
-    error::{Error, ErrorKind::InvalidPararmeterSet},
-    random::random_scalar,
+    error::Error,
+    generators::Generators,
+    keystore::{KdfParams, read_keystore, write_keystore},
 };
-use curve25519_dalek::RistrettoPoint;
 use pi_s_ppvss::party::Party;
 use rand::{CryptoRng, RngCore};
+use std::{path::Path, sync::Arc};
 
 use crate::bulletin_board::BulletinBoard;
 
-#[derive(Clone)]
 pub struct Tallier {
     pub party: Party,
     pub bulletin_board: Option<BulletinBoard>,
@@ -16,60 +16,64 @@ pub struct Tallier {
 
 impl Tallier {
     pub fn new<R>(
-        G: &RistrettoPoint,
+        generators: &Arc<Generators>,
         rng: &mut R,
         n: usize,
         t: usize,
         index: usize,
-        pk0: RistrettoPoint,
     ) -> Result<Self, Error>
     where
         R: CryptoRng + RngCore,
     {
-        let private_key = random_scalar(rng);
-        let public_key = G * &private_key;
+        Ok(Self {
+            party: Party::new(generators, rng, n, t, index)?,
+            bulletin_board: None,
+        })
+    }
+
+    /// Loads a tallier whose keypair was registered ahead of the election
+    pub fn from_keystore<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        path: &Path,
+        password: &[u8],
+        n: usize,
+        t: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let keypair = read_keystore(path, password, generators.G_table())?;
+        Ok(Self {
+            party: Party::from_keypair(generators, rng, &keypair, n, t)?,
+            bulletin_board: None,
+        })
+    }
 
-        if index <= n && t < n && t as f32 == ((n - 1) as f32 / 2.0).floor() {
-            Ok(Self {
-                party: Party {
-                    private_key,
-                    public_key: (public_key.compress(), public_key),
-                    index,
-                    n,
-                    t,
-                    dealer_proof: None,
-                    encrypted_share: None,
-                    decrypted_share: None,
-                    share_proof: None,
-                    share_proofs: None,
-                    encrypted_shares: None,
-                    decrypted_shares: None,
-                    public_keys: None,
-                    validated_shares: vec![],
-                    pk0: pk0,
-                },
-                bulletin_board: None,
-            })
-        } else {
-            Err(InvalidPararmeterSet(n, t as isize, index).into())
-        }
+    pub fn write_keystore<R>(
+        &self,
+        path: &Path,
+        password: &[u8],
+        params: &KdfParams,
+        rng: &mut R,
+    ) -> Result<(), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        write_keystore(path, &self.party.keypair(), password, params, rng)
     }
 
     pub fn generate_talliers<R>(
-        G: &RistrettoPoint,
+        generators: &Arc<Generators>,
         rng: &mut R,
         n: usize,
         t: usize,
-        pk0: &RistrettoPoint,
     ) -> Vec<Self>
     where
         R: CryptoRng + RngCore,
     {
         (1..=n)
-            .map(|i| Self {
-                party: Party::new(G, rng, n, t, i, pk0.clone()).unwrap(),
-                bulletin_board: None,
-            })
+            .map(|i| Self::new(generators, rng, n, t, i).unwrap())
             .collect()
     }
 }
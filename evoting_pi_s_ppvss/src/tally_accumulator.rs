@@ -0,0 +1,124 @@
+use crate::voter::VoteProof;
+use blake3::Hasher;
+use common::{generators::Generators, polynomial::Polynomial};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto, traits::Identity};
+use pi_s_ppvss::utils::verify_encrypted_shares_standalone;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Verifies ballots as they arrive and keeps only running sums, so memory is O(n) instead of O(m * n).
+/// The accepted ballots themselves are committed to in a running blake3 digest for later audit.
+#[derive(Clone)]
+pub struct TallyAccumulator {
+    /// G, pk0 and the talliers' public keys
+    pub generators: Arc<Generators>,
+    pub t: usize,
+    // same layout as BulletinBoard::sum_encrypted_shares, the y0 slot stays the identity
+    encrypted_share_sums: Vec<RistrettoPoint>,
+    encrypted_vote_sum: RistrettoPoint,
+    valid_votes: usize,
+    rejected_votes: usize,
+    digest: Hasher,
+}
+
+impl TallyAccumulator {
+    pub fn new(generators: &Arc<Generators>, t: usize) -> Self {
+        Self {
+            generators: generators.clone(),
+            t,
+            encrypted_share_sums: vec![
+                RistrettoPoint::identity();
+                generators.public_keys().len() + 1
+            ],
+            encrypted_vote_sum: RistrettoPoint::identity(),
+            valid_votes: 0,
+            rejected_votes: 0,
+            digest: Hasher::new(),
+        }
+    }
+
+    /// Verifies the dealing and the vote proof of a ballot and folds it into the tally if both hold.
+    /// A rejected ballot only bumps the rejection count, leaving the sums and the digest as they were.
+    pub fn ingest_vote(
+        &mut self,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        encrypted_shares: Vec<RistrettoPoint>,
+        encrypted_share_proof: (Scalar, Polynomial),
+        encrypted_vote: RistrettoPoint,
+        vote_proof: VoteProof,
+    ) -> bool {
+        let compressed_shares: Vec<CompressedRistretto> = encrypted_shares
+            .par_iter()
+            .map(|share| share.compress())
+            .collect();
+        let encrypted_shares = (compressed_shares, encrypted_shares);
+        let (d, z) = &encrypted_share_proof;
+        let dealing_keys = self.generators.dealing_tables();
+
+        let valid = encrypted_shares.1.len() == dealing_keys.len()
+            && z.len() == self.t + 1
+            && verify_encrypted_shares_standalone(
+                &encrypted_shares,
+                &dealing_keys,
+                (d, z),
+                hasher,
+                buf,
+            )
+            .unwrap_or(false)
+            && vote_proof.verify(
+                hasher,
+                buf,
+                &self.generators,
+                &encrypted_vote,
+                &encrypted_shares.1[0],
+            );
+
+        if valid {
+            encrypted_shares
+                .0
+                .iter()
+                .for_each(|share| _ = self.digest.update(share.as_bytes()));
+            self.digest.update(d.as_bytes());
+            z.coefficients()
+                .iter()
+                .for_each(|coefficient| _ = self.digest.update(coefficient.as_bytes()));
+            self.digest.update(encrypted_vote.compress().as_bytes());
+            self.digest.update(&vote_proof.compress().to_bytes());
+
+            encrypted_shares
+                .1
+                .par_iter()
+                .skip(1)
+                // this is where we skip y0
+                .zip(self.encrypted_share_sums.par_iter_mut().skip(1))
+                .for_each(|(enc_share, sum)| *sum += enc_share);
+            self.encrypted_vote_sum += encrypted_vote;
+            self.valid_votes += 1;
+        } else {
+            self.rejected_votes += 1;
+        }
+        valid
+    }
+
+    pub fn sum_encrypted_shares(&self) -> Vec<RistrettoPoint> {
+        self.encrypted_share_sums.clone()
+    }
+
+    pub fn tally_encrypted_votes(&self) -> RistrettoPoint {
+        self.encrypted_vote_sum
+    }
+
+    pub fn count_valid_votes(&self) -> usize {
+        self.valid_votes
+    }
+
+    pub fn count_rejected_votes(&self) -> usize {
+        self.rejected_votes
+    }
+
+    /// blake3 over every accepted ballot, in ingestion order
+    pub fn digest(&self) -> [u8; 32] {
+        *self.digest.finalize().as_bytes()
+    }
+}
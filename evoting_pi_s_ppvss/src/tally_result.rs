@@ -0,0 +1,152 @@
+use blake3::Hasher;
+use common::{
+    error::{
+        Error,
+        ErrorKind::{CountMismatch, UndecodableTally, UninitializedValue},
+    },
+    utils::{batch_decompress_ristretto_points, lagrange_coefficients, verify_decrypted_share},
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto, traits::Identity};
+
+use crate::{bulletin_board::BulletinBoard, tallier::Tallier};
+
+/// The published outcome of an election: enough for anyone holding the bulletin board
+/// to check the decoded count without redoing the decryption
+#[derive(Clone, Debug)]
+pub struct TallyResult {
+    /// one per share position, slot 0 (pk0) included
+    pub aggregated_encrypted_shares: Vec<CompressedRistretto>,
+    /// tallier indices (1-based) of the t+1 decrypted shares used for reconstruction
+    pub indices: Vec<usize>,
+    pub decrypted_shares: Vec<CompressedRistretto>,
+    pub share_proofs: Vec<(Scalar, Scalar)>,
+    pub count: u64,
+}
+
+/// G * s for the Lagrange interpolation at 0 of the given decrypted shares
+fn reconstruct(indices: &[usize], decrypted_shares: &[RistrettoPoint]) -> RistrettoPoint {
+    lagrange_coefficients(indices)
+        .iter()
+        .zip(decrypted_shares)
+        .map(|(lambda, share)| lambda * share)
+        .sum()
+}
+
+fn decode(G: &RistrettoPoint, exp_vote: &RistrettoPoint, max: u64) -> Option<u64> {
+    let mut acc = RistrettoPoint::identity();
+    for i in 0..=max {
+        if acc == *exp_vote {
+            return Some(i);
+        }
+        acc += G;
+    }
+    None
+}
+
+impl TallyResult {
+    /// Builds the result from a tallier that has verified the other talliers' decrypted shares
+    pub fn new(bulletin_board: &BulletinBoard, tallier: &Tallier) -> Result<Self, Error> {
+        let party = &tallier.party;
+        let (decrypted_shares, share_proofs) = match (&party.decrypted_shares, &party.share_proofs)
+        {
+            (Some(decrypted_shares), Some(share_proofs)) => (decrypted_shares, share_proofs),
+            _ => {
+                return Err(UninitializedValue("party.{decrypted_shares, share_proofs}").into());
+            }
+        };
+        if party.validated_shares.len() <= party.t {
+            return Err(CountMismatch(
+                party.t + 1,
+                "required shares",
+                party.validated_shares.len(),
+                "valid decrypted shares",
+            )
+            .into());
+        }
+
+        let positions = &party.validated_shares[..=party.t];
+        let indices: Vec<usize> = positions.iter().map(|i| i + 1).collect();
+        let used_shares: Vec<RistrettoPoint> =
+            positions.iter().map(|i| decrypted_shares[*i]).collect();
+
+        let exp_vote = bulletin_board.tally_encrypted_votes() - reconstruct(&indices, &used_shares);
+        let count = decode(
+            bulletin_board.generators.G(),
+            &exp_vote,
+            bulletin_board.max_tally(),
+        )
+        .ok_or(Error::from(UndecodableTally))?;
+
+        Ok(Self {
+            aggregated_encrypted_shares: bulletin_board.sum_encrypted_shares_compressed(),
+            indices,
+            decrypted_shares: used_shares.iter().map(|share| share.compress()).collect(),
+            share_proofs: positions.iter().map(|i| share_proofs[*i]).collect(),
+            count,
+        })
+    }
+
+    /// Checks the result against the bulletin board: the aggregated shares, each decrypted share's
+    /// DLEQ proof, and that the reconstructed secret decodes the encrypted tally to `count`
+    pub fn verify(&self, bulletin_board: &BulletinBoard) -> Result<bool, Error> {
+        let generators = &bulletin_board.generators;
+        let public_keys = generators.public_keys();
+        let t = bulletin_board.t;
+
+        if self.indices.len() != t + 1 {
+            return Err(
+                CountMismatch(t + 1, "required shares", self.indices.len(), "indices").into(),
+            );
+        }
+        if self.decrypted_shares.len() != self.indices.len()
+            || self.share_proofs.len() != self.indices.len()
+        {
+            return Err(CountMismatch(
+                self.indices.len(),
+                "indices",
+                self.decrypted_shares.len().min(self.share_proofs.len()),
+                "decrypted shares and proofs",
+            )
+            .into());
+        }
+        if self.aggregated_encrypted_shares != bulletin_board.sum_encrypted_shares_compressed() {
+            return Ok(false);
+        }
+        if !self.indices.windows(2).all(|pair| pair[0] < pair[1])
+            || self.indices[0] == 0
+            || self.indices[t] > public_keys.len()
+        {
+            return Ok(false);
+        }
+
+        let aggregated_encrypted_shares =
+            batch_decompress_ristretto_points(&self.aggregated_encrypted_shares)?;
+        let decrypted_shares = batch_decompress_ristretto_points(&self.decrypted_shares)?;
+
+        let mut hasher = Hasher::new();
+        let mut buf = [0u8; 64];
+        let proofs_hold = self
+            .indices
+            .iter()
+            .zip(decrypted_shares.iter().zip(&self.share_proofs))
+            .all(|(index, (decrypted_share, proof))| {
+                verify_decrypted_share(
+                    generators.G(),
+                    &public_keys[index - 1],
+                    &aggregated_encrypted_shares[*index],
+                    decrypted_share,
+                    proof,
+                    &mut hasher,
+                    &mut buf,
+                )
+            });
+        if !proofs_hold || self.count > bulletin_board.max_tally() {
+            return Ok(false);
+        }
+
+        Ok(
+            bulletin_board.tally_encrypted_votes() - reconstruct(&self.indices, &decrypted_shares)
+                == generators.G_table() * &Scalar::from(self.count),
+        )
+    }
+}
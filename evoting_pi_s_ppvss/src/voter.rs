@@ -1,17 +1,33 @@
 use blake3::Hasher;
-use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use curve25519_dalek::{
+    RistrettoPoint, Scalar,
+    ristretto::{CompressedRistretto, RistrettoBasepointTable},
+};
 
 use common::{
-    error::{Error, ErrorKind::UninitializedValue},
+    error::{
+        Error,
+        ErrorKind::{InvalidProof, InvalidSelection, InvalidWeight, UninitializedValue},
+    },
+    generators::Generators,
     polynomial::Polynomial,
     random::random_scalar,
+    range_proof::{self, BitProof},
+    secret::Secret,
+    signature::{Signature, SigningKey},
     utils::batch_decompress_ristretto_points,
 };
-use rand::{CryptoRng, RngCore};
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use zeroize::Zeroize;
 
+use crate::{
+    approval::ApprovalBallot,
+    rerandomizer::{BallotCommitment, PendingBallot},
+};
 use pi_s_ppvss::{dealer::Dealer, utils::verify_encrypted_shares_standalone};
 use rayon::prelude::*;
+use std::sync::Arc;
 #[derive(Clone)]
 pub struct CompressedVoteProof {
     pub(crate) a0: CompressedRistretto,
@@ -48,6 +64,137 @@ impl CompressedVoteProof {
             r1,
         }
     }
+    /// Disjunctive proof that `u` opens to 0 or 1 with the same exponent as `y0`
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn prove<R>(
+        rng: &mut R,
+        generators: &Generators,
+        u: &RistrettoPoint,
+        y0: &RistrettoPoint,
+        s: &Scalar,
+        bit: bool,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut proof = Self::commit(rng, generators, u, y0, bit)?;
+
+        hasher.update(u.compress().as_bytes());
+        hasher.update(y0.compress().as_bytes());
+        hasher.update(proof.a0.as_bytes());
+        hasher.update(proof.b0.as_bytes());
+        hasher.update(proof.a1.as_bytes());
+        hasher.update(proof.b1.as_bytes());
+
+        hasher.finalize_xof().fill(buf);
+
+        proof.respond(&Scalar::from_bytes_mod_order_wide(buf), s, bit);
+
+        buf.zeroize();
+        hasher.reset();
+
+        Ok(proof)
+    }
+
+    /// The commitments of `prove`. The simulated branch is already answered, the real one holds
+    /// its nonce until `respond`.
+    pub(crate) fn commit<R>(
+        rng: &mut R,
+        generators: &Generators,
+        u: &RistrettoPoint,
+        y0: &RistrettoPoint,
+        bit: bool,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let pk0 = generators.pk0_table()?;
+        let (G, G_table) = (generators.G(), generators.G_table());
+
+        let w = random_scalar(rng);
+
+        // if vote {proof.d0 = d0t_d1f; proof.d1 = d0f_d1t} else {proof.d0 = d0f_d1t; proof.d1 = d0t_d1f};
+        let d0t_d1f = random_scalar(rng);
+        let d0f_d1t = -d0t_d1f;
+
+        // if vote {proof.r0 = r0t_r1f} else {proof.r1 = r0t_r1f};
+        let r0t_r1f = random_scalar(rng);
+
+        // proof.a0 = vote ? a0t_a1f : a0f_a1t;
+        // proof.a1 = vote ? a0f_a1t : a0t_a1f;
+        let a0t_a1f = ((&r0t_r1f * pk0) + (d0t_d1f * y0)).compress();
+        let a0f_a1t = (&w * pk0).compress();
+
+        // if vote {proof.b0 = b0t; proof.b1 = b0f_b1t} else {proof.b0 = b0f_b1t; proof.b1 = b1f};
+        let b0f_b1t = (&w * G_table).compress();
+        let b1f = ((&r0t_r1f * G_table) + ((u - G) * d0t_d1f)).compress();
+        let b0t = ((&r0t_r1f * G_table) + (u * d0t_d1f)).compress();
+
+        Ok(match bit {
+            true => CompressedVoteProof::new(
+                a0t_a1f, a0f_a1t, b0t, b0f_b1t, d0t_d1f, d0f_d1t, r0t_r1f, w,
+            ),
+            false => CompressedVoteProof::new(
+                a0f_a1t, a0t_a1f, b0f_b1t, b1f, d0f_d1t, d0t_d1f, w, r0t_r1f,
+            ),
+        })
+    }
+
+    /// Answers the challenge `c` on the real branch, d0 + d1 = c afterwards
+    pub(crate) fn respond(&mut self, c: &Scalar, s: &Scalar, bit: bool) {
+        match bit {
+            true => {
+                self.d1 += c;
+                self.r1 -= s * self.d1;
+            }
+            false => {
+                self.d0 += c;
+                self.r0 -= s * self.d0;
+            }
+        }
+    }
+
+    /// a0 | a1 | b0 | b1 | d0 | d1 | r0 | r1
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.a0, self.a1, self.b0, self.b1]
+            .iter()
+            .flat_map(|point| point.to_bytes())
+            .chain(
+                [self.d0, self.d1, self.r0, self.r1]
+                    .iter()
+                    .flat_map(|scalar| scalar.to_bytes()),
+            )
+            .collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != 8 * 32 {
+            return Err(InvalidProof(format!("vote proof len: {}", bytes.len())).into());
+        }
+        let chunks: Vec<[u8; 32]> = bytes
+            .chunks_exact(32)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect();
+        let scalar = |chunk: [u8; 32]| -> Result<Scalar, Error> {
+            Option::from(Scalar::from_canonical_bytes(chunk))
+                .ok_or_else(|| InvalidProof(format!("non canonical scalar {chunk:x?}")).into())
+        };
+
+        Ok(Self {
+            a0: CompressedRistretto(chunks[0]),
+            a1: CompressedRistretto(chunks[1]),
+            b0: CompressedRistretto(chunks[2]),
+            b1: CompressedRistretto(chunks[3]),
+            c: Scalar::ZERO,
+            d0: scalar(chunks[4])?,
+            d1: scalar(chunks[5])?,
+            r0: scalar(chunks[6])?,
+            r1: scalar(chunks[7])?,
+        })
+    }
+
     pub fn decompress(self) -> VoteProof {
         VoteProof {
             a0: self.a0.decompress().unwrap(),
@@ -79,11 +226,15 @@ impl VoteProof {
         &self,
         hasher: &mut Hasher,
         buf: &mut [u8; 64],
-        G: &RistrettoPoint,
+        generators: &Generators,
         encrypted_vote: &RistrettoPoint,
-        pk0: &RistrettoPoint,
         y0: &RistrettoPoint,
     ) -> bool {
+        let Ok(pk0) = generators.pk0_table() else {
+            return false;
+        };
+        let (G, G_table) = (generators.G(), generators.G_table());
+
         hasher.update(encrypted_vote.compress().as_bytes());
         hasher.update(y0.compress().as_bytes());
         hasher.update(self.a0.compress().as_bytes());
@@ -98,10 +249,10 @@ impl VoteProof {
         buf.zeroize();
 
         c == (self.d0 + self.d1)
-            && self.a0 == (pk0 * self.r0) + (y0 * self.d0)
-            && self.a1 == (pk0 * self.r1) + (y0 * self.d1)
-            && self.b0 == (G * self.r0) + (encrypted_vote * self.d0)
-            && self.b1 == (G * self.r1) + ((encrypted_vote - G) * self.d1)
+            && self.a0 == (pk0 * &self.r0) + (y0 * self.d0)
+            && self.a1 == (pk0 * &self.r1) + (y0 * self.d1)
+            && self.b0 == (G_table * &self.r0) + (encrypted_vote * self.d0)
+            && self.b1 == (G_table * &self.r1) + ((encrypted_vote - G) * self.d1)
     }
     pub fn compress(&self) -> CompressedVoteProof {
         CompressedVoteProof {
@@ -118,6 +269,82 @@ impl VoteProof {
     }
 }
 
+impl BitProof for VoteProof {
+    type Compressed = CompressedVoteProof;
+
+    fn base(generators: &Generators) -> Result<&RistrettoBasepointTable, Error> {
+        generators.pk0_table()
+    }
+
+    fn prove<R>(
+        rng: &mut R,
+        generators: &Generators,
+        u: &RistrettoPoint,
+        c0: &RistrettoPoint,
+        s: &Scalar,
+        bit: bool,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<CompressedVoteProof, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        CompressedVoteProof::prove(rng, generators, u, c0, s, bit, hasher, buf)
+    }
+
+    fn verify(
+        &self,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        generators: &Generators,
+        u: &RistrettoPoint,
+        c0: &RistrettoPoint,
+    ) -> bool {
+        VoteProof::verify(self, hasher, buf, generators, u, c0)
+    }
+
+    fn compress(&self) -> CompressedVoteProof {
+        VoteProof::compress(self)
+    }
+
+    fn decompress(compressed: CompressedVoteProof) -> Self {
+        compressed.decompress()
+    }
+}
+
+pub type RangeProof = range_proof::RangeProof<VoteProof>;
+pub type CompressedRangeProof = range_proof::CompressedRangeProof<VoteProof>;
+
+/// The proof attached to a ballot on the bulletin board
+#[derive(Clone)]
+pub enum BallotProof {
+    /// the encrypted vote is 0 or 1
+    Binary(Box<VoteProof>),
+    /// the encrypted vote lies in [0, w] for the voter's weight w
+    Weighted(RangeProof),
+}
+
+impl BallotProof {
+    pub fn verify(
+        &self,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        generators: &Generators,
+        weight: u64,
+        encrypted_vote: &RistrettoPoint,
+        y0: &RistrettoPoint,
+    ) -> bool {
+        match self {
+            BallotProof::Binary(proof) => {
+                weight >= 1 && proof.verify(hasher, buf, generators, encrypted_vote, y0)
+            }
+            BallotProof::Weighted(proof) => {
+                proof.verify(hasher, buf, generators, weight, encrypted_vote, y0)
+            }
+        }
+    }
+}
+
 pub struct Vote {
     pub(crate) encrypted_vote: RistrettoPoint,
     pub(crate) proof: VoteProof,
@@ -135,61 +362,169 @@ pub struct CompressedVote {
     pub(crate) proof: CompressedVoteProof,
 }
 
+/// (encrypted_shares, dealer_proof, encrypted_vote, vote_proof)
+pub type Ballot = (
+    Vec<(CompressedRistretto, RistrettoPoint)>,
+    (Scalar, Polynomial),
+    CompressedRistretto,
+    CompressedVoteProof,
+);
+
+/// Identifies the voter behind a ballot. Only the valid ballot with the highest sequence number
+/// per credential is tallied, so a voter can override an earlier (e.g. coerced) ballot.
+#[derive(Clone)]
+pub struct BallotCredential {
+    pub public_key: RistrettoPoint,
+    pub sequence: u64,
+    /// over `ballot_message`
+    pub signature: Signature,
+}
+
+/// "ballot" | session | sequence | encrypted_vote | encrypted_shares
+pub fn ballot_message(
+    session: &[u8; 32],
+    sequence: u64,
+    encrypted_vote: &CompressedRistretto,
+    encrypted_shares: &[CompressedRistretto],
+) -> Vec<u8> {
+    b"ballot"
+        .iter()
+        .chain(session)
+        .chain(&sequence.to_le_bytes())
+        .chain(encrypted_vote.as_bytes())
+        .chain(encrypted_shares.iter().flat_map(|share| share.as_bytes()))
+        .copied()
+        .collect()
+}
+
+/// A ballot prepared from a fresh seed for the Benaloh challenge: the voter either casts it or
+/// audits it, which reveals the seed and spoils the ballot
+pub struct PreparedBallot {
+    seed: Secret<[u8; 32]>,
+    choice: bool,
+    pub ballot: Ballot,
+}
+
+impl PreparedBallot {
+    pub fn cast(self) -> Ballot {
+        self.ballot
+    }
+
+    /// Consumes the ballot, it can never be cast. The voter prepares a fresh one afterwards.
+    pub fn audit(self) -> BallotAudit {
+        let seed = *self.seed.expose();
+        BallotAudit {
+            seed,
+            choice: self.choice,
+            // the first draw of `Voter::vote`
+            s: random_scalar(&mut ChaCha20Rng::from_seed(seed)),
+            ballot: self.ballot,
+        }
+    }
+}
+
+/// What an audited ballot reveals: the seed behind s, the polynomials and the proofs' randomness
+pub struct BallotAudit {
+    pub seed: [u8; 32],
+    pub choice: bool,
+    pub s: Scalar,
+    pub ballot: Ballot,
+}
+
+impl BallotAudit {
+    /// Recomputes the ballot from the revealed seed and choice and checks it matches the one shown
+    /// to the voter
+    pub fn verify(
+        &self,
+        generators: &Arc<Generators>,
+        n: usize,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<bool, Error> {
+        let mut voter = Voter::new(n, t, generators)?;
+        let ballot = voter.vote(
+            &mut ChaCha20Rng::from_seed(self.seed),
+            hasher,
+            buf,
+            self.choice,
+        )?;
+        let encrypted_vote = generators.G_table() * &(self.s + Scalar::from(self.choice as u64));
+
+        Ok(self.ballot.2 == encrypted_vote.compress()
+            && ballot_bytes(&ballot) == ballot_bytes(&self.ballot))
+    }
+}
+
+/// encrypted_shares | d | z | encrypted_vote | vote_proof
+fn ballot_bytes(ballot: &Ballot) -> Vec<u8> {
+    let (encrypted_shares, (d, z), encrypted_vote, vote_proof) = ballot;
+    encrypted_shares
+        .iter()
+        .flat_map(|share| share.0.to_bytes())
+        .chain(d.to_bytes())
+        .chain(z.coefficients().iter().flat_map(|coef| coef.to_bytes()))
+        .chain(encrypted_vote.to_bytes())
+        .chain(vote_proof.to_bytes())
+        .collect()
+}
+
 pub struct Voter {
     pub dealer: Dealer,
-    vote: Option<Scalar>,
+    vote: Option<Secret<Scalar>>,
     encrypted_vote: Option<RistrettoPoint>,
+    credential: Option<SigningKey>,
+    /// of the last signed ballot
+    sequence: u64,
 }
 
 impl Voter {
-    pub fn new(
-        n: usize,
-        t: usize,
-        public_keys: &[CompressedRistretto],
-        pk0: &RistrettoPoint,
-    ) -> Result<Self, Error> {
-        let dealer = Dealer::new(n, t, public_keys, pk0)?;
+    pub fn new(n: usize, t: usize, generators: &Arc<Generators>) -> Result<Self, Error> {
+        let dealer = Dealer::new(n, t, generators)?;
 
         Ok(Voter {
             dealer: dealer,
             vote: None,
             encrypted_vote: None,
+            credential: None,
+            sequence: 0,
         })
     }
-    // Returns (encrypted_shares, dealer_proof, encrypted_vote, vote_proof)
-    pub fn vote<R>(
+
+    pub fn with_credential(mut self, credential: SigningKey) -> Self {
+        self.credential = Some(credential);
+        self
+    }
+    /// Deals a fresh secret s, returns it with (encrypted_shares, dealer_proof, y0)
+    #[allow(clippy::type_complexity)]
+    fn deal_ballot<R>(
         &mut self,
-        G: &RistrettoPoint,
         rng: &mut R,
         hasher: &mut Hasher,
         buf: &mut [u8; 64],
-        choice: bool,
     ) -> Result<
         (
+            Secret<Scalar>,
             Vec<(CompressedRistretto, RistrettoPoint)>,
             (Scalar, Polynomial),
-            CompressedRistretto,
-            CompressedVoteProof,
+            RistrettoPoint,
         ),
         Error,
     >
     where
         R: CryptoRng + RngCore,
     {
-        let s = random_scalar(rng);
-        let (encrypted_shares, (d, z)) = self.dealer.deal_secret(rng, hasher, buf, &s);
+        let s = Secret::new(random_scalar(rng));
+        let (encrypted_shares, (d, z)) = self.dealer.deal_secret(rng, hasher, buf, s.expose());
 
         let decompressed_shares = batch_decompress_ristretto_points(&encrypted_shares).unwrap();
 
         let y0 = encrypted_shares[0].decompress().unwrap();
 
-        // generate vote
-        self.generate_vote(&G, &s, choice);
-
         assert!(
             verify_encrypted_shares_standalone(
                 &(encrypted_shares.clone(), decompressed_shares.clone()),
-                &self.dealer.public_keys,
+                &self.dealer.generators.dealing_tables(),
                 (&d, &z),
                 hasher,
                 buf,
@@ -197,14 +532,33 @@ impl Voter {
             .unwrap()
         );
 
-        //dleq_vote
-        let vote_proof = self.dleq_vote(rng, G, &y0, &s, hasher, buf)?;
-
         let out: Vec<(CompressedRistretto, RistrettoPoint)> = encrypted_shares
             .par_iter()
             .zip(decompressed_shares.par_iter())
             .map(|(a, b)| (*a, *b))
             .collect();
+        Ok((s, out, (d, z), y0))
+    }
+
+    // Returns (encrypted_shares, dealer_proof, encrypted_vote, vote_proof)
+    pub fn vote<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        choice: bool,
+    ) -> Result<Ballot, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let (s, out, (d, z), y0) = self.deal_ballot(rng, hasher, buf)?;
+
+        // generate vote
+        self.generate_vote(s.expose(), choice);
+
+        //dleq_vote
+        let vote_proof = self.dleq_vote(rng, &y0, s.expose(), hasher, buf)?;
+
         Ok((
             out,
             (d, z),
@@ -213,18 +567,267 @@ impl Voter {
         ))
     }
 
-    pub fn generate_vote(&mut self, G: &RistrettoPoint, s: &Scalar, choice: bool) {
-        self.vote = Some(match choice {
-            true => Scalar::ONE,
-            false => Scalar::ZERO,
-        });
-        self.encrypted_vote = Some(G * (s + self.vote.unwrap()));
+    /// Same as `vote`, signed with the voter's credential under the next sequence number
+    pub fn vote_signed<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        choice: bool,
+    ) -> Result<(Ballot, BallotCredential), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if self.credential.is_none() {
+            return Err(UninitializedValue("voter.credential").into());
+        }
+        let ballot = self.vote(rng, hasher, buf, choice)?;
+        let credential = self.sign_ballot(rng, &ballot.0, &ballot.2)?;
+
+        Ok((ballot, credential))
+    }
+
+    /// Signs a ballot with the voter's credential under the next sequence number
+    fn sign_ballot<R>(
+        &mut self,
+        rng: &mut R,
+        encrypted_shares: &[(CompressedRistretto, RistrettoPoint)],
+        encrypted_vote: &CompressedRistretto,
+    ) -> Result<BallotCredential, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if self.credential.is_none() {
+            return Err(UninitializedValue("voter.credential").into());
+        }
+        self.sequence += 1;
+
+        let encrypted_shares: Vec<CompressedRistretto> =
+            encrypted_shares.iter().map(|share| share.0).collect();
+        let message = ballot_message(
+            &self.dealer.generators.session(),
+            self.sequence,
+            encrypted_vote,
+            &encrypted_shares,
+        );
+        // checked above
+        let credential = self.credential.as_ref().unwrap();
+        let signature = credential.sign(rng, self.dealer.generators.G_table(), &message);
+
+        Ok(BallotCredential {
+            public_key: credential.public_key,
+            sequence: self.sequence,
+            signature,
+        })
+    }
+
+    /// Prepares a ballot for the cast-or-audit challenge, see `PreparedBallot`
+    pub fn prepare<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        choice: bool,
+    ) -> Result<PreparedBallot, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut seed = Secret::new([0u8; 32]);
+        rng.fill_bytes(seed.expose_mut());
+        let ballot = self.vote(
+            &mut ChaCha20Rng::from_seed(*seed.expose()),
+            hasher,
+            buf,
+            choice,
+        )?;
+
+        Ok(PreparedBallot {
+            seed,
+            choice,
+            ballot,
+        })
+    }
+
+    /// First move of a ballot cast through a `Rerandomizer`, the voter answers its challenges with
+    /// `PendingBallot::respond`. Requires a credential, the rerandomizer proves its work to it.
+    pub fn commit_ballot<R>(
+        &mut self,
+        rng: &mut R,
+        choice: bool,
+    ) -> Result<(BallotCommitment, PendingBallot), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let public_key = match &self.credential {
+            Some(credential) => credential.public_key,
+            None => return Err(UninitializedValue("voter.credential").into()),
+        };
+        let generators = self.dealer.generators.clone();
+
+        let s = Secret::new(random_scalar(rng));
+        let (f, r) = Polynomial::sample_two_set_f0(self.dealer.t, s.expose(), rng);
+        let (f, r) = (Secret::new(f), Secret::new(r));
+        let (encrypted_shares, share_commitments) = f
+            .expose()
+            .evaluate_multiply_two_ppvss(r.expose(), &generators.dealing_tables());
+        let encrypted_shares = batch_decompress_ristretto_points(&encrypted_shares)?;
+
+        self.generate_vote(s.expose(), choice);
+        let encrypted_vote = *self.encrypted_vote.as_ref().unwrap();
+        let vote_proof = CompressedVoteProof::commit(
+            rng,
+            &generators,
+            &encrypted_vote,
+            &encrypted_shares[0],
+            choice,
+        )?;
+        let VoteProof { a0, a1, b0, b1, .. } = vote_proof.clone().decompress();
+
+        let commitment = BallotCommitment {
+            public_key,
+            encrypted_shares,
+            encrypted_vote,
+            share_commitments: batch_decompress_ristretto_points(&share_commitments)?,
+            vote_commitments: [a0, a1, b0, b1],
+        };
+
+        Ok((
+            commitment.clone(),
+            PendingBallot {
+                commitment,
+                f,
+                r,
+                choice,
+                vote_proof,
+            },
+        ))
+    }
+
+    /// Casts `value` of the voter's `weight` votes, proven to lie in [0, weight], and signs the
+    /// ballot with the voter's credential. The board looks the weight up on its roll by credential.
+    // Returns (ballot, credential), the ballot being
+    // (encrypted_shares, dealer_proof, encrypted_vote, range_proof)
+    #[allow(clippy::type_complexity)]
+    pub fn vote_weighted<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        weight: u64,
+        value: u64,
+    ) -> Result<
+        (
+            (
+                Vec<(CompressedRistretto, RistrettoPoint)>,
+                (Scalar, Polynomial),
+                CompressedRistretto,
+                CompressedRangeProof,
+            ),
+            BallotCredential,
+        ),
+        Error,
+    >
+    where
+        R: CryptoRng + RngCore,
+    {
+        if value > weight {
+            return Err(InvalidWeight(value, weight).into());
+        }
+        if self.credential.is_none() {
+            return Err(UninitializedValue("voter.credential").into());
+        }
+        let (s, out, (d, z), _) = self.deal_ballot(rng, hasher, buf)?;
+
+        self.generate_weighted_vote(s.expose(), value);
+
+        let range_proof = CompressedRangeProof::prove(
+            rng,
+            &self.dealer.generators,
+            weight,
+            value,
+            s.expose(),
+            hasher,
+            buf,
+        )?;
+
+        let ballot = (
+            out,
+            (d, z),
+            self.encrypted_vote.as_ref().unwrap().compress(),
+            range_proof,
+        );
+        let credential = self.sign_ballot(rng, &ballot.0, &ballot.2)?;
+
+        Ok((ballot, credential))
+    }
+
+    /// Casts one binary ballot per candidate, the number of approvals is proven to lie in [min, max]
+    #[allow(clippy::too_many_arguments)]
+    pub fn vote_approval<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        approvals: &[bool],
+        min: u64,
+        max: u64,
+    ) -> Result<ApprovalBallot, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let approved = approvals.iter().filter(|approval| **approval).count() as u64;
+        if approved < min || approved > max {
+            return Err(InvalidSelection(min, max, approvals.len()).into());
+        }
+
+        // the sum of the encrypted votes is encrypted under the sum of the exponents
+        let mut exponent = Secret::new(Scalar::ZERO);
+        let mut candidates = Vec::with_capacity(approvals.len());
+        for approval in approvals {
+            let (s, out, (d, z), y0) = self.deal_ballot(rng, hasher, buf)?;
+
+            self.generate_vote(s.expose(), *approval);
+            let vote_proof = self.dleq_vote(rng, &y0, s.expose(), hasher, buf)?;
+
+            *exponent.expose_mut() += s.expose();
+            candidates.push((
+                out,
+                (d, z),
+                self.encrypted_vote.as_ref().unwrap().compress(),
+                vote_proof,
+            ));
+        }
+
+        let sum_proof = CompressedRangeProof::prove(
+            rng,
+            &self.dealer.generators,
+            max - min,
+            approved - min,
+            exponent.expose(),
+            hasher,
+            buf,
+        )?;
+
+        Ok(ApprovalBallot {
+            candidates,
+            sum_proof,
+        })
+    }
+
+    pub fn generate_vote(&mut self, s: &Scalar, choice: bool) {
+        self.generate_weighted_vote(s, choice as u64);
+    }
+
+    pub fn generate_weighted_vote(&mut self, s: &Scalar, value: u64) {
+        let vote = Secret::new(Scalar::from(value));
+        let exponent = Secret::new(s + vote.expose());
+        self.encrypted_vote = Some(self.dealer.generators.G_table() * exponent.expose());
+        self.vote = Some(vote);
     }
 
     pub fn dleq_vote<R>(
         &self,
         rng: &mut R,
-        G: &RistrettoPoint,
         y0: &RistrettoPoint,
         s: &Scalar,
         hasher: &mut Hasher,
@@ -233,90 +836,17 @@ impl Voter {
     where
         R: CryptoRng + RngCore,
     {
-        match (self.vote, self.encrypted_vote) {
-            (Some(v), Some(u)) => {
-                let pk0 = self.dealer.get_pk0();
-                hasher.update(u.compress().as_bytes());
-                hasher.update(y0.compress().as_bytes());
-
-                let w = random_scalar(rng);
-
-                // if vote {proof.d0 = d0t_d1f; proof.d1 = d0f_d1t} else {proof.d0 = d0f_d1t; proof.d1 = d0t_d1f};
-                let d0t_d1f = random_scalar(rng);
-                let d0f_d1t = -d0t_d1f;
-
-                // if vote {proof.r0 = r0t_r1f} else {proof.r1 = r0t_r1f};
-                let r0t_r1f = random_scalar(rng);
-
-                // proof.a0 = vote ? a0t_a1f : a0f_a1t;
-                // proof.a1 = vote ? a0f_a1t : a0t_a1f;
-                let a0t_a1f = ((r0t_r1f * pk0) + (d0t_d1f * y0)).compress();
-                let a0f_a1t = (w * pk0).compress();
-
-                // if vote {proof.b0 = b0t; proof.b1 = b0f_b1t} else {proof.b0 = b0f_b1t; proof.b1 = b1f};
-                let b0f_b1t = (w * G).compress();
-                let b1f = ((r0t_r1f * G) + ((u - G) * d0t_d1f)).compress();
-                let b0t = ((r0t_r1f * G) + (u * d0t_d1f)).compress();
-
-                Ok(match v == Scalar::ONE {
-                    true => {
-                        let mut proof = CompressedVoteProof {
-                            a0: a0t_a1f,
-                            a1: a0f_a1t,
-                            b0: b0t,
-                            b1: b0f_b1t,
-                            c: Scalar::ZERO,
-                            d0: d0t_d1f,
-                            d1: d0f_d1t,
-                            r0: r0t_r1f,
-                            r1: w,
-                        };
-
-                        hasher.update(proof.a0.as_bytes());
-                        hasher.update(proof.b0.as_bytes());
-                        hasher.update(proof.a1.as_bytes());
-                        hasher.update(proof.b1.as_bytes());
-
-                        hasher.finalize_xof().fill(buf);
-
-                        proof.d1 += Scalar::from_bytes_mod_order_wide(buf);
-                        proof.r1 -= s * proof.d1;
-
-                        buf.zeroize();
-                        hasher.reset();
-
-                        proof
-                    }
-                    false => {
-                        let mut proof = CompressedVoteProof {
-                            a0: a0f_a1t,
-                            a1: a0t_a1f,
-                            b0: b0f_b1t,
-                            b1: b1f,
-                            c: Scalar::ZERO,
-                            d0: d0f_d1t,
-                            d1: d0t_d1f,
-                            r0: w,
-                            r1: r0t_r1f,
-                        };
-
-                        hasher.update(proof.a0.as_bytes());
-                        hasher.update(proof.b0.as_bytes());
-                        hasher.update(proof.a1.as_bytes());
-                        hasher.update(proof.b1.as_bytes());
-
-                        hasher.finalize_xof().fill(buf);
-
-                        proof.d0 += Scalar::from_bytes_mod_order_wide(buf);
-                        proof.r0 -= s * proof.d0;
-
-                        buf.zeroize();
-                        hasher.reset();
-
-                        proof
-                    }
-                })
-            }
+        match (&self.vote, self.encrypted_vote) {
+            (Some(v), Some(u)) => CompressedVoteProof::prove(
+                rng,
+                &self.dealer.generators,
+                &u,
+                y0,
+                s,
+                *v.expose() == Scalar::ONE,
+                hasher,
+                buf,
+            ),
             (None, None) => Err(UninitializedValue("voter.{vote,encrypted_vote}").into()),
             (None, _) => Err(UninitializedValue("voter.vote").into()),
             (_, None) => Err(UninitializedValue("voter.encrypted_vote").into()),
@@ -327,11 +857,10 @@ impl Voter {
         m: usize,
         n: usize,
         t: usize,
-        public_keys: &[CompressedRistretto],
-        pk0: &RistrettoPoint,
+        generators: &Arc<Generators>,
     ) -> Vec<Self> {
         (0..m)
-            .map(|_| Self::new(n, t, public_keys, pk0).unwrap())
+            .map(|_| Self::new(n, t, generators).unwrap())
             .collect()
     }
 }
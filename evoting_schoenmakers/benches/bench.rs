@@ -1,4 +1,6 @@
 use common::{
+    generators::Generators,
+    key_proof::KeyProof,
     random::{random_point, random_scalar},
     utils::precompute_lambda,
 };
@@ -6,6 +8,7 @@ use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
 use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto, scalar::Scalar};
 use evoting_schoenmakers::{bulletin_board::BulletinBoard, tallier::Tallier, voter::Voter};
 use rayon::prelude::*;
+use std::sync::Arc;
 
 const PARAMSET: [(usize, usize, usize); 4] =
     [(128, 9, 4), (128, 17, 8), (256, 256, 127), (512, 512, 255)];
@@ -28,15 +31,26 @@ fn tallying(c: &mut Criterion) {
         let G: RistrettoPoint = random_point(&mut rng);
         let H: RistrettoPoint = random_point(&mut rng);
 
-        let mut talliers = Tallier::generate_talliers(&G, &H, &mut rng, n, t);
+        let generators = Arc::new(Generators::new(&G).with_H(&H));
+        let mut talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
         let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) = talliers
             .iter()
             .map(|tallier| tallier.party.public_key)
             .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys.1, &key_proofs)
+                .unwrap(),
+        );
 
-        let mut voters = Voter::generate_voters(&G, &H, m, n, t, &public_keys.0);
+        let mut voters = Voter::generate_voters(m, n, t, &generators);
 
-        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&G, &H, &public_keys.1, m, n, t);
+        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&generators, m, n, t);
 
         voters.iter_mut().enumerate().for_each(|(i, voter)| {
             let (encrypted_shares, (d, z), dealer_commitments, encrypted_vote, vote_proof) = voter
@@ -72,7 +86,7 @@ fn tallying(c: &mut Criterion) {
         let mut decrypted_share_proofs: Vec<(Scalar, Scalar)> = Vec::with_capacity(n);
 
         {
-            let mut tallier0 = talliers[0].clone();
+            let tallier0 = &mut talliers[0];
 
             let public_keys: Vec<CompressedRistretto> = public_keys
                 .0
@@ -192,15 +206,26 @@ fn ballot_verification(c: &mut Criterion) {
         let G: RistrettoPoint = random_point(&mut rng);
         let H: RistrettoPoint = random_point(&mut rng);
 
-        let talliers = Tallier::generate_talliers(&G, &H, &mut rng, n, t);
+        let generators = Arc::new(Generators::new(&G).with_H(&H));
+        let talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
         let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) = talliers
             .iter()
             .map(|tallier| tallier.party.public_key)
             .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys.1, &key_proofs)
+                .unwrap(),
+        );
 
-        let mut voter = Voter::new(&G, &H, n, t, &public_keys.0).unwrap();
+        let mut voter = Voter::new(n, t, &generators).unwrap();
 
-        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&G, &H, &public_keys.1, 1, n, t);
+        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&generators, 1, n, t);
 
         let (encrypted_shares, (d, z), dealer_commitments, encrypted_vote, vote_proof) =
             voter.vote(&mut rng, &mut hasher, &mut buf, false).unwrap();
@@ -240,13 +265,24 @@ fn cast_ballot(c: &mut Criterion) {
         let G: RistrettoPoint = random_point(&mut rng);
         let H: RistrettoPoint = random_point(&mut rng);
 
-        let talliers = Tallier::generate_talliers(&G, &H, &mut rng, n, t);
+        let generators = Arc::new(Generators::new(&G).with_H(&H));
+        let talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
         let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) = talliers
             .iter()
             .map(|tallier| tallier.party.public_key)
             .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys.1, &key_proofs)
+                .unwrap(),
+        );
 
-        let mut voter = Voter::new(&G, &H, n, t, &public_keys.0).unwrap();
+        let mut voter = Voter::new(n, t, &generators).unwrap();
 
         c.bench_function(
             &format!("(n: {}, t: {}) | Sch | Voter: Cast Vote", n, t),
@@ -273,6 +309,77 @@ fn cast_ballot(c: &mut Criterion) {
     }
 }
 
+fn summing(c: &mut Criterion) {
+    for (m, n, t) in PARAMSET {
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf: [u8; 64] = [0u8; 64];
+
+        let G: RistrettoPoint = random_point(&mut rng);
+        let H: RistrettoPoint = random_point(&mut rng);
+
+        let generators = Arc::new(Generators::new(&G).with_H(&H));
+        let talliers = Tallier::generate_talliers(&generators, &mut rng, n, t);
+        let public_keys: (Vec<CompressedRistretto>, Vec<RistrettoPoint>) = talliers
+            .iter()
+            .map(|tallier| tallier.party.public_key)
+            .collect();
+        let key_proofs: Vec<KeyProof> = talliers
+            .iter()
+            .map(|tallier| tallier.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys.1, &key_proofs)
+                .unwrap(),
+        );
+
+        // the sum does not depend on the ballot contents, so one ballot is posted m times
+        let (encrypted_shares, (d, z), dealer_commitments, encrypted_vote, vote_proof) =
+            Voter::new(n, t, &generators)
+                .unwrap()
+                .vote(&mut rng, &mut hasher, &mut buf, true)
+                .unwrap();
+        let encrypted_shares: Vec<RistrettoPoint> = encrypted_shares
+            .iter()
+            .map(|enc_share| enc_share.1)
+            .collect();
+        let encrypted_vote = encrypted_vote.decompress().unwrap();
+
+        let mut bulletin_board: BulletinBoard = BulletinBoard::new(&generators, m, n, t);
+        for _ in 0..m {
+            bulletin_board.ingest_vote(
+                encrypted_shares.clone(),
+                (d, z.clone()),
+                encrypted_vote,
+                dealer_commitments.clone(),
+                vote_proof.clone().decompress(),
+            );
+        }
+        bulletin_board
+            .encrypted_shares
+            .iter_mut()
+            .for_each(|(status, _)| *status = true);
+
+        c.bench_function(
+            &format!(
+                "(m: {}, n: {}, t: {}) | Sch | Bulletin Board: Sum Encrypted Shares",
+                m, n, t
+            ),
+            |b| b.iter(|| bulletin_board.sum_encrypted_shares()),
+        );
+
+        c.bench_function(
+            &format!(
+                "(m: {}, n: {}, t: {}) | Sch | Bulletin Board: Sum and Compress Encrypted Shares",
+                m, n, t
+            ),
+            |b| b.iter(|| bulletin_board.sum_encrypted_shares_compressed()),
+        );
+    }
+}
+
 // criterion_group!(benches, cast_ballot, ballot_verification, tallying);
-criterion_group!(benches, cast_ballot, ballot_verification);
+criterion_group!(benches, cast_ballot, ballot_verification, summing);
 criterion_main!(benches);
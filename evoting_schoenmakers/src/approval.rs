@@ -0,0 +1,176 @@
+use blake3::Hasher;
+use common::{
+    error::{
+        Error,
+        ErrorKind::{CountMismatch, InvalidSelection},
+    },
+    generators::Generators,
+    utils::batch_decompress_ristretto_points,
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto, traits::Identity};
+use rayon::prelude::*;
+use std::sync::Arc;
+
+use crate::{
+    bulletin_board::BulletinBoard,
+    voter::{Ballot, CompressedRangeProof, RangeProof},
+};
+
+/// A ballot over c candidates: one binary ballot per candidate and a range proof that the
+/// number of approvals lies in [min, max]
+pub struct ApprovalBallot {
+    pub candidates: Vec<Ballot>,
+    pub sum_proof: CompressedRangeProof,
+}
+
+#[derive(Clone)]
+pub struct ApprovalBoard {
+    /// G, H and the talliers' public keys
+    pub generators: Arc<Generators>,
+    pub min: u64,
+    pub max: u64,
+    /// a single-choice board per candidate, ballot i sits at position i of each
+    pub candidates: Vec<BulletinBoard>,
+    pub sum_proofs: Vec<RangeProof>,
+}
+
+impl ApprovalBoard {
+    pub fn new(
+        generators: &Arc<Generators>,
+        m: usize,
+        n: usize,
+        t: usize,
+        c: usize,
+        min: u64,
+        max: u64,
+    ) -> Result<Self, Error> {
+        if min > max || max > c as u64 {
+            return Err(InvalidSelection(min, max, c).into());
+        }
+        Ok(Self {
+            generators: generators.clone(),
+            min,
+            max,
+            candidates: (0..c)
+                .map(|_| BulletinBoard::new(generators, m, n, t))
+                .collect(),
+            sum_proofs: Vec::with_capacity(m),
+        })
+    }
+
+    pub fn ingest_ballot(&mut self, ballot: ApprovalBallot) -> Result<(), Error> {
+        if ballot.candidates.len() != self.candidates.len() {
+            return Err(CountMismatch(
+                self.candidates.len(),
+                "candidates",
+                ballot.candidates.len(),
+                "candidate ballots",
+            )
+            .into());
+        }
+        let sum_proof = ballot.sum_proof.decompress()?;
+        let encrypted_votes = batch_decompress_ristretto_points(
+            &ballot
+                .candidates
+                .iter()
+                .map(|(_, _, _, encrypted_vote, _)| *encrypted_vote)
+                .collect::<Vec<CompressedRistretto>>(),
+        )?;
+
+        self.candidates
+            .iter_mut()
+            .zip(ballot.candidates.into_iter().zip(encrypted_votes))
+            .for_each(
+                |(
+                    board,
+                    (
+                        (encrypted_shares, dealer_proof, dealer_commitments, _, vote_proof),
+                        encrypted_vote,
+                    ),
+                )| {
+                    board.ingest_vote(
+                        encrypted_shares.into_iter().map(|share| share.1).collect(),
+                        dealer_proof,
+                        encrypted_vote,
+                        dealer_commitments,
+                        vote_proof.decompress(),
+                    )
+                },
+            );
+        self.sum_proofs.push(sum_proof);
+        Ok(())
+    }
+
+    /// A ballot counts, for every candidate, only if all its dealings, all its 0/1 proofs and
+    /// its sum proof hold
+    pub fn verify_ballots(&mut self) {
+        self.candidates.par_iter_mut().for_each(|board| {
+            board.verify_encrypted_shares();
+            board.verify_votes();
+        });
+
+        let G_min = self.generators.G_table() * &Scalar::from(self.min);
+        let valid: Vec<bool> = self
+            .sum_proofs
+            .par_iter()
+            .enumerate()
+            .map_init(
+                || (Hasher::new(), [0u8; 64]),
+                |(hasher, buf), (i, sum_proof)| {
+                    let parts_hold = self
+                        .candidates
+                        .iter()
+                        .all(|board| board.encrypted_shares[i].0 && board.encrypted_votes[i].0);
+                    if !parts_hold {
+                        return false;
+                    }
+                    let (c0, u) = self.candidates.iter().fold(
+                        (RistrettoPoint::identity(), RistrettoPoint::identity()),
+                        |(c0, u), board| {
+                            (
+                                c0 + board.dealer_commitments[i][0],
+                                u + board.encrypted_votes[i].1,
+                            )
+                        },
+                    );
+
+                    sum_proof.verify(
+                        hasher,
+                        buf,
+                        &self.generators,
+                        self.max - self.min,
+                        &(u - G_min),
+                        &c0,
+                    )
+                },
+            )
+            .collect();
+
+        for board in &mut self.candidates {
+            board
+                .encrypted_shares
+                .iter_mut()
+                .zip(board.encrypted_votes.iter_mut())
+                .zip(&valid)
+                .for_each(|((shares, vote), valid)| {
+                    shares.0 = *valid;
+                    vote.0 = *valid;
+                });
+        }
+    }
+
+    /// The encrypted tally of each candidate, each is decrypted like a single-choice election
+    /// over the candidate's board
+    pub fn tally_encrypted_votes(&self) -> Vec<RistrettoPoint> {
+        self.candidates
+            .par_iter()
+            .map(|board| board.tally_encrypted_votes())
+            .collect()
+    }
+
+    pub fn count_valid_ballots(&self) -> usize {
+        self.candidates
+            .first()
+            .map_or(0, |board| board.count_valid_votes())
+    }
+}
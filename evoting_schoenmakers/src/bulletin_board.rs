@@ -1,14 +1,15 @@
-use crate::voter::VoteProof;
+use crate::voter::{BallotCredential, BallotProof, RangeProof, VoteProof, ballot_message};
 use blake3::Hasher;
-use curve25519_dalek::{RistrettoPoint, Scalar, traits::Identity};
+use common::generators::Generators;
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto, traits::Identity};
 use rayon::prelude::*;
 use schoenmakers::utils::verify_encrypted_shares_standalone;
+use std::{collections::HashMap, sync::Arc};
 
 #[derive(Clone)]
 pub struct BulletinBoard {
-    pub G: RistrettoPoint,
-    pub H: RistrettoPoint,
-    pub public_keys: Vec<RistrettoPoint>,
+    /// G, H and the talliers' public keys
+    pub generators: Arc<Generators>,
     pub n: usize,
     pub t: usize,
 
@@ -17,29 +18,60 @@ pub struct BulletinBoard {
     pub encrypted_share_proofs: Vec<(Scalar, Vec<Scalar>)>,
     pub encrypted_votes: Vec<(bool, RistrettoPoint)>,
     pub dealer_commitments: Vec<Vec<RistrettoPoint>>,
-    pub vote_proofs: Vec<VoteProof>,
+    pub vote_proofs: Vec<BallotProof>,
+    /// set for ballots cast with `ingest_signed_vote`
+    pub credentials: Vec<Option<BallotCredential>>,
+    /// voter roll, the weight of each credential. In signed mode only ballots signed by a
+    /// credential on the roll are valid, each checked against that credential's weight.
+    /// Without it every voter weighs 1.
+    pub roll: Option<HashMap<CompressedRistretto, u64>>,
 }
 
 impl BulletinBoard {
-    pub fn new(
-        G: &RistrettoPoint,
-        H: &RistrettoPoint,
-        public_keys: &Vec<RistrettoPoint>,
-        m: usize,
-        n: usize,
-        t: usize,
-    ) -> Self {
+    pub fn new(generators: &Arc<Generators>, m: usize, n: usize, t: usize) -> Self {
         Self {
-            G: G.clone(),
-            H: H.clone(),
-            public_keys: public_keys.clone(),
+            generators: generators.clone(),
             encrypted_shares: Vec::with_capacity(m),
             encrypted_share_proofs: Vec::with_capacity(m),
             encrypted_votes: Vec::with_capacity(m),
             dealer_commitments: Vec::with_capacity(m),
             vote_proofs: Vec::with_capacity(m),
+            credentials: Vec::with_capacity(m),
             n: n,
             t: t,
+            roll: None,
+        }
+    }
+
+    /// Signed mode with weights: unsigned ballots and ballots signed by a credential off the roll
+    /// are invalid, the others are checked against their credential's weight
+    pub fn with_voter_roll(mut self, roll: &[(RistrettoPoint, u64)]) -> Self {
+        self.roll = Some(
+            roll.iter()
+                .map(|(public_key, weight)| (public_key.compress(), *weight))
+                .collect(),
+        );
+        self
+    }
+
+    /// Signed mode with every credential on the roll weighing 1
+    pub fn with_credential_roll(self, public_keys: &[RistrettoPoint]) -> Self {
+        let roll: Vec<(RistrettoPoint, u64)> = public_keys
+            .iter()
+            .map(|public_key| (*public_key, 1))
+            .collect();
+        self.with_voter_roll(&roll)
+    }
+
+    /// The weight of the credential that signed ballot i, if it is on the roll
+    pub fn weight(&self, i: usize) -> Option<u64> {
+        match &self.roll {
+            Some(roll) => self
+                .credentials
+                .get(i)?
+                .as_ref()
+                .and_then(|credential| roll.get(&credential.public_key.compress()).copied()),
+            None => Some(1),
         }
     }
 
@@ -55,38 +87,167 @@ impl BulletinBoard {
         self.encrypted_share_proofs.push(encrypted_share_proof);
         self.encrypted_votes.push((false, encrypted_vote));
         self.dealer_commitments.push(dealer_commitments);
-        self.vote_proofs.push(vote_proof);
+        self.vote_proofs
+            .push(BallotProof::Binary(Box::new(vote_proof)));
+        self.credentials.push(None);
+    }
+
+    /// A ballot from an identified voter, only their latest valid ballot is tallied
+    pub fn ingest_signed_vote(
+        &mut self,
+        encrypted_shares: Vec<RistrettoPoint>,
+        encrypted_share_proof: (Scalar, Vec<Scalar>),
+        encrypted_vote: RistrettoPoint,
+        dealer_commitments: Vec<RistrettoPoint>,
+        vote_proof: VoteProof,
+        credential: BallotCredential,
+    ) {
+        self.ingest_vote(
+            encrypted_shares,
+            encrypted_share_proof,
+            encrypted_vote,
+            dealer_commitments,
+            vote_proof,
+        );
+        *self.credentials.last_mut().unwrap() = Some(credential);
+    }
+
+    /// A weighted ballot, checked against the weight its credential has on the roll
+    pub fn ingest_weighted_vote(
+        &mut self,
+        encrypted_shares: Vec<RistrettoPoint>,
+        encrypted_share_proof: (Scalar, Vec<Scalar>),
+        encrypted_vote: RistrettoPoint,
+        dealer_commitments: Vec<RistrettoPoint>,
+        range_proof: RangeProof,
+        credential: BallotCredential,
+    ) {
+        self.encrypted_shares.push((false, encrypted_shares));
+        self.encrypted_share_proofs.push(encrypted_share_proof);
+        self.encrypted_votes.push((false, encrypted_vote));
+        self.dealer_commitments.push(dealer_commitments);
+        self.vote_proofs.push(BallotProof::Weighted(range_proof));
+        self.credentials.push(Some(credential));
     }
 
     pub fn verify_votes(&mut self) {
+        let weights: Vec<Option<u64>> = (0..self.encrypted_votes.len())
+            .map(|i| self.weight(i))
+            .collect();
         self.vote_proofs
             .par_iter()
             .zip(self.encrypted_votes.par_iter_mut())
             .zip(self.dealer_commitments.par_iter())
+            .zip(weights.par_iter())
             .for_each_init(
                 || (Hasher::new(), [0u8; 64]),
-                |(hasher, buf), ((proof, encrypted_vote), dealer_commitments)| {
-                    (*encrypted_vote).0 = proof.verify(
-                        hasher,
-                        buf,
-                        &self.G,
-                        &self.H,
-                        &encrypted_vote.1,
-                        &dealer_commitments[0],
-                    );
+                |(hasher, buf), (((proof, encrypted_vote), dealer_commitments), weight)| {
+                    (*encrypted_vote).0 = match *weight {
+                        Some(weight) => proof.verify(
+                            hasher,
+                            buf,
+                            &self.generators,
+                            weight,
+                            &encrypted_vote.1,
+                            &dealer_commitments[0],
+                        ),
+                        None => false,
+                    };
                 },
-            )
+            );
+
+        let session = self.generators.session();
+        let signatures_hold: Vec<bool> = self
+            .credentials
+            .par_iter()
+            .zip(self.encrypted_shares.par_iter())
+            .zip(self.encrypted_votes.par_iter())
+            .map(|((credential, (_, enc_shares)), (_, encrypted_vote))| {
+                let Some(credential) = credential else {
+                    return self.roll.is_none();
+                };
+                let on_roll = self
+                    .roll
+                    .as_ref()
+                    .is_none_or(|roll| roll.contains_key(&credential.public_key.compress()));
+                let enc_shares: Vec<CompressedRistretto> =
+                    enc_shares.iter().map(|share| share.compress()).collect();
+                on_roll
+                    && credential.signature.verify(
+                        self.generators.G_table(),
+                        &credential.public_key,
+                        &ballot_message(
+                            &session,
+                            credential.sequence,
+                            &encrypted_vote.compress(),
+                            &enc_shares,
+                        ),
+                    )
+            })
+            .collect();
+        self.encrypted_votes
+            .iter_mut()
+            .zip(signatures_hold)
+            .for_each(|(encrypted_vote, signature_holds)| encrypted_vote.0 &= signature_holds);
+    }
+
+    /// Marks every ballot of an identified voter except their valid ballot with the highest
+    /// sequence number (the later one on a tie). Superseded ballots stay on the board and
+    /// verifiable, they are just left out of the tally. Unsigned ballots are only valid outside
+    /// signed mode (`with_voter_roll`), where they are never superseded.
+    pub fn superseded(&self) -> Vec<bool> {
+        let mut latest: HashMap<CompressedRistretto, (u64, usize)> = HashMap::new();
+        self.credentials
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.encrypted_shares[*i].0 && self.encrypted_votes[*i].0)
+            .for_each(|(i, credential)| {
+                if let Some(credential) = credential {
+                    let latest = latest
+                        .entry(credential.public_key.compress())
+                        .or_insert((credential.sequence, i));
+                    if credential.sequence >= latest.0 {
+                        *latest = (credential.sequence, i);
+                    }
+                }
+            });
+
+        self.credentials
+            .iter()
+            .enumerate()
+            .map(|(i, credential)| match credential {
+                Some(credential) => latest
+                    .get(&credential.public_key.compress())
+                    .is_none_or(|(_, latest)| *latest != i),
+                None => false,
+            })
+            .collect()
+    }
+
+    /// Ballots whose shares and vote both hold and that are not superseded
+    fn counted(&self) -> Vec<bool> {
+        self.encrypted_shares
+            .par_iter()
+            .zip(self.encrypted_votes.par_iter())
+            .zip(self.superseded())
+            .map(|(((shares_hold, _), (vote_holds, _)), superseded)| {
+                *shares_hold && *vote_holds && !superseded
+            })
+            .collect()
     }
 
     pub fn tally_encrypted_votes(&self) -> RistrettoPoint {
         self.encrypted_votes
             .par_iter()
-            .filter(|enc_vote| enc_vote.0 == true)
-            .map(|enc_vote| enc_vote.1)
+            .zip(self.counted())
+            .filter(|(_, counted)| *counted)
+            .map(|(enc_vote, _)| enc_vote.1)
             .sum()
     }
 
     pub fn verify_encrypted_shares(&mut self) {
+        let H = self.generators.H_table().unwrap();
+        let public_keys = self.generators.public_key_tables();
         self.encrypted_shares
             .par_iter_mut()
             .zip(self.encrypted_share_proofs.par_iter())
@@ -100,42 +261,71 @@ impl BulletinBoard {
                         .collect();
 
                     *status = verify_encrypted_shares_standalone(
-                        &self.H,
+                        H,
                         hasher,
                         buf,
                         &(compressed_shares, enc_shares.to_owned()),
-                        &self.public_keys,
+                        public_keys,
                         dealer_commitments,
                         (d, z),
                         self.n,
                         self.t,
                     )
-                    .unwrap();
+                    .unwrap_or(false);
                 },
             );
     }
 
+    /// Folds the valid voters' shares in parallel and reduces the partial sums pairwise
     pub fn sum_encrypted_shares(&self) -> Vec<RistrettoPoint> {
-        let mut output = vec![RistrettoPoint::identity(); self.n];
+        self.encrypted_shares
+            .par_iter()
+            .zip(self.counted())
+            .filter(|(_, counted)| *counted)
+            .map(|((_, enc_shares), _)| enc_shares)
+            .fold(
+                || vec![RistrettoPoint::identity(); self.n],
+                |mut output, enc_shares| {
+                    output
+                        .iter_mut()
+                        .zip(enc_shares)
+                        .for_each(|(output_slot, enc_share)| *output_slot += enc_share);
+                    output
+                },
+            )
+            .reduce(
+                || vec![RistrettoPoint::identity(); self.n],
+                |mut lhs, rhs| {
+                    lhs.iter_mut()
+                        .zip(rhs)
+                        .for_each(|(lhs_slot, rhs_slot)| *lhs_slot += rhs_slot);
+                    lhs
+                },
+            )
+    }
 
-        for enc_shares in self
-            .encrypted_shares
-            .iter()
-            .filter(|(status, _)| *status == true)
-            .map(|(_, enc_shares)| enc_shares)
-        {
-            enc_shares
-                .into_par_iter()
-                .zip(output.par_iter_mut())
-                .for_each(|(enc_share, output_slot)| *output_slot += enc_share);
-        }
-        output
+    /// Same as `sum_encrypted_shares`, the sums stay in extended coordinates and are compressed once
+    pub fn sum_encrypted_shares_compressed(&self) -> Vec<CompressedRistretto> {
+        self.sum_encrypted_shares()
+            .par_iter()
+            .map(|share| share.compress())
+            .collect()
     }
 
     pub fn count_valid_votes(&self) -> usize {
-        self.encrypted_votes
-            .par_iter()
-            .filter(|(status, _)| *status == true)
+        self.counted()
+            .into_par_iter()
+            .filter(|counted| *counted)
             .count()
     }
+
+    /// The largest count the valid ballots can add up to
+    pub fn max_tally(&self) -> u64 {
+        self.counted()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, counted)| *counted)
+            .map(|(i, _)| self.weight(i).unwrap_or(0))
+            .sum()
+    }
 }
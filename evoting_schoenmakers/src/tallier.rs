@@ -1,14 +1,14 @@
 use common::{
-    error::{Error, ErrorKind::InvalidPararmeterSet},
-    random::random_scalar,
+    error::Error,
+    generators::Generators,
+    keystore::{KdfParams, read_keystore, write_keystore},
 };
-use curve25519_dalek::RistrettoPoint;
 use rand::{CryptoRng, RngCore};
 use schoenmakers::party::Party;
+use std::{path::Path, sync::Arc};
 
 use crate::bulletin_board::BulletinBoard;
 
-#[derive(Clone)]
 pub struct Tallier {
     pub party: Party,
     pub bulletin_board: Option<BulletinBoard>,
@@ -16,8 +16,7 @@ pub struct Tallier {
 
 impl Tallier {
     pub fn new<R>(
-        G: &RistrettoPoint,
-        H: &RistrettoPoint,
+        generators: &Arc<Generators>,
         rng: &mut R,
         n: usize,
         t: usize,
@@ -26,40 +25,46 @@ impl Tallier {
     where
         R: CryptoRng + RngCore,
     {
-        let private_key = random_scalar(rng);
-        let public_key = G * &private_key;
+        Ok(Self {
+            party: Party::new(generators, rng, n, t, index)?,
+            bulletin_board: None,
+        })
+    }
+
+    /// Loads a tallier whose keypair was registered ahead of the election
+    pub fn from_keystore<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        path: &Path,
+        password: &[u8],
+        n: usize,
+        t: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let keypair = read_keystore(path, password, generators.G_table())?;
+        Ok(Self {
+            party: Party::from_keypair(generators, rng, &keypair, n, t)?,
+            bulletin_board: None,
+        })
+    }
 
-        if index <= n && t < n && t as f32 == ((n - 1) as f32 / 2.0).floor() {
-            Ok(Self {
-                party: Party {
-                    G: *G,
-                    H: *H,
-                    private_key,
-                    public_key: (public_key.compress(), public_key),
-                    index,
-                    n,
-                    t,
-                    dealer_proof: None,
-                    encrypted_share: None,
-                    decrypted_share: None,
-                    share_proof: None,
-                    share_proofs: None,
-                    encrypted_shares: None,
-                    decrypted_shares: None,
-                    dealer_commitments: None,
-                    public_keys: None,
-                    validated_shares: vec![],
-                },
-                bulletin_board: None,
-            })
-        } else {
-            Err(InvalidPararmeterSet(n, t as isize, index).into())
-        }
+    pub fn write_keystore<R>(
+        &self,
+        path: &Path,
+        password: &[u8],
+        params: &KdfParams,
+        rng: &mut R,
+    ) -> Result<(), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        write_keystore(path, &self.party.keypair(), password, params, rng)
     }
 
     pub fn generate_talliers<R>(
-        G: &RistrettoPoint,
-        H: &RistrettoPoint,
+        generators: &Arc<Generators>,
         rng: &mut R,
         n: usize,
         t: usize,
@@ -68,10 +73,7 @@ impl Tallier {
         R: CryptoRng + RngCore,
     {
         (1..=n)
-            .map(|i| Self {
-                party: Party::new(*G, *H, rng, n, t, i).unwrap(),
-                bulletin_board: None,
-            })
+            .map(|i| Self::new(generators, rng, n, t, i).unwrap())
             .collect()
     }
 }
@@ -0,0 +1,130 @@
+use crate::voter::VoteProof;
+use blake3::Hasher;
+use common::generators::Generators;
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto, traits::Identity};
+use rayon::prelude::*;
+use schoenmakers::utils::verify_encrypted_shares_standalone;
+use std::sync::Arc;
+
+/// Verifies ballots as they arrive and keeps only running sums, so memory is O(n) instead of O(m * n).
+/// The accepted ballots themselves are committed to in a running blake3 digest for later audit.
+#[derive(Clone)]
+pub struct TallyAccumulator {
+    /// G, H and the talliers' public keys
+    pub generators: Arc<Generators>,
+    pub n: usize,
+    pub t: usize,
+    encrypted_share_sums: Vec<RistrettoPoint>,
+    encrypted_vote_sum: RistrettoPoint,
+    valid_votes: usize,
+    rejected_votes: usize,
+    digest: Hasher,
+}
+
+impl TallyAccumulator {
+    pub fn new(generators: &Arc<Generators>, n: usize, t: usize) -> Self {
+        Self {
+            generators: generators.clone(),
+            n,
+            t,
+            encrypted_share_sums: vec![RistrettoPoint::identity(); n],
+            encrypted_vote_sum: RistrettoPoint::identity(),
+            valid_votes: 0,
+            rejected_votes: 0,
+            digest: Hasher::new(),
+        }
+    }
+
+    /// Verifies the dealing and the vote proof of a ballot and folds it into the tally if both hold.
+    /// A rejected ballot only bumps the rejection count, leaving the sums and the digest as they were.
+    #[allow(clippy::too_many_arguments)]
+    pub fn ingest_vote(
+        &mut self,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        encrypted_shares: Vec<RistrettoPoint>,
+        encrypted_share_proof: (Scalar, Vec<Scalar>),
+        encrypted_vote: RistrettoPoint,
+        dealer_commitments: Vec<RistrettoPoint>,
+        vote_proof: VoteProof,
+    ) -> bool {
+        let compressed_shares: Vec<CompressedRistretto> = encrypted_shares
+            .par_iter()
+            .map(|share| share.compress())
+            .collect();
+        let encrypted_shares = (compressed_shares, encrypted_shares);
+        let (d, z) = &encrypted_share_proof;
+        let compressed_commitments: Vec<CompressedRistretto> = dealer_commitments
+            .par_iter()
+            .map(|commitment| commitment.compress())
+            .collect();
+
+        let valid = encrypted_shares.1.len() == self.n
+            && z.len() == self.n
+            && dealer_commitments.len() == self.t + 1
+            && verify_encrypted_shares_standalone(
+                self.generators.H_table().unwrap(),
+                hasher,
+                buf,
+                &encrypted_shares,
+                self.generators.public_key_tables(),
+                &dealer_commitments,
+                (d, z),
+                self.n,
+                self.t,
+            )
+            .unwrap_or(false)
+            && vote_proof.verify(
+                hasher,
+                buf,
+                &self.generators,
+                &encrypted_vote,
+                &dealer_commitments[0],
+            );
+
+        if valid {
+            encrypted_shares
+                .0
+                .iter()
+                .chain(compressed_commitments.iter())
+                .for_each(|point| _ = self.digest.update(point.as_bytes()));
+            self.digest.update(d.as_bytes());
+            z.iter()
+                .for_each(|response| _ = self.digest.update(response.as_bytes()));
+            self.digest.update(encrypted_vote.compress().as_bytes());
+            self.digest.update(&vote_proof.compress().to_bytes());
+
+            encrypted_shares
+                .1
+                .par_iter()
+                .zip(self.encrypted_share_sums.par_iter_mut())
+                .for_each(|(enc_share, sum)| *sum += enc_share);
+            self.encrypted_vote_sum += encrypted_vote;
+            self.valid_votes += 1;
+        } else {
+            self.rejected_votes += 1;
+        }
+        valid
+    }
+
+    pub fn sum_encrypted_shares(&self) -> Vec<RistrettoPoint> {
+        self.encrypted_share_sums.clone()
+    }
+
+    pub fn tally_encrypted_votes(&self) -> RistrettoPoint {
+        self.encrypted_vote_sum
+    }
+
+    pub fn count_valid_votes(&self) -> usize {
+        self.valid_votes
+    }
+
+    pub fn count_rejected_votes(&self) -> usize {
+        self.rejected_votes
+    }
+
+    /// blake3 over every accepted ballot, in ingestion order
+    pub fn digest(&self) -> [u8; 32] {
+        *self.digest.finalize().as_bytes()
+    }
+}
@@ -1,17 +1,31 @@
 use blake3::Hasher;
-use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use curve25519_dalek::{
+    RistrettoPoint, Scalar,
+    ristretto::{CompressedRistretto, RistrettoBasepointTable},
+};
 
 use common::{
-    error::{Error, ErrorKind::UninitializedValue},
+    error::{
+        Error,
+        ErrorKind::{InvalidProof, InvalidSelection, InvalidWeight, UninitializedValue},
+    },
+    generators::Generators,
     random::random_scalar,
+    range_proof::{self, BitProof},
+    secret::Secret,
+    signature::{Signature, SigningKey},
     utils::batch_decompress_ristretto_points,
 };
-use rand::{CryptoRng, RngCore};
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use zeroize::Zeroize;
 
+use crate::approval::ApprovalBallot;
 use rayon::prelude::*;
 use schoenmakers::{dealer::Dealer, utils::verify_encrypted_shares_standalone};
+use std::sync::Arc;
 
+#[derive(Clone)]
 pub struct CompressedVoteProof {
     pub(crate) a0: CompressedRistretto,
     pub(crate) a1: CompressedRistretto,
@@ -45,6 +59,142 @@ impl CompressedVoteProof {
             r1,
         }
     }
+    /// Disjunctive proof that `u` opens to 0 or 1 with the same exponent as `c0`
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn prove<R>(
+        rng: &mut R,
+        generators: &Generators,
+        u: &RistrettoPoint,
+        c0: &RistrettoPoint,
+        s: &Scalar,
+        bit: bool,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let H = generators.H_table()?;
+        let (G, G_table) = (generators.G(), generators.G_table());
+        hasher.update(u.compress().as_bytes());
+        hasher.update(c0.compress().as_bytes());
+
+        let w = random_scalar(rng);
+
+        // if vote {proof.d0 = d0t_d1f; proof.d1 = d0f_d1t} else {proof.d0 = d0f_d1t; proof.d1 = d0t_d1f};
+        let d0t_d1f = random_scalar(rng);
+        let d0f_d1t = -d0t_d1f;
+
+        // if vote {proof.r0 = r0t_r1f} else {proof.r1 = r0t_r1f};
+        let r0t_r1f = random_scalar(rng);
+
+        // proof.a0 = vote ? a0t_a1f : a0f_a1t;
+        // proof.a1 = vote ? a0f_a1t : a0t_a1f;
+        let a0t_a1f = ((&r0t_r1f * H) + (d0t_d1f * c0)).compress();
+        let a0f_a1t = (&w * H).compress();
+
+        // if vote {proof.b0 = b0t; proof.b1 = b0f_b1t} else {proof.b0 = b0f_b1t; proof.b1 = b1f};
+        let b0f_b1t = (&w * G_table).compress();
+
+        let b1f = ((&r0t_r1f * G_table) + ((u - G) * d0t_d1f)).compress();
+        let b0t = ((&r0t_r1f * G_table) + (u * d0t_d1f)).compress();
+
+        Ok(match bit {
+            true => {
+                let mut proof = CompressedVoteProof {
+                    a0: a0t_a1f,
+                    a1: a0f_a1t,
+                    b0: b0t,
+                    b1: b0f_b1t,
+                    d0: d0t_d1f,
+                    d1: d0f_d1t,
+                    r0: r0t_r1f,
+                    r1: w,
+                };
+
+                hasher.update(proof.a0.as_bytes());
+                hasher.update(proof.b0.as_bytes());
+                hasher.update(proof.a1.as_bytes());
+                hasher.update(proof.b1.as_bytes());
+
+                hasher.finalize_xof().fill(buf);
+
+                proof.d1 += Scalar::from_bytes_mod_order_wide(buf);
+                proof.r1 -= s * proof.d1;
+
+                hasher.reset();
+                buf.zeroize();
+
+                proof
+            }
+            false => {
+                let mut proof = CompressedVoteProof {
+                    a0: a0f_a1t,
+                    a1: a0t_a1f,
+                    b0: b0f_b1t,
+                    b1: b1f,
+                    d0: d0f_d1t,
+                    d1: d0t_d1f,
+                    r0: w,
+                    r1: r0t_r1f,
+                };
+
+                hasher.update(proof.a0.as_bytes());
+                hasher.update(proof.b0.as_bytes());
+                hasher.update(proof.a1.as_bytes());
+                hasher.update(proof.b1.as_bytes());
+
+                hasher.finalize_xof().fill(buf);
+
+                proof.d0 += Scalar::from_bytes_mod_order_wide(buf);
+                proof.r0 -= s * proof.d0;
+
+                hasher.reset();
+                buf.zeroize();
+
+                proof
+            }
+        })
+    }
+
+    /// a0 | a1 | b0 | b1 | d0 | d1 | r0 | r1
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.a0, self.a1, self.b0, self.b1]
+            .iter()
+            .flat_map(|point| point.to_bytes())
+            .chain(
+                [self.d0, self.d1, self.r0, self.r1]
+                    .iter()
+                    .flat_map(|scalar| scalar.to_bytes()),
+            )
+            .collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != 8 * 32 {
+            return Err(InvalidProof(format!("vote proof len: {}", bytes.len())).into());
+        }
+        let chunks: Vec<[u8; 32]> = bytes
+            .chunks_exact(32)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect();
+        let scalar = |chunk: [u8; 32]| -> Result<Scalar, Error> {
+            Option::from(Scalar::from_canonical_bytes(chunk))
+                .ok_or_else(|| InvalidProof(format!("non canonical scalar {chunk:x?}")).into())
+        };
+
+        Ok(Self {
+            a0: CompressedRistretto(chunks[0]),
+            a1: CompressedRistretto(chunks[1]),
+            b0: CompressedRistretto(chunks[2]),
+            b1: CompressedRistretto(chunks[3]),
+            d0: scalar(chunks[4])?,
+            d1: scalar(chunks[5])?,
+            r0: scalar(chunks[6])?,
+            r1: scalar(chunks[7])?,
+        })
+    }
+
     pub fn decompress(self) -> VoteProof {
         VoteProof {
             a0: self.a0.decompress().unwrap(),
@@ -74,11 +224,15 @@ impl VoteProof {
         &self,
         hasher: &mut Hasher,
         buf: &mut [u8; 64],
-        G: &RistrettoPoint,
-        H: &RistrettoPoint,
+        generators: &Generators,
         encrypted_vote: &RistrettoPoint,
         c0: &RistrettoPoint,
     ) -> bool {
+        let Ok(H) = generators.H_table() else {
+            return false;
+        };
+        let (G, G_table) = (generators.G(), generators.G_table());
+
         hasher.update(encrypted_vote.compress().as_bytes());
         hasher.update(c0.compress().as_bytes());
         hasher.update(self.a0.compress().as_bytes());
@@ -93,10 +247,10 @@ impl VoteProof {
         buf.zeroize();
 
         c == (self.d0 + self.d1)
-            && self.a0 == (H * self.r0) + (c0 * self.d0)
-            && self.a1 == (H * self.r1) + (c0 * self.d1)
-            && self.b0 == (G * self.r0) + (encrypted_vote * self.d0)
-            && self.b1 == (G * self.r1) + ((encrypted_vote - G) * self.d1)
+            && self.a0 == (H * &self.r0) + (c0 * self.d0)
+            && self.a1 == (H * &self.r1) + (c0 * self.d1)
+            && self.b0 == (G_table * &self.r0) + (encrypted_vote * self.d0)
+            && self.b1 == (G_table * &self.r1) + ((encrypted_vote - G) * self.d1)
     }
     pub fn compress(&self) -> CompressedVoteProof {
         CompressedVoteProof {
@@ -112,6 +266,82 @@ impl VoteProof {
     }
 }
 
+impl BitProof for VoteProof {
+    type Compressed = CompressedVoteProof;
+
+    fn base(generators: &Generators) -> Result<&RistrettoBasepointTable, Error> {
+        generators.H_table()
+    }
+
+    fn prove<R>(
+        rng: &mut R,
+        generators: &Generators,
+        u: &RistrettoPoint,
+        c0: &RistrettoPoint,
+        s: &Scalar,
+        bit: bool,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<CompressedVoteProof, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        CompressedVoteProof::prove(rng, generators, u, c0, s, bit, hasher, buf)
+    }
+
+    fn verify(
+        &self,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        generators: &Generators,
+        u: &RistrettoPoint,
+        c0: &RistrettoPoint,
+    ) -> bool {
+        VoteProof::verify(self, hasher, buf, generators, u, c0)
+    }
+
+    fn compress(&self) -> CompressedVoteProof {
+        VoteProof::compress(self)
+    }
+
+    fn decompress(compressed: CompressedVoteProof) -> Self {
+        compressed.decompress()
+    }
+}
+
+pub type RangeProof = range_proof::RangeProof<VoteProof>;
+pub type CompressedRangeProof = range_proof::CompressedRangeProof<VoteProof>;
+
+/// The proof attached to a ballot on the bulletin board
+#[derive(Clone)]
+pub enum BallotProof {
+    /// the encrypted vote is 0 or 1
+    Binary(Box<VoteProof>),
+    /// the encrypted vote lies in [0, w] for the voter's weight w
+    Weighted(RangeProof),
+}
+
+impl BallotProof {
+    pub fn verify(
+        &self,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        generators: &Generators,
+        weight: u64,
+        encrypted_vote: &RistrettoPoint,
+        c0: &RistrettoPoint,
+    ) -> bool {
+        match self {
+            BallotProof::Binary(proof) => {
+                weight >= 1 && proof.verify(hasher, buf, generators, encrypted_vote, c0)
+            }
+            BallotProof::Weighted(proof) => {
+                proof.verify(hasher, buf, generators, weight, encrypted_vote, c0)
+            }
+        }
+    }
+}
+
 pub struct Vote {
     pub(crate) encrypted_vote: RistrettoPoint,
     pub(crate) proof: VoteProof,
@@ -130,71 +360,180 @@ pub struct CompressedVote {
     pub(crate) proof: CompressedVoteProof,
 }
 
+/// (encrypted_shares, dealer_proof, dealer_commitments, encrypted_vote, vote_proof)
+pub type Ballot = (
+    Vec<(CompressedRistretto, RistrettoPoint)>,
+    (Scalar, Vec<Scalar>),
+    Vec<RistrettoPoint>,
+    CompressedRistretto,
+    CompressedVoteProof,
+);
+
+/// Identifies the voter behind a ballot. Only the valid ballot with the highest sequence number
+/// per credential is tallied, so a voter can override an earlier (e.g. coerced) ballot.
+#[derive(Clone)]
+pub struct BallotCredential {
+    pub public_key: RistrettoPoint,
+    pub sequence: u64,
+    /// over `ballot_message`
+    pub signature: Signature,
+}
+
+/// "ballot" | session | sequence | encrypted_vote | encrypted_shares
+pub fn ballot_message(
+    session: &[u8; 32],
+    sequence: u64,
+    encrypted_vote: &CompressedRistretto,
+    encrypted_shares: &[CompressedRistretto],
+) -> Vec<u8> {
+    b"ballot"
+        .iter()
+        .chain(session)
+        .chain(&sequence.to_le_bytes())
+        .chain(encrypted_vote.as_bytes())
+        .chain(encrypted_shares.iter().flat_map(|share| share.as_bytes()))
+        .copied()
+        .collect()
+}
+
+/// A ballot prepared from a fresh seed for the Benaloh challenge: the voter either casts it or
+/// audits it, which reveals the seed and spoils the ballot
+pub struct PreparedBallot {
+    seed: Secret<[u8; 32]>,
+    choice: bool,
+    pub ballot: Ballot,
+}
+
+impl PreparedBallot {
+    pub fn cast(self) -> Ballot {
+        self.ballot
+    }
+
+    /// Consumes the ballot, it can never be cast. The voter prepares a fresh one afterwards.
+    pub fn audit(self) -> BallotAudit {
+        let seed = *self.seed.expose();
+        BallotAudit {
+            seed,
+            choice: self.choice,
+            // the first draw of `Voter::vote`
+            s: random_scalar(&mut ChaCha20Rng::from_seed(seed)),
+            ballot: self.ballot,
+        }
+    }
+}
+
+/// What an audited ballot reveals: the seed behind s, the polynomials and the proofs' randomness
+pub struct BallotAudit {
+    pub seed: [u8; 32],
+    pub choice: bool,
+    pub s: Scalar,
+    pub ballot: Ballot,
+}
+
+impl BallotAudit {
+    /// Recomputes the ballot from the revealed seed and choice and checks it matches the one shown
+    /// to the voter
+    pub fn verify(
+        &self,
+        generators: &Arc<Generators>,
+        n: usize,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<bool, Error> {
+        let mut voter = Voter::new(n, t, generators)?;
+        let ballot = voter.vote(
+            &mut ChaCha20Rng::from_seed(self.seed),
+            hasher,
+            buf,
+            self.choice,
+        )?;
+        let encrypted_vote = generators.G_table() * &(self.s + Scalar::from(self.choice as u64));
+
+        Ok(self.ballot.3 == encrypted_vote.compress()
+            && ballot_bytes(&ballot) == ballot_bytes(&self.ballot))
+    }
+}
+
+/// encrypted_shares | d | z | dealer_commitments | encrypted_vote | vote_proof
+fn ballot_bytes(ballot: &Ballot) -> Vec<u8> {
+    let (encrypted_shares, (d, z), dealer_commitments, encrypted_vote, vote_proof) = ballot;
+    encrypted_shares
+        .iter()
+        .flat_map(|share| share.0.to_bytes())
+        .chain(d.to_bytes())
+        .chain(z.iter().flat_map(|z_i| z_i.to_bytes()))
+        .chain(
+            dealer_commitments
+                .iter()
+                .flat_map(|commitment| commitment.compress().to_bytes()),
+        )
+        .chain(encrypted_vote.to_bytes())
+        .chain(vote_proof.to_bytes())
+        .collect()
+}
+
 pub struct Voter {
     pub dealer: Dealer,
-    G: RistrettoPoint,
-    vote: Option<Scalar>,
+    vote: Option<Secret<Scalar>>,
     encrypted_vote: Option<RistrettoPoint>,
+    credential: Option<SigningKey>,
+    /// of the last signed ballot
+    sequence: u64,
 }
 
 impl Voter {
-    pub fn new(
-        G: &RistrettoPoint,
-        H: &RistrettoPoint,
-        n: usize,
-        t: usize,
-        public_keys: &[CompressedRistretto],
-    ) -> Result<Self, Error> {
-        let dealer = Dealer::new(*H, n, t, public_keys)?;
+    pub fn new(n: usize, t: usize, generators: &Arc<Generators>) -> Result<Self, Error> {
+        let dealer = Dealer::new(n, t, generators)?;
 
         Ok(Voter {
             dealer: dealer,
             vote: None,
-            G: *G,
             encrypted_vote: None,
+            credential: None,
+            sequence: 0,
         })
     }
-    // Returns (encrypted_shares, dealer_proof, dealer_commitments, encrypted_vote, vote_proof)
-    pub fn vote<R>(
+
+    pub fn with_credential(mut self, credential: SigningKey) -> Self {
+        self.credential = Some(credential);
+        self
+    }
+    /// Deals a fresh secret s, returns it with (encrypted_shares, dealer_proof, dealer_commitments)
+    #[allow(clippy::type_complexity)]
+    fn deal_ballot<R>(
         &mut self,
         rng: &mut R,
         hasher: &mut Hasher,
         buf: &mut [u8; 64],
-        choice: bool,
     ) -> Result<
         (
+            Secret<Scalar>,
             Vec<(CompressedRistretto, RistrettoPoint)>,
             (Scalar, Vec<Scalar>),
             Vec<RistrettoPoint>,
-            CompressedRistretto,
-            CompressedVoteProof,
         ),
         Error,
     >
     where
         R: CryptoRng + RngCore,
     {
-        let s = random_scalar(rng);
+        let s = Secret::new(random_scalar(rng));
 
         let (encrypted_shares, (d, z), dealer_commitments) =
-            self.dealer.deal_secret(rng, hasher, buf, s.clone());
+            self.dealer.deal_secret(rng, hasher, buf, *s.expose());
 
         let decompressed_shares = batch_decompress_ristretto_points(&encrypted_shares).unwrap();
         let decompressed_commitments =
             batch_decompress_ristretto_points(&dealer_commitments).unwrap();
 
-        let c0 = dealer_commitments[0].decompress().unwrap();
-
-        // generate vote
-        self.generate_vote(&s, choice);
-
         assert!(
             verify_encrypted_shares_standalone(
-                &self.dealer.H,
+                self.dealer.generators.H_table()?,
                 hasher,
                 buf,
                 &(encrypted_shares.clone(), decompressed_shares.clone()),
-                &self.dealer.public_keys,
+                self.dealer.generators.public_key_tables(),
                 &decompressed_commitments,
                 (&d, &z),
                 self.dealer.n,
@@ -203,29 +542,247 @@ impl Voter {
             .unwrap()
         );
 
-        //dleq_vote
-        let vote_proof = self.dleq_vote(rng, &c0, &s, hasher, buf)?;
-
         let out: Vec<(CompressedRistretto, RistrettoPoint)> = encrypted_shares
             .par_iter()
             .zip(decompressed_shares.par_iter())
             .map(|(a, b)| (*a, *b))
             .collect();
+        Ok((s, out, (d, z), decompressed_commitments))
+    }
+
+    // Returns (encrypted_shares, dealer_proof, dealer_commitments, encrypted_vote, vote_proof)
+    pub fn vote<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        choice: bool,
+    ) -> Result<Ballot, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let (s, out, (d, z), dealer_commitments) = self.deal_ballot(rng, hasher, buf)?;
+
+        // generate vote
+        self.generate_vote(s.expose(), choice);
+
+        //dleq_vote
+        let vote_proof = self.dleq_vote(rng, &dealer_commitments[0], s.expose(), hasher, buf)?;
+
         Ok((
             out,
             (d, z),
-            decompressed_commitments,
+            dealer_commitments,
             self.encrypted_vote.as_ref().unwrap().compress(),
             vote_proof,
         ))
     }
 
+    /// Same as `vote`, signed with the voter's credential under the next sequence number
+    pub fn vote_signed<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        choice: bool,
+    ) -> Result<(Ballot, BallotCredential), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if self.credential.is_none() {
+            return Err(UninitializedValue("voter.credential").into());
+        }
+        let ballot = self.vote(rng, hasher, buf, choice)?;
+        let credential = self.sign_ballot(rng, &ballot.0, &ballot.3)?;
+
+        Ok((ballot, credential))
+    }
+
+    /// Signs a ballot with the voter's credential under the next sequence number
+    fn sign_ballot<R>(
+        &mut self,
+        rng: &mut R,
+        encrypted_shares: &[(CompressedRistretto, RistrettoPoint)],
+        encrypted_vote: &CompressedRistretto,
+    ) -> Result<BallotCredential, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if self.credential.is_none() {
+            return Err(UninitializedValue("voter.credential").into());
+        }
+        self.sequence += 1;
+
+        let encrypted_shares: Vec<CompressedRistretto> =
+            encrypted_shares.iter().map(|share| share.0).collect();
+        let message = ballot_message(
+            &self.dealer.generators.session(),
+            self.sequence,
+            encrypted_vote,
+            &encrypted_shares,
+        );
+        // checked above
+        let credential = self.credential.as_ref().unwrap();
+        let signature = credential.sign(rng, self.dealer.generators.G_table(), &message);
+
+        Ok(BallotCredential {
+            public_key: credential.public_key,
+            sequence: self.sequence,
+            signature,
+        })
+    }
+
+    /// Prepares a ballot for the cast-or-audit challenge, see `PreparedBallot`
+    pub fn prepare<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        choice: bool,
+    ) -> Result<PreparedBallot, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut seed = Secret::new([0u8; 32]);
+        rng.fill_bytes(seed.expose_mut());
+        let ballot = self.vote(
+            &mut ChaCha20Rng::from_seed(*seed.expose()),
+            hasher,
+            buf,
+            choice,
+        )?;
+
+        Ok(PreparedBallot {
+            seed,
+            choice,
+            ballot,
+        })
+    }
+
+    /// Casts `value` of the voter's `weight` votes, proven to lie in [0, weight], and signs the
+    /// ballot with the voter's credential. The board looks the weight up on its roll by credential.
+    // Returns (ballot, credential), the ballot being
+    // (encrypted_shares, dealer_proof, dealer_commitments, encrypted_vote, range_proof)
+    #[allow(clippy::type_complexity)]
+    pub fn vote_weighted<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        weight: u64,
+        value: u64,
+    ) -> Result<
+        (
+            (
+                Vec<(CompressedRistretto, RistrettoPoint)>,
+                (Scalar, Vec<Scalar>),
+                Vec<RistrettoPoint>,
+                CompressedRistretto,
+                CompressedRangeProof,
+            ),
+            BallotCredential,
+        ),
+        Error,
+    >
+    where
+        R: CryptoRng + RngCore,
+    {
+        if value > weight {
+            return Err(InvalidWeight(value, weight).into());
+        }
+        if self.credential.is_none() {
+            return Err(UninitializedValue("voter.credential").into());
+        }
+        let (s, out, (d, z), dealer_commitments) = self.deal_ballot(rng, hasher, buf)?;
+
+        self.generate_weighted_vote(s.expose(), value);
+
+        let range_proof = CompressedRangeProof::prove(
+            rng,
+            &self.dealer.generators,
+            weight,
+            value,
+            s.expose(),
+            hasher,
+            buf,
+        )?;
+
+        let ballot = (
+            out,
+            (d, z),
+            dealer_commitments,
+            self.encrypted_vote.as_ref().unwrap().compress(),
+            range_proof,
+        );
+        let credential = self.sign_ballot(rng, &ballot.0, &ballot.3)?;
+
+        Ok((ballot, credential))
+    }
+
+    /// Casts one binary ballot per candidate, the number of approvals is proven to lie in [min, max]
+    #[allow(clippy::too_many_arguments)]
+    pub fn vote_approval<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        approvals: &[bool],
+        min: u64,
+        max: u64,
+    ) -> Result<ApprovalBallot, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let approved = approvals.iter().filter(|approval| **approval).count() as u64;
+        if approved < min || approved > max {
+            return Err(InvalidSelection(min, max, approvals.len()).into());
+        }
+
+        // the sum of the encrypted votes is encrypted under the sum of the exponents
+        let mut exponent = Secret::new(Scalar::ZERO);
+        let mut candidates = Vec::with_capacity(approvals.len());
+        for approval in approvals {
+            let (s, out, (d, z), dealer_commitments) = self.deal_ballot(rng, hasher, buf)?;
+
+            self.generate_vote(s.expose(), *approval);
+            let vote_proof =
+                self.dleq_vote(rng, &dealer_commitments[0], s.expose(), hasher, buf)?;
+
+            *exponent.expose_mut() += s.expose();
+            candidates.push((
+                out,
+                (d, z),
+                dealer_commitments,
+                self.encrypted_vote.as_ref().unwrap().compress(),
+                vote_proof,
+            ));
+        }
+
+        let sum_proof = CompressedRangeProof::prove(
+            rng,
+            &self.dealer.generators,
+            max - min,
+            approved - min,
+            exponent.expose(),
+            hasher,
+            buf,
+        )?;
+
+        Ok(ApprovalBallot {
+            candidates,
+            sum_proof,
+        })
+    }
+
     pub fn generate_vote(&mut self, s: &Scalar, choice: bool) {
-        self.vote = Some(match choice {
-            true => Scalar::ONE,
-            false => Scalar::ZERO,
-        });
-        self.encrypted_vote = Some(self.G * (s + self.vote.unwrap()));
+        self.generate_weighted_vote(s, choice as u64);
+    }
+
+    pub fn generate_weighted_vote(&mut self, s: &Scalar, value: u64) {
+        let vote = Secret::new(Scalar::from(value));
+        let exponent = Secret::new(s + vote.expose());
+        self.encrypted_vote = Some(self.dealer.generators.G_table() * exponent.expose());
+        self.vote = Some(vote);
     }
 
     pub fn dleq_vote<R>(
@@ -239,90 +796,17 @@ impl Voter {
     where
         R: CryptoRng + RngCore,
     {
-        let G = &self.G;
-        let H = &self.dealer.H;
-        match (self.vote, self.encrypted_vote) {
-            (Some(v), Some(u)) => {
-                hasher.update(u.compress().as_bytes());
-                hasher.update(c0.compress().as_bytes());
-
-                let w = random_scalar(rng);
-
-                // if vote {proof.d0 = d0t_d1f; proof.d1 = d0f_d1t} else {proof.d0 = d0f_d1t; proof.d1 = d0t_d1f};
-                let d0t_d1f = random_scalar(rng);
-                let d0f_d1t = -d0t_d1f;
-
-                // if vote {proof.r0 = r0t_r1f} else {proof.r1 = r0t_r1f};
-                let r0t_r1f = random_scalar(rng);
-
-                // proof.a0 = vote ? a0t_a1f : a0f_a1t;
-                // proof.a1 = vote ? a0f_a1t : a0t_a1f;
-                let a0t_a1f = ((r0t_r1f * H) + (d0t_d1f * c0)).compress();
-                let a0f_a1t = (w * H).compress();
-
-                // if vote {proof.b0 = b0t; proof.b1 = b0f_b1t} else {proof.b0 = b0f_b1t; proof.b1 = b1f};
-                let b0f_b1t = (w * G).compress();
-
-                let b1f = ((r0t_r1f * G) + ((u - G) * d0t_d1f)).compress();
-                let b0t = ((r0t_r1f * G) + (u * d0t_d1f)).compress();
-
-                Ok(match v == Scalar::ONE {
-                    true => {
-                        let mut proof = CompressedVoteProof {
-                            a0: a0t_a1f,
-                            a1: a0f_a1t,
-                            b0: b0t,
-                            b1: b0f_b1t,
-                            d0: d0t_d1f,
-                            d1: d0f_d1t,
-                            r0: r0t_r1f,
-                            r1: w,
-                        };
-
-                        hasher.update(proof.a0.as_bytes());
-                        hasher.update(proof.b0.as_bytes());
-                        hasher.update(proof.a1.as_bytes());
-                        hasher.update(proof.b1.as_bytes());
-
-                        hasher.finalize_xof().fill(buf);
-
-                        proof.d1 += Scalar::from_bytes_mod_order_wide(buf);
-                        proof.r1 -= s * proof.d1;
-
-                        hasher.reset();
-                        buf.zeroize();
-
-                        proof
-                    }
-                    false => {
-                        let mut proof = CompressedVoteProof {
-                            a0: a0f_a1t,
-                            a1: a0t_a1f,
-                            b0: b0f_b1t,
-                            b1: b1f,
-                            d0: d0f_d1t,
-                            d1: d0t_d1f,
-                            r0: w,
-                            r1: r0t_r1f,
-                        };
-
-                        hasher.update(proof.a0.as_bytes());
-                        hasher.update(proof.b0.as_bytes());
-                        hasher.update(proof.a1.as_bytes());
-                        hasher.update(proof.b1.as_bytes());
-
-                        hasher.finalize_xof().fill(buf);
-
-                        proof.d0 += Scalar::from_bytes_mod_order_wide(buf);
-                        proof.r0 -= s * proof.d0;
-
-                        hasher.reset();
-                        buf.zeroize();
-
-                        proof
-                    }
-                })
-            }
+        match (&self.vote, self.encrypted_vote) {
+            (Some(v), Some(u)) => CompressedVoteProof::prove(
+                rng,
+                &self.dealer.generators,
+                &u,
+                c0,
+                s,
+                *v.expose() == Scalar::ONE,
+                hasher,
+                buf,
+            ),
             (None, None) => Err(UninitializedValue("voter.{vote,encrypted_vote}").into()),
             (None, _) => Err(UninitializedValue("voter.vote").into()),
             (_, None) => Err(UninitializedValue("voter.encrypted_vote").into()),
@@ -330,15 +814,13 @@ impl Voter {
     }
 
     pub fn generate_voters(
-        G: &RistrettoPoint,
-        H: &RistrettoPoint,
         m: usize,
         n: usize,
         t: usize,
-        public_keys: &[CompressedRistretto],
+        generators: &Arc<Generators>,
     ) -> Vec<Self> {
         (0..m)
-            .map(|_| Self::new(G, H, n, t, public_keys).unwrap())
+            .map(|_| Self::new(n, t, generators).unwrap())
             .collect()
     }
 }
@@ -1,10 +1,16 @@
 use common::{
+    generators::Generators,
+    key_proof::KeyProof,
+    polynomial::Polynomial,
     random::{random_point, random_scalar},
-    utils::precompute_lambda,
+    utils::{batch_decompress_ristretto_points, precompute_lambda},
 };
 use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
 use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto};
-use pi_s_ppvss::{dealer::Dealer, party::generate_parties};
+use pi_s_ppvss::{
+    dealer::Dealer, party::generate_parties, utils::verify_batched_encrypted_shares_standalone,
+};
+use std::sync::Arc;
 
 fn pvss(c: &mut Criterion) {
     for (n, t) in [
@@ -25,12 +31,24 @@ fn pvss(c: &mut Criterion) {
 
         let pk0 = random_point(&mut rng);
 
-        let mut parties = generate_parties(&G, &mut rng, n, t, &pk0);
+        let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+        let mut parties = generate_parties(&generators, &mut rng, n, t);
 
         let public_keys: Vec<CompressedRistretto> =
             parties.iter().map(|party| party.public_key.0).collect();
 
-        let mut dealer = Dealer::new(n, t, &public_keys, &pk0).unwrap();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+
+        let dealer_generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(
+                    &batch_decompress_ristretto_points(&public_keys).unwrap(),
+                    &key_proofs,
+                )
+                .unwrap(),
+        );
+        let mut dealer = Dealer::new(n, t, &dealer_generators).unwrap();
 
         for party in &mut parties {
             let public_keys: Vec<CompressedRistretto> = public_keys
@@ -88,6 +106,40 @@ fn pvss(c: &mut Criterion) {
             },
         );
 
+        let secrets: Vec<_> = (0..32).map(|_| random_scalar(&mut rng)).collect();
+        let (batch, (batch_d, batch_z)) =
+            dealer.deal_secrets(&mut rng, &mut hasher, &mut buf, &secrets);
+        let batch: Vec<_> = batch
+            .iter()
+            .map(|row| (row.clone(), batch_decompress_ristretto_points(row).unwrap()))
+            .collect();
+
+        c.bench_function(
+            &format!(
+                "(n: {}, t: {}) | Pi_S PPVSS | Party: Verify Batched Encrypted Shares (32 secrets)",
+                n, t
+            ),
+            |b| {
+                b.iter_batched(
+                    || (blake3::Hasher::new(), [0u8; 64]),
+                    |(mut hasher, mut buf)| {
+                        assert!(
+                            verify_batched_encrypted_shares_standalone(
+                                &batch,
+                                &dealer_generators.dealing_tables(),
+                                (&batch_d, &batch_z),
+                                t,
+                                &mut hasher,
+                                &mut buf
+                            )
+                            .unwrap()
+                        )
+                    },
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+
         //     let (decrypted_shares, share_proofs): (Vec<CompressedRistretto>, Vec<(Scalar, Scalar)>) =
         //         parties
         //             .iter_mut()
@@ -167,5 +219,71 @@ fn pvss(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, pvss);
+/// Fixed-base multiplication through precomputed tables against the variable-base path
+fn generators(c: &mut Criterion) {
+    for (n, t) in [(64, 31), (256, 127), (1024, 511)] {
+        let mut rng = rand::rng();
+
+        let G: RistrettoPoint = random_point(&mut rng);
+        let pk0 = random_point(&mut rng);
+
+        let parties = generate_parties(
+            &Arc::new(Generators::new(&G).with_pk0(&pk0)),
+            &mut rng,
+            n,
+            t,
+        );
+        let public_keys: Vec<RistrettoPoint> =
+            parties.iter().map(|party| party.public_key.1).collect();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+
+        c.bench_function(
+            &format!(
+                "(n: {}, t: {}) | Generators: Verify Key Proofs and Build Tables",
+                n, t
+            ),
+            |b| {
+                b.iter(|| {
+                    Generators::new(&G)
+                        .with_pk0(&pk0)
+                        .with_public_keys(&public_keys, &key_proofs)
+                        .unwrap()
+                })
+            },
+        );
+
+        let generators = Generators::new(&G)
+            .with_pk0(&pk0)
+            .with_public_keys(&public_keys, &key_proofs)
+            .unwrap();
+
+        let s = random_scalar(&mut rng);
+
+        c.bench_function(
+            &format!("(n: {}, t: {}) | G * s | Variable Base", n, t),
+            |b| b.iter(|| G * s),
+        );
+        c.bench_function(
+            &format!("(n: {}, t: {}) | G * s | Basepoint Table", n, t),
+            |b| b.iter(|| generators.G_table() * &s),
+        );
+
+        let (f, r) = Polynomial::sample_two(t, &mut rng);
+
+        let mut dealing_points = public_keys.clone();
+        dealing_points.insert(0, pk0);
+        let dealing_tables = generators.dealing_tables();
+
+        c.bench_function(
+            &format!("(n: {}, t: {}) | Encrypt Shares | Variable Base", n, t),
+            |b| b.iter(|| f.evaluate_multiply_two_ppvss(&r, &dealing_points)),
+        );
+        c.bench_function(
+            &format!("(n: {}, t: {}) | Encrypt Shares | Basepoint Tables", n, t),
+            |b| b.iter(|| f.evaluate_multiply_two_ppvss(&r, &dealing_tables)),
+        );
+    }
+}
+
+criterion_group!(benches, pvss, generators);
 criterion_main!(benches);
@@ -1,40 +1,39 @@
 use blake3::Hasher;
 use common::{
     error::{Error, ErrorKind::CountMismatch},
+    generators::Generators,
     polynomial::Polynomial,
-    utils::batch_decompress_ristretto_points,
+    secret::Secret,
+    signature::{Signature, SigningKey},
 };
 use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
 use rand::{CryptoRng, RngCore};
+use rayon::prelude::*;
+use std::sync::Arc;
 use zeroize::Zeroize;
 
+use crate::party::f0_message;
+
 pub struct Dealer {
     pub t: usize,
-    pub public_keys: Vec<RistrettoPoint>,
-    pub(crate) secret: Option<Scalar>,
+    /// pk0 and the parties' public keys, in dealing order
+    pub generators: Arc<Generators>,
+    pub(crate) secret: Option<Secret<Scalar>>,
 }
 
 impl Dealer {
-    pub fn new(
-        n: usize,
-        t: usize,
-        public_keys: &[CompressedRistretto],
-        pk0: &RistrettoPoint,
-    ) -> Result<Self, Error> {
+    pub fn new(n: usize, t: usize, generators: &Arc<Generators>) -> Result<Self, Error> {
+        let public_keys = generators.public_keys();
         if public_keys.len() != n {
             return Err(CountMismatch(n, "parties", public_keys.len(), "public keys").into());
         }
-        match batch_decompress_ristretto_points(public_keys) {
-            Ok(mut pks) => {
-                pks.insert(0, *pk0);
-                Ok(Self {
-                    t,
-                    public_keys: pks,
-                    secret: None,
-                })
-            }
-            Err(x) => Err(x),
-        }
+        generators.pk0()?;
+
+        Ok(Self {
+            t,
+            generators: generators.clone(),
+            secret: None,
+        })
     }
 
     pub fn t(&self) -> usize {
@@ -52,9 +51,12 @@ impl Dealer {
         R: CryptoRng + RngCore,
     {
         let (mut z, r) = Polynomial::sample_two_set_f0(self.t, secret, rng);
-        self.secret = Some(*secret);
+        let r = Secret::new(r);
+        // kept for the optimistic reconstruction, see `publish_f0`
+        self.secret = Some(Secret::new(*secret));
 
-        let (encrypted_shares, r_vals) = z.evaluate_multiply_two_ppvss(&r, &self.public_keys);
+        let (encrypted_shares, r_vals) =
+            z.evaluate_multiply_two_ppvss(r.expose(), &self.generators.dealing_tables());
 
         encrypted_shares.iter().chain(r_vals.iter()).for_each(|x| {
             hasher.update(x.as_bytes());
@@ -67,16 +69,97 @@ impl Dealer {
         buf.zeroize();
         hasher.reset();
 
-        z.mul_sum(&d, &r);
+        z.mul_sum(&d, r.expose());
+
+        (encrypted_shares, (d, z))
+    }
+
+    /// Shares every secret with its own polynomial f_k under a single proof: one mask r, one
+    /// challenge d over all encrypted shares, and z = r + sum_k d^k * f_k. Row k starts with
+    /// pk0 * s_k like a single dealing; the optimistic `publish_f0` is not available here.
+    pub fn deal_secrets<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        secrets: &[Scalar],
+    ) -> (Vec<Vec<CompressedRistretto>>, (Scalar, Polynomial))
+    where
+        R: CryptoRng + RngCore,
+    {
+        let tables = self.generators.dealing_tables();
+        let polynomials: Vec<Secret<Polynomial>> = secrets
+            .iter()
+            .map(|secret| Secret::new(Polynomial::sample_set_f0(self.t, rng, secret)))
+            .collect();
+        let r = Secret::new(Polynomial::sample(self.t, rng));
+
+        let encrypted_shares: Vec<Vec<CompressedRistretto>> = polynomials
+            .iter()
+            .map(|f| {
+                let (evals, shares) = f.expose().evaluate_multiply(&tables, 0);
+                drop(Secret::new(evals));
+                shares.par_iter().map(|share| share.compress()).collect()
+            })
+            .collect();
+        let (evals, r_vals) = r.expose().evaluate_multiply(&tables, 0);
+        drop(Secret::new(evals));
+
+        encrypted_shares.iter().flatten().for_each(|x| {
+            hasher.update(x.as_bytes());
+        });
+        r_vals.iter().for_each(|x| {
+            hasher.update(x.compress().as_bytes());
+        });
+
+        hasher.finalize_xof().fill(buf);
+
+        let d = Scalar::from_bytes_mod_order_wide(buf);
+
+        buf.zeroize();
+        hasher.reset();
+
+        // Horner in d: ((f_l * d + f_l-1) * d + ... + f_1) * d + r
+        let mut z = Polynomial::from(vec![Scalar::ZERO; self.t + 1]);
+        for f in polynomials.iter().rev() {
+            z.mul_sum(&d, f.expose());
+        }
+        z.mul_sum(&d, r.expose());
 
         (encrypted_shares, (d, z))
     }
 
     pub fn get_pk0(&self) -> &RistrettoPoint {
-        &self.public_keys[0]
+        // checked in Dealer::new
+        self.generators.pk0().unwrap()
     }
 
     pub fn publish_f0(&self) -> Scalar {
-        self.secret.unwrap()
+        *self.secret.as_ref().unwrap().expose()
+    }
+
+    /// `publish_f0` signed with the dealer's registered key over the dealing it opens, so that
+    /// parties can turn a wrong f0 into `DealerMisbehaviour` evidence
+    pub fn publish_signed_f0<R>(
+        &self,
+        rng: &mut R,
+        signing_key: &SigningKey,
+        encrypted_shares: &[CompressedRistretto],
+        (d, z): &(Scalar, Polynomial),
+    ) -> (Scalar, Signature)
+    where
+        R: CryptoRng + RngCore,
+    {
+        let f0 = self.publish_f0();
+        let message = f0_message(
+            &self.generators.session(),
+            encrypted_shares,
+            &(*d, z.coefficients().to_vec()),
+            &f0,
+        );
+        (
+            f0,
+            signing_key.sign(rng, self.generators.G_table(), &message),
+        )
     }
 }
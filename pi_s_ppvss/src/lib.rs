@@ -6,13 +6,23 @@ pub mod utils;
 
 mod tests {
     use common::{
+        generators::Generators,
+        key_proof::KeyProof,
+        keystore::{KdfParams, Keypair, export_keypair, import_keypair},
+        polynomial::Polynomial,
         random::{random_point, random_scalar},
-        utils::precompute_lambda,
+        signature::SigningKey,
+        utils::{batch_decompress_ristretto_points, lagrange_coefficients, precompute_lambda},
     };
     use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
     use rayon::prelude::*;
+    use std::sync::Arc;
 
-    use crate::{dealer::Dealer, party::generate_parties};
+    use crate::{
+        dealer::Dealer,
+        party::{Party, Reconstruction, f0_message, generate_parties},
+        utils::verify_batched_encrypted_shares_standalone,
+    };
 
     #[test]
     fn end_to_end() {
@@ -29,12 +39,25 @@ mod tests {
 
         let pk0 = random_point(&mut rng);
 
-        let mut parties = generate_parties(&G, &mut rng, N, T, &pk0);
+        let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+        let mut parties = generate_parties(&generators, &mut rng, N, T);
 
         let public_keys: Vec<CompressedRistretto> =
             parties.iter().map(|party| party.public_key.0).collect();
 
-        let mut dealer = Dealer::new(N, T, &public_keys, &pk0).unwrap();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+
+        let dealer_generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(
+                    &batch_decompress_ristretto_points(&public_keys).unwrap(),
+                    &key_proofs,
+                )
+                .unwrap(),
+        );
+        let mut dealer = Dealer::new(N, T, &dealer_generators).unwrap();
+        let dealer_key = SigningKey::generate(&mut rng, generators.G_table());
 
         for party in &mut parties {
             let public_keys: Vec<CompressedRistretto> = public_keys
@@ -64,7 +87,7 @@ mod tests {
                 .iter_mut()
                 .map(|p| {
                     p.decrypt_share().unwrap();
-                    p.dleq_share(&G, &mut rng, &mut hasher, &mut buf).unwrap();
+                    p.dleq_share(&mut rng, &mut hasher, &mut buf).unwrap();
                     (
                         p.decrypted_share.unwrap().compress(),
                         p.share_proof.unwrap(),
@@ -72,6 +95,22 @@ mod tests {
                 })
                 .collect();
 
+        let dealing = (d, z.clone());
+        let (f0, f0_signature) =
+            dealer.publish_signed_f0(&mut rng, &dealer_key, &encrypted_shares, &dealing);
+        // a lying dealer signs its wrong f0 just the same
+        let wrong_f0 = f0 + Scalar::ONE;
+        let wrong_f0_signature = dealer_key.sign(
+            &mut rng,
+            generators.G_table(),
+            &f0_message(
+                &generators.session(),
+                &encrypted_shares,
+                &(d, z.coefficients().to_vec()),
+                &wrong_f0,
+            ),
+        );
+
         let mut reconstructed_secrets: Vec<RistrettoPoint> = vec![];
         for p in &mut parties {
             let (mut decrypted_shares, mut share_proofs) =
@@ -82,7 +121,7 @@ mod tests {
             p.ingest_decrypted_shares_and_proofs(&decrypted_shares, share_proofs)
                 .unwrap();
 
-            p.verify_decrypted_shares(&G).unwrap();
+            p.verify_decrypted_shares().unwrap();
 
             reconstructed_secrets.push(p.reconstruct_secret_pessimistic(&lambdas).unwrap());
 
@@ -91,11 +130,302 @@ mod tests {
                 p.reconstruct_secret_optimistic(&dealer.publish_f0())
                     .unwrap()
             );
+            assert_eq!(
+                p.reconstruct_secret(
+                    Some((&f0, &f0_signature)),
+                    &dealer_key.public_key,
+                    &mut hasher,
+                    &mut buf
+                )
+                .unwrap(),
+                Reconstruction::Optimistic(G * f0)
+            );
+
+            // a silent dealer falls back to the decrypted shares
+            assert_eq!(
+                p.reconstruct_secret(None, &dealer_key.public_key, &mut hasher, &mut buf)
+                    .unwrap(),
+                Reconstruction::Pessimistic {
+                    secret: G * f0,
+                    misbehaviour: None
+                }
+            );
+
+            // so does an unsigned wrong f0, which cannot be pinned on the dealer
+            assert_eq!(
+                p.reconstruct_secret(
+                    Some((&wrong_f0, &f0_signature)),
+                    &dealer_key.public_key,
+                    &mut hasher,
+                    &mut buf
+                )
+                .unwrap(),
+                Reconstruction::Pessimistic {
+                    secret: G * f0,
+                    misbehaviour: None
+                }
+            );
+
+            // a lying dealer does too, and is caught
+            match p
+                .reconstruct_secret(
+                    Some((&wrong_f0, &wrong_f0_signature)),
+                    &dealer_key.public_key,
+                    &mut hasher,
+                    &mut buf,
+                )
+                .unwrap()
+            {
+                Reconstruction::Pessimistic {
+                    secret,
+                    misbehaviour: Some(misbehaviour),
+                } => {
+                    assert_eq!(secret, G * f0);
+                    assert_eq!(misbehaviour.f0, wrong_f0);
+                    assert!(
+                        misbehaviour
+                            .verify(
+                                &dealer_generators,
+                                &dealer_key.public_key,
+                                T,
+                                &mut hasher,
+                                &mut buf
+                            )
+                            .unwrap()
+                    );
+
+                    // the evidence only holds for the dealing it was taken from
+                    let mut forged = misbehaviour.clone();
+                    forged.encrypted_shares[0] = (pk0 * wrong_f0 + G).compress();
+                    assert!(
+                        !forged
+                            .verify(
+                                &dealer_generators,
+                                &dealer_key.public_key,
+                                T,
+                                &mut hasher,
+                                &mut buf
+                            )
+                            .unwrap()
+                    );
+
+                    // and only against the dealer that signed it
+                    assert!(
+                        !misbehaviour
+                            .verify(
+                                &dealer_generators,
+                                &(G * random_scalar(&mut rng)),
+                                T,
+                                &mut hasher,
+                                &mut buf
+                            )
+                            .unwrap()
+                    );
+                }
+                reconstruction => panic!("unexpected reconstruction {reconstruction:?}"),
+            }
         }
 
+        // nothing is reconstructed from a dealing that does not verify
+        parties[0].dealer_proof.as_mut().unwrap().0 += Scalar::ONE;
+        assert!(
+            parties[0]
+                .reconstruct_secret(
+                    Some((&f0, &f0_signature)),
+                    &dealer_key.public_key,
+                    &mut hasher,
+                    &mut buf
+                )
+                .is_err()
+        );
+
         // Pessimistic Case
         reconstructed_secrets
             .par_iter()
-            .for_each(|secret| assert_eq!(G * dealer.secret.unwrap(), *secret));
+            .for_each(|secret| assert_eq!(G * dealer.publish_f0(), *secret));
+    }
+
+    #[test]
+    fn keystore() {
+        let mut rng = rand::rng();
+        let generators =
+            Arc::new(Generators::new(&random_point(&mut rng)).with_pk0(&random_point(&mut rng)));
+        let party = Party::new(&generators, &mut rng, 5, 2, 3).unwrap();
+
+        let params = KdfParams {
+            m_cost: 64,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        let bytes = export_keypair(&party.keypair(), b"password", &params, &mut rng).unwrap();
+
+        let restored = Party::from_keypair(
+            &generators,
+            &mut rng,
+            &import_keypair(&bytes, b"password", generators.G_table()).unwrap(),
+            5,
+            2,
+        )
+        .unwrap();
+        assert_eq!(restored.index, 3);
+        assert_eq!(restored.public_key, party.public_key);
+        assert_eq!(restored.private_key(), party.private_key());
+
+        assert!(import_keypair(&bytes, b"wrong password", generators.G_table()).is_err());
+
+        let mut tampered = bytes.clone();
+        tampered[60] ^= 1;
+        assert!(import_keypair(&tampered, b"password", generators.G_table()).is_err());
+
+        // costs beyond the bounds are refused before any key derivation
+        let mut tampered = bytes.clone();
+        tampered[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(import_keypair(&tampered, b"password", generators.G_table()).is_err());
+
+        // a keystore whose private key is not the one behind its public key
+        let other = Party::new(&generators, &mut rng, 5, 2, 3).unwrap();
+        let mismatched = Keypair::new(3, other.private_key(), party.public_key.0);
+        let bytes = export_keypair(&mismatched, b"password", &params, &mut rng).unwrap();
+        assert!(import_keypair(&bytes, b"password", generators.G_table()).is_err());
+    }
+
+    #[test]
+    fn key_proofs() {
+        let mut rng = rand::rng();
+        let generators =
+            Arc::new(Generators::new(&random_point(&mut rng)).with_pk0(&random_point(&mut rng)));
+        let parties = generate_parties(&generators, &mut rng, 5, 2);
+        let mut public_keys: Vec<RistrettoPoint> =
+            parties.iter().map(|party| party.public_key.1).collect();
+        let mut key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+
+        assert!(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .is_ok()
+        );
+
+        // proofs are bound to the party index
+        key_proofs.swap(0, 1);
+        public_keys.swap(0, 1);
+        assert!(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .is_err()
+        );
+        key_proofs.swap(0, 1);
+        public_keys.swap(0, 1);
+
+        // a key derived from other parties' keys cannot come with a valid proof
+        public_keys[4] = public_keys[0] + public_keys[1];
+        assert!(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .is_err()
+        );
+
+        // nor can a proof be replayed in another session
+        let other_session = Generators::new(generators.G()).with_pk0(&random_point(&mut rng));
+        let public_keys: Vec<RistrettoPoint> =
+            parties.iter().map(|party| party.public_key.1).collect();
+        assert!(
+            other_session
+                .with_public_keys(&public_keys, &key_proofs)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn batched() {
+        const N: usize = 16;
+        const T: usize = 7;
+        const L: usize = 8;
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+        let pk0 = random_point(&mut rng);
+        let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+        let mut parties = generate_parties(&generators, &mut rng, N, T);
+        let public_keys: Vec<RistrettoPoint> =
+            parties.iter().map(|party| party.public_key.1).collect();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+        let dealer_generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+        let mut dealer = Dealer::new(N, T, &dealer_generators).unwrap();
+
+        let secrets: Vec<Scalar> = (0..L).map(|_| random_scalar(&mut rng)).collect();
+        let (encrypted_shares, (d, z)) =
+            dealer.deal_secrets(&mut rng, &mut hasher, &mut buf, &secrets);
+        let mut rows: Vec<(Vec<CompressedRistretto>, Vec<RistrettoPoint>)> = encrypted_shares
+            .iter()
+            .map(|row| (row.clone(), batch_decompress_ristretto_points(row).unwrap()))
+            .collect();
+
+        let tables = dealer_generators.dealing_tables();
+        assert!(
+            verify_batched_encrypted_shares_standalone(
+                &rows,
+                &tables,
+                (&d, &z),
+                T,
+                &mut hasher,
+                &mut buf
+            )
+            .unwrap()
+        );
+        // a zero top coefficient leaves the evaluations alone but exceeds degree t
+        let mut padded = z.coefficients().to_vec();
+        padded.push(Scalar::ZERO);
+        assert!(
+            !verify_batched_encrypted_shares_standalone(
+                &rows,
+                &tables,
+                (&d, &Polynomial::from(padded)),
+                T,
+                &mut hasher,
+                &mut buf
+            )
+            .unwrap()
+        );
+        rows[3].1[0] = pk0 * random_scalar(&mut rng);
+        rows[3].0[0] = rows[3].1[0].compress();
+        assert!(
+            !verify_batched_encrypted_shares_standalone(
+                &rows,
+                &tables,
+                (&d, &z),
+                T,
+                &mut hasher,
+                &mut buf
+            )
+            .unwrap()
+        );
+
+        // every row encrypts its secret for pk0 and decrypts like a single dealing
+        let indices: Vec<usize> = (1..=T + 1).collect();
+        let lambdas = lagrange_coefficients(&indices);
+        for (row, secret) in encrypted_shares.iter().zip(&secrets) {
+            assert_eq!(row[0], (pk0 * secret).compress());
+            let reconstructed: RistrettoPoint = parties[..=T]
+                .iter_mut()
+                .zip(&lambdas)
+                .map(|(p, lambda)| {
+                    p.ingest_encrypted_shares(row).unwrap();
+                    p.decrypt_share().unwrap();
+                    p.decrypted_share.unwrap() * lambda
+                })
+                .sum();
+            assert_eq!(reconstructed, G * secret);
+        }
     }
 }
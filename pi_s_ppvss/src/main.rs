@@ -1,9 +1,12 @@
 use common::{
+    generators::Generators,
+    key_proof::KeyProof,
     random::{random_point, random_scalar},
-    utils::precompute_lambda,
+    utils::{batch_decompress_ristretto_points, precompute_lambda},
 };
 use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto, scalar::Scalar};
 use pi_s_ppvss::{dealer::Dealer, party::generate_parties};
+use std::sync::Arc;
 
 fn main() {
     const N: usize = 33;
@@ -18,12 +21,24 @@ fn main() {
 
     let pk0 = random_point(&mut rng);
 
-    let mut parties = generate_parties(&G, &mut rng, N, T, &pk0);
+    let generators = Arc::new(Generators::new(&G).with_pk0(&pk0));
+    let mut parties = generate_parties(&generators, &mut rng, N, T);
 
     let public_keys: Vec<CompressedRistretto> =
         parties.iter().map(|party| party.public_key.0).collect();
 
-    let mut dealer = Dealer::new(N, T, &public_keys, &pk0).unwrap();
+    let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+
+    let dealer_generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(
+                &batch_decompress_ristretto_points(&public_keys).unwrap(),
+                &key_proofs,
+            )
+            .unwrap(),
+    );
+    let mut dealer = Dealer::new(N, T, &dealer_generators).unwrap();
 
     for party in &mut parties {
         let public_keys: Vec<CompressedRistretto> = public_keys
@@ -53,7 +68,7 @@ fn main() {
             .iter_mut()
             .map(|p| {
                 p.decrypt_share().unwrap();
-                p.dleq_share(&G, &mut rng, &mut hasher, &mut buf).unwrap();
+                p.dleq_share(&mut rng, &mut hasher, &mut buf).unwrap();
                 (
                     p.decrypted_share.unwrap().compress(),
                     p.share_proof.unwrap(),
@@ -71,7 +86,7 @@ fn main() {
         p.ingest_decrypted_shares_and_proofs(&decrypted_shares, share_proofs)
             .unwrap();
 
-        p.verify_decrypted_shares(&G).unwrap();
+        p.verify_decrypted_shares().unwrap();
 
         reconstructed_secrets.push(p.reconstruct_secret_pessimistic(&lambdas).unwrap());
     }
@@ -1,28 +1,35 @@
 use blake3::Hasher;
 
-use curve25519_dalek::{ristretto::CompressedRistretto, RistrettoPoint, Scalar};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
 use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 use common::{
     error::{
         Error,
-        ErrorKind::{CountMismatch, InvalidPararmeterSet, InvalidProof, UninitializedValue},
-    }, 
+        ErrorKind::{
+            CountMismatch, InvalidPararmeterSet, InvalidProof, KeyMismatch, UninitializedValue,
+        },
+    },
+    generators::Generators,
+    key_proof::KeyProof,
+    keystore::Keypair,
     polynomial::Polynomial,
-    random::random_scalar, 
-    utils::{batch_decompress_ristretto_points},
+    random::random_scalar,
+    secret::Secret,
+    signature::Signature,
+    utils::{batch_decompress_ristretto_points, lagrange_coefficients},
 };
 
-use crate::{
-    utils::verify_encrypted_shares_standalone
-};
+use crate::utils::verify_encrypted_shares_standalone;
 use rayon::prelude::*;
+use std::sync::Arc;
 
-#[derive(Clone)]
 pub struct Party {
-    pub private_key: Scalar,
+    private_key: Secret<Scalar>,
     pub public_key: (CompressedRistretto, RistrettoPoint),
+    /// proof of possession of `private_key`, checked when the public key is registered
+    pub key_proof: KeyProof,
     pub index: usize,
     pub n: usize,
     pub t: usize,
@@ -37,26 +44,52 @@ pub struct Party {
     pub decrypted_shares: Option<Vec<RistrettoPoint>>,
     pub share_proofs: Option<Vec<(Scalar, Scalar)>>,
     pub validated_shares: Vec<usize>,
-    pub pk0: RistrettoPoint,
+    /// G and pk0
+    pub generators: Arc<Generators>,
 }
 
 impl Party {
     pub fn new<R>(
-        G: &RistrettoPoint,
+        generators: &Arc<Generators>,
         rng: &mut R,
         n: usize,
         t: usize,
         index: usize,
-        pk0: RistrettoPoint,
     ) -> Result<Self, Error>
     where
         R: CryptoRng + RngCore,
     {
         let private_key = random_scalar(rng);
-        let public_key = G * &private_key;
+        Self::from_private_key(generators, rng, private_key, n, t, index)
+    }
+
+    /// Restores a party from a previously generated private key
+    pub fn from_private_key<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        private_key: Scalar,
+        n: usize,
+        t: usize,
+        index: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        generators.pk0()?;
+
+        let private_key = Secret::new(private_key);
+        let public_key = generators.G_table() * private_key.expose();
 
         if index <= n && t < n && t as f32 == ((n - 1) as f32 / 2.0).floor() {
             Ok(Self {
+                key_proof: KeyProof::prove(
+                    rng,
+                    generators.G_table(),
+                    &generators.session(),
+                    index,
+                    private_key.expose(),
+                    &public_key,
+                ),
                 private_key,
                 public_key: (public_key.compress(), public_key),
                 index,
@@ -71,13 +104,40 @@ impl Party {
                 decrypted_shares: None,
                 public_keys: None,
                 validated_shares: vec![],
-                pk0,
+                generators: generators.clone(),
             })
         } else {
             Err(InvalidPararmeterSet(n, t as isize, index).into())
         }
     }
 
+    /// Restores a party from a keypair loaded from a keystore, checking that the keys match
+    pub fn from_keypair<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        keypair: &Keypair,
+        n: usize,
+        t: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let party =
+            Self::from_private_key(generators, rng, *keypair.private_key(), n, t, keypair.index)?;
+        if party.public_key.0 != keypair.public_key {
+            return Err(KeyMismatch(keypair.index).into());
+        }
+        Ok(party)
+    }
+
+    pub fn keypair(&self) -> Keypair {
+        Keypair::new(self.index, self.private_key(), self.public_key.0)
+    }
+
+    pub fn private_key(&self) -> &Scalar {
+        self.private_key.expose()
+    }
+
     pub fn ingest_encrypted_shares(
         &mut self,
         encrypted_shares: &[CompressedRistretto],
@@ -178,7 +238,7 @@ impl Party {
             Some((d, z)) => match (&self.encrypted_shares, &self.public_keys) {
                 (Some(encrypted_shares), Some(public_keys)) => {
                     let mut new_pub_keys = public_keys.clone();
-                    new_pub_keys.insert(0, self.pk0);
+                    new_pub_keys.insert(0, *self.generators.pk0()?);
                     verify_encrypted_shares_standalone(
                         encrypted_shares,
                         &new_pub_keys,
@@ -198,10 +258,10 @@ impl Party {
     }
 
     pub fn decrypt_share(&mut self) -> Result<(), Error> {
-        let inv_private_key = self.private_key.invert();
+        let inv_private_key = Secret::new(self.private_key.expose().invert());
         match &self.encrypted_share {
             Some(encrypted_share) => {
-                self.decrypted_share = Some(encrypted_share * inv_private_key);
+                self.decrypted_share = Some(encrypted_share * inv_private_key.expose());
                 Ok(())
             }
             None => Err(UninitializedValue("party.encrypted_share").into()),
@@ -209,7 +269,6 @@ impl Party {
     }
     pub fn dleq_share<R>(
         &mut self,
-        G: &RistrettoPoint,
         rng: &mut R,
         hasher: &mut Hasher,
         buf: &mut [u8; 64],
@@ -221,7 +280,7 @@ impl Party {
             (Some(decrypted_share), Some(encrypted_share)) => {
                 let r = random_scalar(rng);
 
-                let c1 = (G * &r).compress();
+                let c1 = (self.generators.G_table() * &r).compress();
                 let c2 = (decrypted_share * r).compress();
 
                 hasher.update(self.public_key.0.as_bytes());
@@ -232,13 +291,13 @@ impl Party {
                 hasher.finalize_xof().fill(buf);
 
                 let d = Scalar::from_bytes_mod_order_wide(buf);
-                let z = r + d * self.private_key;
+                let z = r + d * self.private_key.expose();
 
                 self.share_proof = Some((d, z));
-                
+
                 hasher.reset();
                 buf.zeroize();
-                
+
                 Ok(())
             }
             (None, Some(_)) => Err(UninitializedValue("party.decrypted_share").into()),
@@ -249,7 +308,8 @@ impl Party {
         }
     }
 
-    pub fn verify_decrypted_shares(&mut self, G: &RistrettoPoint) -> Result<bool, Error> {
+    pub fn verify_decrypted_shares(&mut self) -> Result<bool, Error> {
+        let G = self.generators.G_table();
         match (&self.public_keys, &self.encrypted_shares) {
             (Some(public_keys), Some(enc_shares)) => {
                 match (&self.decrypted_shares, &self.share_proofs) {
@@ -318,23 +378,191 @@ impl Party {
         }
     }
     pub fn reconstruct_secret_optimistic(&self, f0: &Scalar) -> Result<bool, Error> {
-        let point = self.encrypted_shares.as_ref().unwrap().1[0];
-        Ok(point.compress().decompress().unwrap()
-            == (self.pk0 * f0).compress().decompress().unwrap())
+        match &self.encrypted_shares {
+            Some(enc_shares) => Ok(enc_shares.1[0] == self.generators.pk0_table()? * f0),
+            None => Err(UninitializedValue("party.encrypted_shares").into()),
+        }
+    }
+
+    /// Verifies the dealing, then tries the dealer's published f0 first and falls back to the
+    /// decrypted shares (ingested with `ingest_decrypted_shares_and_proofs`) when the dealer is
+    /// silent or lies. A wrong f0 only yields evidence if it is signed under `dealer_key`.
+    pub fn reconstruct_secret(
+        &mut self,
+        f0: Option<(&Scalar, &Signature)>,
+        dealer_key: &RistrettoPoint,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Reconstruction, Error> {
+        if !self.verify_encrypted_shares(hasher, buf)? {
+            return Err(InvalidProof("dealing does not verify".to_string()).into());
+        }
+        let misbehaviour = match f0 {
+            Some((f0, _)) if self.reconstruct_secret_optimistic(f0)? => {
+                return Ok(Reconstruction::Optimistic(self.generators.G_table() * f0));
+            }
+            Some((f0, signature)) => {
+                // both checked by verify_encrypted_shares
+                let (d, z) = self.dealer_proof.as_ref().unwrap();
+                let encrypted_shares = &self.encrypted_shares.as_ref().unwrap().0;
+                let dealer_proof = (*d, z.coefficients().to_vec());
+                let message = f0_message(
+                    &self.generators.session(),
+                    encrypted_shares,
+                    &dealer_proof,
+                    f0,
+                );
+                // an unsigned f0 cannot be pinned on the dealer
+                signature
+                    .verify(self.generators.G_table(), dealer_key, &message)
+                    .then(|| DealerMisbehaviour {
+                        f0: *f0,
+                        encrypted_shares: encrypted_shares.clone(),
+                        dealer_proof,
+                        signature: *signature,
+                    })
+            }
+            None => None,
+        };
+
+        if !self.verify_decrypted_shares()? {
+            return Err(CountMismatch(
+                self.t + 1,
+                "required shares",
+                self.validated_shares.len(),
+                "valid decrypted shares",
+            )
+            .into());
+        }
+        let positions = &self.validated_shares[..=self.t];
+        let indices: Vec<usize> = positions.iter().map(|i| i + 1).collect();
+        // checked by verify_decrypted_shares
+        let dec_shares = self.decrypted_shares.as_ref().unwrap();
+        let secret = lagrange_coefficients(&indices)
+            .par_iter()
+            .zip(positions.par_iter())
+            .map(|(lambda, i)| lambda * dec_shares[*i])
+            .sum();
+
+        Ok(Reconstruction::Pessimistic {
+            secret,
+            misbehaviour,
+        })
+    }
+}
+
+/// The outcome of `Party::reconstruct_secret`, both paths yield G * f(0)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reconstruction {
+    /// the dealer's f0 matched the encrypted share for pk0
+    Optimistic(RistrettoPoint),
+    /// reconstructed from t+1 verified decrypted shares
+    Pessimistic {
+        secret: RistrettoPoint,
+        /// set when the dealer published a signed f0 that failed the optimistic check
+        misbehaviour: Option<DealerMisbehaviour>,
+    },
+}
+
+impl Reconstruction {
+    pub fn secret(&self) -> &RistrettoPoint {
+        match self {
+            Reconstruction::Optimistic(secret) => secret,
+            Reconstruction::Pessimistic { secret, .. } => secret,
+        }
+    }
+}
+
+/// "pi_s_ppvss f0" | session | encrypted shares | proof | f0, signed by the dealer when it
+/// publishes f0 so that a wrong one can be pinned on it
+pub fn f0_message(
+    session: &[u8; 32],
+    encrypted_shares: &[CompressedRistretto],
+    dealer_proof: &(Scalar, Vec<Scalar>),
+    f0: &Scalar,
+) -> Vec<u8> {
+    b"pi_s_ppvss f0"
+        .iter()
+        .chain(session)
+        .chain(encrypted_shares.iter().flat_map(|share| share.as_bytes()))
+        .chain(dealer_proof.0.as_bytes())
+        .chain(dealer_proof.1.iter().flat_map(|z| z.as_bytes()))
+        .chain(f0.as_bytes())
+        .copied()
+        .collect()
+}
+
+/// Publicly checkable evidence that the dealer published a wrong f0 for a valid dealing
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DealerMisbehaviour {
+    pub f0: Scalar,
+    /// the dealer's encrypted shares, y0 (for pk0) first
+    pub encrypted_shares: Vec<CompressedRistretto>,
+    /// the dealer's proof (d, z) over `encrypted_shares`
+    pub dealer_proof: (Scalar, Vec<Scalar>),
+    /// the dealer's signature over `f0_message`
+    pub signature: Signature,
+}
+
+impl DealerMisbehaviour {
+    /// true if the evidence holds, i.e. the dealer signed f0 for this dealing under `dealer_key`,
+    /// the dealing verifies against pk0 and the registered public keys with a degree t proof,
+    /// and pk0 * f0 does not open y0
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        dealer_key: &RistrettoPoint,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<bool, Error> {
+        let pk0 = generators.pk0_table()?;
+        let dealing_keys = generators.dealing_tables();
+        let (d, z) = &self.dealer_proof;
+        if *d == Scalar::ZERO
+            || z.len() != t + 1
+            || self.encrypted_shares.len() != dealing_keys.len()
+        {
+            return Ok(false);
+        }
+        let message = f0_message(
+            &generators.session(),
+            &self.encrypted_shares,
+            &self.dealer_proof,
+            &self.f0,
+        );
+        if !self
+            .signature
+            .verify(generators.G_table(), dealer_key, &message)
+        {
+            return Ok(false);
+        }
+        let encrypted_shares = (
+            self.encrypted_shares.clone(),
+            batch_decompress_ristretto_points(&self.encrypted_shares)?,
+        );
+        let dealing_holds = verify_encrypted_shares_standalone(
+            &encrypted_shares,
+            &dealing_keys,
+            (d, &Polynomial::from(z.clone())),
+            hasher,
+            buf,
+        )?;
+
+        Ok(dealing_holds && self.encrypted_shares[0] != (pk0 * &self.f0).compress())
     }
 }
 
 pub fn generate_parties<R>(
-    G: &RistrettoPoint,
+    generators: &Arc<Generators>,
     rng: &mut R,
     n: usize,
     t: usize,
-    pk0: &RistrettoPoint,
 ) -> Vec<Party>
 where
     R: CryptoRng + RngCore,
 {
     (1..=n)
-        .map(|i| Party::new(G, rng, n, t, i, *pk0).unwrap())
+        .map(|i| Party::new(generators, rng, n, t, i).unwrap())
         .collect()
 }
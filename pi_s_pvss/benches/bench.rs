@@ -1,7 +1,15 @@
-use common::{random::random_scalar, utils::precompute_lambda};
+use common::{
+    generators::Generators,
+    key_proof::KeyProof,
+    random::random_scalar,
+    utils::{batch_decompress_ristretto_points, precompute_lambda},
+};
 use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
 use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto, scalar::Scalar};
-use pi_s_pvss::{dealer::Dealer, party::generate_parties};
+use pi_s_pvss::{
+    dealer::Dealer, party::generate_parties, utils::verify_batched_encrypted_shares_standalone,
+};
+use std::sync::Arc;
 
 fn pvss(c: &mut Criterion) {
     for (n, t) in [
@@ -18,23 +26,22 @@ fn pvss(c: &mut Criterion) {
 
         let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
 
-        let mut parties = generate_parties(&G, &mut rng, n, t);
+        let generators = Arc::new(Generators::new(&G));
+        let mut parties = generate_parties(&generators, &mut rng, n, t);
 
-        let public_keys: Vec<CompressedRistretto> =
-            parties.iter().map(|party| party.public_key.0).collect();
-        let mut dealer = Dealer::new(n, t, &public_keys).unwrap();
+        let points: Vec<RistrettoPoint> = parties.iter().map(|party| party.public_key.1).collect();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
 
-        let public_keys: Vec<CompressedRistretto> =
-            parties.iter().map(|party| party.public_key.0).collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&points, &key_proofs)
+                .unwrap(),
+        );
+        let mut dealer = Dealer::new(n, t, &generators).unwrap();
 
         for party in &mut parties {
-            let public_keys: Vec<CompressedRistretto> = public_keys
-                .iter()
-                .filter(|pk| &party.public_key.0 != *pk)
-                .copied()
-                .collect();
-
-            party.ingest_public_keys(&public_keys).unwrap();
+            party.ingest_registered_keys(&generators).unwrap();
         }
         let secret = random_scalar(&mut rng);
 
@@ -83,12 +90,46 @@ fn pvss(c: &mut Criterion) {
             },
         );
 
+        let secrets: Vec<_> = (0..32).map(|_| random_scalar(&mut rng)).collect();
+        let (batch, (batch_d, batch_z)) =
+            dealer.deal_secrets(&mut rng, &mut hasher, &mut buf, &secrets);
+        let batch: Vec<_> = batch
+            .iter()
+            .map(|row| (row.clone(), batch_decompress_ristretto_points(row).unwrap()))
+            .collect();
+
+        c.bench_function(
+            &format!(
+                "(n: {}, t: {}) | Pi_S PVSS | Party: Verify Batched Encrypted Shares (32 secrets)",
+                n, t
+            ),
+            |b| {
+                b.iter_batched(
+                    || (blake3::Hasher::new(), [0u8; 64]),
+                    |(mut hasher, mut buf)| {
+                        assert!(
+                            verify_batched_encrypted_shares_standalone(
+                                &batch,
+                                &points,
+                                (&batch_d, &batch_z),
+                                t,
+                                &mut hasher,
+                                &mut buf
+                            )
+                            .unwrap()
+                        )
+                    },
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+
         let (decrypted_shares, share_proofs): (Vec<CompressedRistretto>, Vec<(Scalar, Scalar)>) =
             parties
                 .iter_mut()
                 .map(|p| {
                     p.decrypt_share().unwrap();
-                    p.dleq_share(&G, &mut rng, &mut hasher, &mut buf).unwrap();
+                    p.dleq_share(&mut rng, &mut hasher, &mut buf).unwrap();
                     (
                         p.decrypted_share.unwrap().compress(),
                         p.share_proof.unwrap(),
@@ -108,7 +149,7 @@ fn pvss(c: &mut Criterion) {
                     || (blake3::Hasher::new(), [0u8; 64]),
                     |(mut hasher, mut buf)| {
                         parties[0]
-                            .dleq_share(&G, &mut rng, &mut hasher, &mut buf)
+                            .dleq_share(&mut rng, &mut hasher, &mut buf)
                             .unwrap()
                     },
                     BatchSize::PerIteration,
@@ -133,7 +174,7 @@ fn pvss(c: &mut Criterion) {
             ),
             |b| {
                 b.iter(|| {
-                    parties[0].verify_decrypted_shares(&G).unwrap();
+                    parties[0].verify_decrypted_shares().unwrap();
                 })
             },
         );
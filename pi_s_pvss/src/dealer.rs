@@ -1,35 +1,35 @@
 use common::{
     error::{Error, ErrorKind::CountMismatch},
+    generators::Generators,
     polynomial::Polynomial,
+    secret::Secret,
 };
 
 use blake3::Hasher;
-use common::utils::batch_decompress_ristretto_points;
-use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use curve25519_dalek::{Scalar, ristretto::CompressedRistretto};
 
 use rand::{CryptoRng, RngCore};
 use rayon::prelude::*;
+use std::sync::Arc;
 use zeroize::Zeroize;
 
 pub struct Dealer {
     t: usize,
-    public_keys: Vec<RistrettoPoint>,
-    pub(crate) secret: Option<Scalar>,
+    /// the parties' public keys, registered with their proofs of possession
+    pub generators: Arc<Generators>,
 }
 
 impl Dealer {
-    pub fn new(n: usize, t: usize, public_keys: &[CompressedRistretto]) -> Result<Self, Error> {
+    pub fn new(n: usize, t: usize, generators: &Arc<Generators>) -> Result<Self, Error> {
+        let public_keys = generators.public_keys();
         if public_keys.len() != n {
             return Err(CountMismatch(n, "parties", public_keys.len(), "public keys").into());
         }
-        match batch_decompress_ristretto_points(public_keys) {
-            Ok(pks) => Ok(Self {
-                t,
-                public_keys: pks.par_iter().map(|pk| *pk).collect(),
-                secret: None,
-            }),
-            Err(x) => Err(x),
-        }
+
+        Ok(Self {
+            t,
+            generators: generators.clone(),
+        })
     }
 
     pub fn deal_secret<R>(
@@ -39,13 +39,35 @@ impl Dealer {
         buf: &mut [u8; 64],
         secret: &Scalar,
     ) -> (Vec<CompressedRistretto>, (Scalar, Polynomial))
+    where
+        R: CryptoRng + RngCore,
+    {
+        let (encrypted_shares, proof, _) = self.deal_secret_with_opening(rng, hasher, buf, secret);
+        (encrypted_shares, proof)
+    }
+
+    /// Same as `deal_secret`, and also returns the sharing polynomial, which opens every encrypted
+    /// share. The dealer does not keep it.
+    pub fn deal_secret_with_opening<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        secret: &Scalar,
+    ) -> (
+        Vec<CompressedRistretto>,
+        (Scalar, Polynomial),
+        Secret<Polynomial>,
+    )
     where
         R: CryptoRng + RngCore,
     {
         let (mut z, r) = Polynomial::sample_two_set_f0(self.t, secret, rng);
-        self.secret = Some(*secret);
+        let r = Secret::new(r);
+        let opening = Secret::new(z.clone());
 
-        let (encrypted_shares, r_vals) = z.evaluate_multiply_two(&r, &self.public_keys);
+        let (encrypted_shares, r_vals) =
+            z.evaluate_multiply_two(r.expose(), self.generators.public_key_tables());
         encrypted_shares.iter().chain(r_vals.iter()).for_each(|x| {
             hasher.update(x.as_bytes());
         });
@@ -57,7 +79,64 @@ impl Dealer {
         hasher.reset();
         buf.zeroize();
 
-        z.mul_sum(&d, &r);
+        z.mul_sum(&d, r.expose());
+
+        (encrypted_shares, (d, z), opening)
+    }
+
+    /// Shares every secret with its own polynomial f_k under a single proof: one mask r, one
+    /// challenge d over all encrypted shares, and z = r + sum_k d^k * f_k
+    pub fn deal_secrets<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        secrets: &[Scalar],
+    ) -> (Vec<Vec<CompressedRistretto>>, (Scalar, Polynomial))
+    where
+        R: CryptoRng + RngCore,
+    {
+        let polynomials: Vec<Secret<Polynomial>> = secrets
+            .iter()
+            .map(|secret| Secret::new(Polynomial::sample_set_f0(self.t, rng, secret)))
+            .collect();
+        let r = Secret::new(Polynomial::sample(self.t, rng));
+
+        let encrypted_shares: Vec<Vec<CompressedRistretto>> = polynomials
+            .iter()
+            .map(|f| {
+                let (evals, shares) = f
+                    .expose()
+                    .evaluate_multiply(self.generators.public_key_tables(), 1);
+                drop(Secret::new(evals));
+                shares.par_iter().map(|share| share.compress()).collect()
+            })
+            .collect();
+        let (evals, r_vals) = r
+            .expose()
+            .evaluate_multiply(self.generators.public_key_tables(), 1);
+        drop(Secret::new(evals));
+
+        encrypted_shares.iter().flatten().for_each(|x| {
+            hasher.update(x.as_bytes());
+        });
+        r_vals.iter().for_each(|x| {
+            hasher.update(x.compress().as_bytes());
+        });
+
+        hasher.finalize_xof().fill(buf);
+
+        let d = Scalar::from_bytes_mod_order_wide(buf);
+
+        hasher.reset();
+        buf.zeroize();
+
+        // Horner in d: ((f_l * d + f_l-1) * d + ... + f_1) * d + r
+        let mut z = Polynomial::from(vec![Scalar::ZERO; self.t + 1]);
+        for f in polynomials.iter().rev() {
+            z.mul_sum(&d, f.expose());
+        }
+        z.mul_sum(&d, r.expose());
 
         (encrypted_shares, (d, z))
     }
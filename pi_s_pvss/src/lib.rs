@@ -1,13 +1,24 @@
 pub mod dealer;
 pub mod party;
+pub mod utils;
 
 #[cfg(test)]
 mod tests {
     use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
 
-    use crate::{dealer::Dealer, party::generate_parties};
+    use crate::{
+        dealer::Dealer,
+        party::{Party, generate_parties},
+        utils::{verify_batched_encrypted_shares_standalone, verify_encrypted_shares_standalone},
+    };
 
-    use common::utils::precompute_lambda;
+    use common::{
+        generators::Generators,
+        key_proof::KeyProof,
+        polynomial::Polynomial,
+        utils::{batch_decompress_ristretto_points, lagrange_coefficients, precompute_lambda},
+    };
+    use std::sync::Arc;
 
     #[test]
     fn end_to_end() {
@@ -22,25 +33,27 @@ mod tests {
 
         let lambdas = precompute_lambda(N, T);
 
-        let mut parties = generate_parties(&G, &mut rng, N, T);
+        let generators = Arc::new(Generators::new(&G));
+        let mut parties = generate_parties(&generators, &mut rng, N, T);
 
-        let public_keys: Vec<CompressedRistretto> =
-            parties.iter().map(|party| party.public_key.0).collect();
+        let public_keys: Vec<RistrettoPoint> =
+            parties.iter().map(|party| party.public_key.1).collect();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
 
-        let mut dealer = Dealer::new(N, T, &public_keys).unwrap();
-
-        let public_keys: Vec<CompressedRistretto> =
-            parties.iter().map(|party| party.public_key.0).collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+        let mut dealer = Dealer::new(N, T, &generators).unwrap();
 
         for party in &mut parties {
-            let public_keys: Vec<CompressedRistretto> = public_keys
-                .iter()
-                .filter(|pk| &party.public_key.0 != *pk)
-                .copied()
-                .collect();
-
-            party.ingest_public_keys(&public_keys).unwrap();
+            party.ingest_registered_keys(&generators).unwrap();
         }
+        // a party only takes registered keys that include its own
+        let mut outsider = Party::new(&generators, &mut rng, N, T, 1).unwrap();
+        assert!(outsider.ingest_registered_keys(&generators).is_err());
 
         let secret = common::random::random_scalar(&mut rng);
         let (encrypted_shares, (d, z)) =
@@ -60,7 +73,7 @@ mod tests {
                 .iter_mut()
                 .map(|p| {
                     p.decrypt_share().unwrap();
-                    p.dleq_share(&G, &mut rng, &mut hasher, &mut buf).unwrap();
+                    p.dleq_share(&mut rng, &mut hasher, &mut buf).unwrap();
 
                     (
                         p.decrypted_share.unwrap().compress(),
@@ -79,12 +92,119 @@ mod tests {
             p.ingest_decrypted_shares_and_proofs(&decrypted_shares, share_proofs)
                 .unwrap();
 
-            assert!(p.verify_decrypted_shares(&G).unwrap());
+            assert!(p.verify_decrypted_shares().unwrap());
 
             reconstructed_secrets.push(p.reconstruct_secret(&lambdas).unwrap());
         }
         reconstructed_secrets
             .iter()
-            .for_each(|secret| assert_eq!(G * dealer.secret.unwrap(), *secret));
+            .for_each(|reconstructed| assert_eq!(G * secret, *reconstructed));
+    }
+
+    #[test]
+    fn batched() {
+        const N: usize = 16;
+        const T: usize = 7;
+        const L: usize = 8;
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let G: RistrettoPoint = RistrettoPoint::mul_base(&common::random::random_scalar(&mut rng));
+
+        let generators = Arc::new(Generators::new(&G));
+        let mut parties = generate_parties(&generators, &mut rng, N, T);
+        let public_keys: Vec<RistrettoPoint> =
+            parties.iter().map(|party| party.public_key.1).collect();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+        let mut dealer = Dealer::new(N, T, &generators).unwrap();
+
+        let secrets: Vec<Scalar> = (0..L)
+            .map(|_| common::random::random_scalar(&mut rng))
+            .collect();
+        let (encrypted_shares, (d, z)) =
+            dealer.deal_secrets(&mut rng, &mut hasher, &mut buf, &secrets);
+        let rows: Vec<(Vec<CompressedRistretto>, Vec<RistrettoPoint>)> = encrypted_shares
+            .iter()
+            .map(|row| (row.clone(), batch_decompress_ristretto_points(row).unwrap()))
+            .collect();
+
+        assert!(
+            verify_batched_encrypted_shares_standalone(
+                &rows,
+                &public_keys,
+                (&d, &z),
+                T,
+                &mut hasher,
+                &mut buf
+            )
+            .unwrap()
+        );
+        // a zero top coefficient leaves the evaluations alone but exceeds degree t
+        let mut padded = z.coefficients().to_vec();
+        padded.push(Scalar::ZERO);
+        assert!(
+            !verify_batched_encrypted_shares_standalone(
+                &rows,
+                &public_keys,
+                (&d, &Polynomial::from(padded)),
+                T,
+                &mut hasher,
+                &mut buf
+            )
+            .unwrap()
+        );
+        let mut swapped = rows.clone();
+        swapped.swap(2, 5);
+        assert!(
+            !verify_batched_encrypted_shares_standalone(
+                &swapped,
+                &public_keys,
+                (&d, &z),
+                T,
+                &mut hasher,
+                &mut buf
+            )
+            .unwrap()
+        );
+
+        // a batch of one is an ordinary dealing
+        let (single, (d, z)) = dealer.deal_secrets(&mut rng, &mut hasher, &mut buf, &secrets[..1]);
+        assert!(
+            verify_encrypted_shares_standalone(
+                &(
+                    single[0].clone(),
+                    batch_decompress_ristretto_points(&single[0]).unwrap()
+                ),
+                &public_keys,
+                (&d, &z),
+                &mut hasher,
+                &mut buf
+            )
+            .unwrap()
+        );
+
+        // every row decrypts like a single dealing
+        let indices: Vec<usize> = (1..=T + 1).collect();
+        let lambdas = lagrange_coefficients(&indices);
+        for (row, secret) in encrypted_shares.iter().zip(&secrets) {
+            let reconstructed: RistrettoPoint = parties[..=T]
+                .iter_mut()
+                .zip(&lambdas)
+                .map(|(p, lambda)| {
+                    p.ingest_encrypted_shares(row).unwrap();
+                    p.decrypt_share().unwrap();
+                    p.decrypted_share.unwrap() * lambda
+                })
+                .sum();
+            assert_eq!(reconstructed, G * secret);
+        }
     }
 }
@@ -1,6 +1,9 @@
-use common::{random::random_scalar, utils::precompute_lambda};
+use common::{
+    generators::Generators, key_proof::KeyProof, random::random_scalar, utils::precompute_lambda,
+};
 use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto, scalar::Scalar};
 use pi_s_pvss::{dealer::Dealer, party::generate_parties};
+use std::sync::Arc;
 
 fn main() {
     const N: usize = 2048;
@@ -14,25 +17,24 @@ fn main() {
 
     let lambdas = precompute_lambda(N, T);
 
-    let mut parties = generate_parties(&G, &mut rng, N, T);
+    let generators = Arc::new(Generators::new(&G));
+    let mut parties = generate_parties(&generators, &mut rng, N, T);
 
-    let public_keys: Vec<CompressedRistretto> =
-        parties.iter().map(|party| party.public_key.0).collect();
+    let public_keys: Vec<RistrettoPoint> = parties.iter().map(|party| party.public_key.1).collect();
+    let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
 
-    let mut dealer = Dealer::new(N, T, &public_keys).unwrap();
-
-    let public_keys: Vec<CompressedRistretto> =
-        parties.iter().map(|party| party.public_key.0).collect();
+    let generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(&public_keys, &key_proofs)
+            .unwrap(),
+    );
+    let mut dealer = Dealer::new(N, T, &generators).unwrap();
 
     for party in &mut parties {
-        let public_keys: Vec<CompressedRistretto> = public_keys
-            .iter()
-            .filter(|pk| &party.public_key.0 != *pk)
-            .copied()
-            .collect();
-
-        party.ingest_public_keys(&public_keys).unwrap();
+        party.ingest_registered_keys(&generators).unwrap();
     }
+
     let secret = random_scalar(&mut rng);
     let (encrypted_shares, (d, z)) = dealer.deal_secret(&mut rng, &mut hasher, &mut buf, &secret);
 
@@ -50,7 +52,7 @@ fn main() {
             .iter_mut()
             .map(|p| {
                 p.decrypt_share().unwrap();
-                p.dleq_share(&G, &mut rng, &mut hasher, &mut buf).unwrap();
+                p.dleq_share(&mut rng, &mut hasher, &mut buf).unwrap();
 
                 (
                     p.decrypted_share.unwrap().compress(),
@@ -69,7 +71,7 @@ fn main() {
         p.ingest_decrypted_shares_and_proofs(&decrypted_shares, share_proofs)
             .unwrap();
 
-        p.verify_decrypted_shares(&G).unwrap();
+        p.verify_decrypted_shares().unwrap();
 
         reconstructed_secrets.push(p.reconstruct_secret(&lambdas).unwrap());
     }
@@ -7,16 +7,28 @@ use zeroize::Zeroize;
 use common::{
     error::{
         Error,
-        ErrorKind::{CountMismatch, InvalidPararmeterSet, InvalidProof, UninitializedValue},
+        ErrorKind::{
+            CountMismatch, InvalidPararmeterSet, InvalidProof, KeyMismatch, UninitializedValue,
+        },
     },
+    generators::Generators,
+    key_proof::KeyProof,
+    keystore::Keypair,
     polynomial::Polynomial,
+    random::random_scalar,
+    secret::Secret,
     utils::batch_decompress_ristretto_points,
 };
+
+use crate::utils::verify_encrypted_shares_standalone;
 use rayon::prelude::*;
+use std::sync::Arc;
 
 pub struct Party {
-    pub private_key: Scalar,
+    private_key: Secret<Scalar>,
     pub public_key: (CompressedRistretto, RistrettoPoint),
+    /// proof of possession of `private_key`, checked when the public key is registered
+    pub key_proof: KeyProof,
     pub index: usize,
     pub n: usize,
     pub t: usize,
@@ -32,11 +44,13 @@ pub struct Party {
     pub decrypted_shares: Option<Vec<RistrettoPoint>>,
     pub share_proofs: Option<Vec<(Scalar, Scalar)>>,
     pub validated_shares: Vec<usize>,
+    /// G, and the parties' public keys once registered
+    pub generators: Arc<Generators>,
 }
 
 impl Party {
     pub fn new<R>(
-        G: &RistrettoPoint,
+        generators: &Arc<Generators>,
         rng: &mut R,
         n: usize,
         t: usize,
@@ -45,11 +59,35 @@ impl Party {
     where
         R: CryptoRng + RngCore,
     {
-        let private_key = common::random::random_scalar(rng);
-        let public_key = G * private_key;
+        let private_key = random_scalar(rng);
+        Self::from_private_key(generators, rng, private_key, n, t, index)
+    }
+
+    /// Restores a party from a previously generated private key
+    pub fn from_private_key<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        private_key: Scalar,
+        n: usize,
+        t: usize,
+        index: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let private_key = Secret::new(private_key);
+        let public_key = generators.G_table() * private_key.expose();
 
         if index <= n && t < n && t as f32 == ((n - 1) as f32 / 2.0).floor() {
             Ok(Self {
+                key_proof: KeyProof::prove(
+                    rng,
+                    generators.G_table(),
+                    &generators.session(),
+                    index,
+                    private_key.expose(),
+                    &public_key,
+                ),
                 private_key,
                 public_key: (public_key.compress(), public_key),
                 index,
@@ -64,12 +102,40 @@ impl Party {
                 decrypted_shares: None,
                 public_keys: None,
                 validated_shares: vec![],
+                generators: generators.clone(),
             })
         } else {
             Err(InvalidPararmeterSet(n, t as isize, index).into())
         }
     }
 
+    /// Restores a party from a keypair loaded from a keystore, checking that the keys match
+    pub fn from_keypair<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        keypair: &Keypair,
+        n: usize,
+        t: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let party =
+            Self::from_private_key(generators, rng, *keypair.private_key(), n, t, keypair.index)?;
+        if party.public_key.0 != keypair.public_key {
+            return Err(KeyMismatch(keypair.index).into());
+        }
+        Ok(party)
+    }
+
+    pub fn keypair(&self) -> Keypair {
+        Keypair::new(self.index, self.private_key(), self.public_key.0)
+    }
+
+    pub fn private_key(&self) -> &Scalar {
+        self.private_key.expose()
+    }
+
     pub fn ingest_encrypted_shares(
         &mut self,
         encrypted_shares: &[CompressedRistretto],
@@ -93,21 +159,18 @@ impl Party {
             .into())
         }
     }
-    pub fn ingest_public_keys(&mut self, public_keys: &[CompressedRistretto]) -> Result<(), Error> {
-        if public_keys.len() == self.n - 1 {
-            match batch_decompress_ristretto_points(public_keys) {
-                Ok(mut pks) => {
-                    pks.insert(
-                        self.index - 1,
-                        self.public_key.1.compress().decompress().unwrap(),
-                    );
-                    self.public_keys = Some(pks);
-                    Ok(())
-                }
-                Err(x) => Err(x),
-            }
-        } else {
+    /// Takes the parties' public keys from generators they were registered with, so every key
+    /// came with a valid proof of possession. This party's own key must be among them.
+    pub fn ingest_registered_keys(&mut self, generators: &Arc<Generators>) -> Result<(), Error> {
+        let public_keys = generators.public_keys();
+        if public_keys.len() != self.n {
             Err(CountMismatch(self.n, "parties", public_keys.len(), "public_keys").into())
+        } else if public_keys[self.index - 1] != self.public_key.1 {
+            Err(KeyMismatch(self.index).into())
+        } else {
+            self.public_keys = Some(public_keys.to_vec());
+            self.generators = generators.clone();
+            Ok(())
         }
     }
 
@@ -167,31 +230,13 @@ impl Party {
     ) -> Result<bool, Error> {
         match &self.dealer_proof {
             Some((d, z)) => match (&self.encrypted_shares, &self.public_keys) {
-                (Some(encrypted_shares), Some(public_keys)) => {
-                    let shares: Vec<CompressedRistretto> = z
-                        .evaluate_multiply(public_keys, 1)
-                        .1
-                        .par_iter()
-                        .zip(encrypted_shares.1.par_iter())
-                        .map(|(x, enc_share)| (x - (enc_share * d)).compress())
-                        .collect();
-
-                    let flat_vec: Vec<u8> = encrypted_shares
-                        .0
-                        .iter()
-                        .chain(shares.iter())
-                        .flat_map(|x| x.to_bytes())
-                        .collect();
-
-                    hasher.update(&flat_vec);
-
-                    hasher.finalize_xof().fill(buf);
-                    let reconstructed_d = Scalar::from_bytes_mod_order_wide(buf);
-
-                    hasher.reset();
-                    buf.zeroize();
-                    Ok(*d == reconstructed_d)
-                }
+                (Some(encrypted_shares), Some(_)) => verify_encrypted_shares_standalone(
+                    encrypted_shares,
+                    self.generators.public_key_tables(),
+                    (d, z),
+                    hasher,
+                    buf,
+                ),
                 (Some(_), None) => Err(UninitializedValue("party.public_keys").into()),
                 (None, Some(_)) => Err(UninitializedValue("party.encrypted_shares").into()),
                 (None, None) => {
@@ -202,10 +247,10 @@ impl Party {
         }
     }
     pub fn decrypt_share(&mut self) -> Result<(), Error> {
-        let inv_private_key = self.private_key.invert();
+        let inv_private_key = Secret::new(self.private_key.expose().invert());
         match &self.encrypted_share {
             Some(encrypted_share) => {
-                self.decrypted_share = Some(encrypted_share * inv_private_key);
+                self.decrypted_share = Some(encrypted_share * inv_private_key.expose());
                 Ok(())
             }
             None => Err(UninitializedValue("party.encrypted_share").into()),
@@ -213,7 +258,6 @@ impl Party {
     }
     pub fn dleq_share<R>(
         &mut self,
-        G: &RistrettoPoint,
         rng: &mut R,
         hasher: &mut Hasher,
         buf: &mut [u8; 64],
@@ -223,8 +267,8 @@ impl Party {
     {
         match (&self.decrypted_share, &self.encrypted_share) {
             (Some(decrypted_share), Some(encrypted_share)) => {
-                let r = common::random::random_scalar(rng);
-                let c1 = (G * &r).compress();
+                let r = random_scalar(rng);
+                let c1 = (self.generators.G_table() * &r).compress();
                 let c2 = (decrypted_share * r).compress();
 
                 hasher.update(self.public_key.0.as_bytes());
@@ -235,7 +279,7 @@ impl Party {
                 hasher.finalize_xof().fill(buf);
 
                 let d = Scalar::from_bytes_mod_order_wide(buf);
-                let z = r + d * self.private_key;
+                let z = r + d * self.private_key.expose();
 
                 self.share_proof = Some((d, z));
 
@@ -252,7 +296,8 @@ impl Party {
         }
     }
 
-    pub fn verify_decrypted_shares(&mut self, G: &RistrettoPoint) -> Result<bool, Error> {
+    pub fn verify_decrypted_shares(&mut self) -> Result<bool, Error> {
+        let G = self.generators.G_table();
         match (&self.public_keys, &self.encrypted_shares) {
             (Some(public_keys), Some(enc_shares)) => {
                 match (&self.decrypted_shares, &self.share_proofs) {
@@ -319,11 +364,16 @@ impl Party {
     }
 }
 
-pub fn generate_parties<R>(G: &RistrettoPoint, rng: &mut R, n: usize, t: usize) -> Vec<Party>
+pub fn generate_parties<R>(
+    generators: &Arc<Generators>,
+    rng: &mut R,
+    n: usize,
+    t: usize,
+) -> Vec<Party>
 where
     R: CryptoRng + RngCore,
 {
     (1..=n)
-        .map(|i| Party::new(G, rng, n, t, i).unwrap())
+        .map(|i| Party::new(generators, rng, n, t, i).unwrap())
         .collect()
 }
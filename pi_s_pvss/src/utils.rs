@@ -0,0 +1,130 @@
+use blake3::Hasher;
+use common::{
+    error::{Error, ErrorKind::CountMismatch},
+    generators::MulBase,
+    polynomial::Polynomial,
+};
+
+use curve25519_dalek::{
+    Scalar,
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    traits::VartimeMultiscalarMul,
+};
+
+use rayon::prelude::*;
+use zeroize::Zeroize;
+
+pub fn verify_encrypted_shares_standalone<B: MulBase>(
+    encrypted_shares: &(Vec<CompressedRistretto>, Vec<RistrettoPoint>),
+    public_keys: &[B],
+    proof: (&Scalar, &Polynomial),
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+) -> Result<bool, Error> {
+    if encrypted_shares.0.len() == encrypted_shares.1.len() {
+        if public_keys.len() == encrypted_shares.0.len() {
+            let (d, z) = (proof.0, proof.1);
+            let shares: Vec<CompressedRistretto> = z
+                .evaluate_multiply(public_keys, 1)
+                .1
+                .par_iter()
+                .zip(encrypted_shares.1.par_iter())
+                .map(|(x, enc_share)| (x - (enc_share * d)).compress())
+                .collect();
+
+            let flat_vec: Vec<u8> = encrypted_shares
+                .0
+                .iter()
+                .chain(shares.iter())
+                .flat_map(|x| x.to_bytes())
+                .collect();
+
+            hasher.update(&flat_vec);
+
+            hasher.finalize_xof().fill(buf);
+            let reconstructed_d = Scalar::from_bytes_mod_order_wide(buf);
+
+            hasher.reset();
+            buf.zeroize();
+            Ok(*d == reconstructed_d)
+        } else {
+            Err(CountMismatch(
+                public_keys.len(),
+                "public_keys",
+                encrypted_shares.0.len(),
+                "encrypted_shares",
+            )
+            .into())
+        }
+    } else {
+        Err(CountMismatch(
+            encrypted_shares.0.len(),
+            "encrypted_shares.0",
+            encrypted_shares.1.len(),
+            "encrypted_shares.1",
+        )
+        .into())
+    }
+}
+
+/// Checks a `Dealer::deal_secrets` dealing, one row of encrypted shares per secret. The rows are
+/// folded with powers of d, so each party costs one multiscalar multiplication whatever the
+/// number of secrets.
+pub fn verify_batched_encrypted_shares_standalone<B: MulBase>(
+    encrypted_shares: &[(Vec<CompressedRistretto>, Vec<RistrettoPoint>)],
+    public_keys: &[B],
+    proof: (&Scalar, &Polynomial),
+    t: usize,
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+) -> Result<bool, Error> {
+    for (compressed, points) in encrypted_shares {
+        if compressed.len() != points.len() || points.len() != public_keys.len() {
+            return Err(CountMismatch(
+                public_keys.len(),
+                "public_keys",
+                points.len(),
+                "encrypted_shares",
+            )
+            .into());
+        }
+    }
+    let (d, z) = (proof.0, proof.1);
+    // z must have degree t, or the shares need not lie on a degree t polynomial
+    if z.len() != t + 1 {
+        return Ok(false);
+    }
+    let weights: Vec<Scalar> = std::iter::successors(Some(-d), |weight| Some(weight * d))
+        .take(encrypted_shares.len())
+        .collect();
+
+    let shares: Vec<CompressedRistretto> = z
+        .evaluate_multiply(public_keys, 1)
+        .1
+        .par_iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let folded = RistrettoPoint::vartime_multiscalar_mul(
+                &weights,
+                encrypted_shares.iter().map(|(_, points)| points[i]),
+            );
+            (x + folded).compress()
+        })
+        .collect();
+
+    let flat_vec: Vec<u8> = encrypted_shares
+        .iter()
+        .flat_map(|(compressed, _)| compressed.iter())
+        .chain(shares.iter())
+        .flat_map(|x| x.to_bytes())
+        .collect();
+
+    hasher.update(&flat_vec);
+
+    hasher.finalize_xof().fill(buf);
+    let reconstructed_d = Scalar::from_bytes_mod_order_wide(buf);
+
+    hasher.reset();
+    buf.zeroize();
+    Ok(*d == reconstructed_d)
+}
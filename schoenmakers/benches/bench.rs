@@ -1,7 +1,13 @@
-use common::{random::random_scalar, utils::precompute_lambda};
+use common::{
+    generators::Generators,
+    key_proof::KeyProof,
+    random::random_scalar,
+    utils::{batch_decompress_ristretto_points, precompute_lambda},
+};
 use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
 use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto};
 use schoenmakers::{dealer::Dealer, party::generate_parties};
+use std::sync::Arc;
 
 fn pvss(c: &mut Criterion) {
     for (n, t) in [
@@ -25,12 +31,24 @@ fn pvss(c: &mut Criterion) {
 
         let lambdas = precompute_lambda(n, t);
 
-        let mut parties = generate_parties(G, H, &mut rng, n, t);
+        let generators = Arc::new(Generators::new(&G).with_H(&H));
+        let mut parties = generate_parties(&generators, &mut rng, n, t);
 
         let public_keys: Vec<CompressedRistretto> =
             parties.iter().map(|party| party.public_key.0).collect();
 
-        let mut dealer = Dealer::new(H, n, t, &public_keys).unwrap();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+
+        let dealer_generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(
+                    &batch_decompress_ristretto_points(&public_keys).unwrap(),
+                    &key_proofs,
+                )
+                .unwrap(),
+        );
+        let mut dealer = Dealer::new(n, t, &dealer_generators).unwrap();
 
         let public_keys: Vec<CompressedRistretto> =
             parties.iter().map(|party| party.public_key.0).collect();
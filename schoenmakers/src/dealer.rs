@@ -1,47 +1,42 @@
 use blake3::Hasher;
 use common::{
     error::{Error, ErrorKind::CountMismatch},
+    generators::Generators,
     polynomial::Polynomial,
     random::random_scalar,
-    utils::batch_decompress_ristretto_points,
+    secret::Secret,
 };
-use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use curve25519_dalek::{Scalar, ristretto::CompressedRistretto};
 use rand::{CryptoRng, RngCore};
 use rayon::prelude::*;
+use std::sync::Arc;
 use zeroize::Zeroize;
 
 pub struct Dealer {
-    pub H: RistrettoPoint,
     pub n: usize,
     pub t: usize,
-    pub public_keys: Vec<RistrettoPoint>,
-    pub(crate) secret: Option<Scalar>,
+    /// H and the parties' public keys
+    pub generators: Arc<Generators>,
 }
 
 impl Dealer {
-    pub fn new(
-        H: RistrettoPoint,
-        n: usize,
-        t: usize,
-        public_keys: &[CompressedRistretto],
-    ) -> Result<Self, Error> {
+    pub fn new(n: usize, t: usize, generators: &Arc<Generators>) -> Result<Self, Error> {
+        let public_keys = generators.public_keys();
         if public_keys.len() != n {
             return Err(CountMismatch(n, "parties", public_keys.len(), "public keys").into());
         }
-        match batch_decompress_ristretto_points(public_keys) {
-            Ok(pks) => Ok(Self {
-                n,
-                t,
-                public_keys: pks.par_iter().map(|pk| *pk).collect(),
-                H,
-                secret: None,
-            }),
-            Err(x) => Err(x),
-        }
+        generators.H()?;
+
+        Ok(Self {
+            n,
+            t,
+            generators: generators.clone(),
+        })
     }
 
     pub(crate) fn generate_commitments(&self, f: &Polynomial) -> Vec<CompressedRistretto> {
-        f.mul_with_point_compress(&self.H)
+        // checked in Dealer::new
+        f.mul_with_point_compress(self.generators.H_table().unwrap())
     }
 
     /// Proof of correct construction of encrypted shares
@@ -57,24 +52,25 @@ impl Dealer {
         R: CryptoRng + RngCore,
     {
         let randomizer_vals: Vec<Scalar> = (0..self.n).map(|_| random_scalar(rng)).collect();
+        let H = self.generators.H_table().unwrap();
 
         let flat: Vec<u8> = evals
             // gen_eval_str
             .par_iter()
-            .flat_map(|eval| (self.H * eval).compress().to_bytes())
+            .flat_map(|eval| (H * eval).compress().to_bytes())
             // enc_eval_str
             .chain(enc_shares.par_iter().flat_map(|x| x.to_bytes()))
             // a1_str
             .chain(
                 randomizer_vals
                     .par_iter()
-                    .flat_map(|r| (self.H * r).compress().to_bytes()),
+                    .flat_map(|r| (H * r).compress().to_bytes()),
             )
             //a2_str
             .chain(
                 randomizer_vals
                     .par_iter()
-                    .zip(self.public_keys.par_iter())
+                    .zip(self.generators.public_key_tables().par_iter())
                     .flat_map(|(r, public_key)| (public_key * r).compress().to_bytes()),
             )
             .collect();
@@ -111,21 +107,46 @@ impl Dealer {
     where
         R: CryptoRng + RngCore,
     {
-        let f = Polynomial::sample_set_f0(self.t, rng, &secret);
-        self.secret = Some(secret);
+        let (encrypted_shares, proof, commitments, _) =
+            self.deal_secret_with_shares(rng, hasher, buf, secret);
+        (encrypted_shares, proof, commitments)
+    }
+
+    /// Same as `deal_secret`, and also returns the scalar shares p(1), ..., p(n). The dealer does
+    /// not keep them.
+    #[allow(clippy::type_complexity)]
+    pub fn deal_secret_with_shares<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        secret: Scalar,
+    ) -> (
+        Vec<CompressedRistretto>,
+        (Scalar, Vec<Scalar>),
+        Vec<CompressedRistretto>,
+        Secret<Vec<Scalar>>,
+    )
+    where
+        R: CryptoRng + RngCore,
+    {
+        let f = Secret::new(Polynomial::sample_set_f0(self.t, rng, &secret));
 
-        let commitments = self.generate_commitments(&f);
+        let commitments = self.generate_commitments(f.expose());
 
         // eval [1..n+1], eval_i * pk_i
-        let (evals, enc_evals) = f.evaluate_multiply(&self.public_keys, 1);
+        let (evals, enc_evals) = f
+            .expose()
+            .evaluate_multiply(self.generators.public_key_tables(), 1);
+        let evals = Secret::new(evals);
 
         let compressed_enc_evals = enc_evals
             .par_iter()
             .map(|enc_eval| enc_eval.compress())
             .collect();
 
-        let (c, r_vals) = self.dleq_pol(&evals, &compressed_enc_evals, hasher, rng, buf);
+        let (c, r_vals) = self.dleq_pol(evals.expose(), &compressed_enc_evals, hasher, rng, buf);
 
-        (compressed_enc_evals, (c, r_vals), commitments)
+        (compressed_enc_evals, (c, r_vals), commitments, evals)
     }
 }
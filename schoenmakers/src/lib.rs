@@ -4,8 +4,14 @@ pub mod utils;
 
 #[cfg(test)]
 mod tests {
-    use common::{random::random_scalar, utils::precompute_lambda};
+    use common::{
+        generators::Generators,
+        key_proof::KeyProof,
+        random::random_scalar,
+        utils::{batch_decompress_ristretto_points, precompute_lambda},
+    };
     use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+    use std::sync::Arc;
 
     use crate::{dealer::Dealer, party::generate_parties};
 
@@ -27,12 +33,24 @@ mod tests {
 
         let lambdas = precompute_lambda(N, T);
 
-        let mut parties = generate_parties(G, H, &mut rng, N, T);
+        let generators = Arc::new(Generators::new(&G).with_H(&H));
+        let mut parties = generate_parties(&generators, &mut rng, N, T);
 
         let public_keys: Vec<CompressedRistretto> =
             parties.iter().map(|party| party.public_key.0).collect();
 
-        let mut dealer = Dealer::new(H, N, T, &public_keys).unwrap();
+        let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+
+        let dealer_generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(
+                    &batch_decompress_ristretto_points(&public_keys).unwrap(),
+                    &key_proofs,
+                )
+                .unwrap(),
+        );
+        let mut dealer = Dealer::new(N, T, &dealer_generators).unwrap();
 
         let public_keys: Vec<CompressedRistretto> =
             parties.iter().map(|party| party.public_key.0).collect();
@@ -90,6 +108,6 @@ mod tests {
         }
         reconstructed_secrets
             .iter()
-            .for_each(|secret| assert_eq!((G * &dealer.secret.unwrap()), *secret));
+            .for_each(|reconstructed| assert_eq!(G * secret, *reconstructed));
     }
 }
@@ -1,5 +1,11 @@
-use common::{random::random_scalar, utils::precompute_lambda};
+use common::{
+    generators::Generators,
+    key_proof::KeyProof,
+    random::random_scalar,
+    utils::{batch_decompress_ristretto_points, precompute_lambda},
+};
 use curve25519_dalek::{RistrettoPoint, ristretto::CompressedRistretto, scalar::Scalar};
+use std::sync::Arc;
 
 use schoenmakers::{dealer::Dealer, party::generate_parties};
 
@@ -19,12 +25,24 @@ fn main() {
 
     let lambdas = precompute_lambda(N, T);
 
-    let mut parties = generate_parties(G, H, &mut rng, N, T);
+    let generators = Arc::new(Generators::new(&G).with_H(&H));
+    let mut parties = generate_parties(&generators, &mut rng, N, T);
 
     let public_keys: Vec<CompressedRistretto> =
         parties.iter().map(|party| party.public_key.0).collect();
 
-    let mut dealer = Dealer::new(G, N, T, &public_keys).unwrap();
+    let key_proofs: Vec<KeyProof> = parties.iter().map(|party| party.key_proof).collect();
+
+    let dealer_generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(
+                &batch_decompress_ristretto_points(&public_keys).unwrap(),
+                &key_proofs,
+            )
+            .unwrap(),
+    );
+    let mut dealer = Dealer::new(N, T, &dealer_generators).unwrap();
 
     for party in &mut parties {
         let public_keys: Vec<CompressedRistretto> = public_keys
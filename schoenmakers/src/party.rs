@@ -1,28 +1,34 @@
 use blake3::Hasher;
-use curve25519_dalek::{ristretto::CompressedRistretto, RistrettoPoint, Scalar};
 use common::{
     error::{
         Error,
-        ErrorKind::{CountMismatch, InvalidPararmeterSet, InvalidProof, UninitializedValue},
+        ErrorKind::{
+            CountMismatch, InvalidPararmeterSet, InvalidProof, KeyMismatch, UninitializedValue,
+        },
     },
+    generators::Generators,
+    key_proof::KeyProof,
+    keystore::Keypair,
     random::random_scalar,
+    secret::Secret,
     utils::batch_decompress_ristretto_points,
 };
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
 use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
-use crate::{
-    utils::{verify_encrypted_shares_standalone},
-};
+use crate::utils::verify_encrypted_shares_standalone;
 use rayon::prelude::*;
+use std::sync::Arc;
 
-#[derive(Clone)]
 pub struct Party {
-    pub G: RistrettoPoint,
-    pub H: RistrettoPoint,
+    /// G and H
+    pub generators: Arc<Generators>,
 
-    pub private_key: Scalar,
+    private_key: Secret<Scalar>,
     pub public_key: (CompressedRistretto, RistrettoPoint),
+    /// proof of possession of `private_key`, checked when the public key is registered
+    pub key_proof: KeyProof,
     pub index: usize,
     pub n: usize,
     pub t: usize,
@@ -42,8 +48,7 @@ pub struct Party {
 
 impl Party {
     pub fn new<R>(
-        G: RistrettoPoint,
-        H: RistrettoPoint,
+        generators: &Arc<Generators>,
         rng: &mut R,
         n: usize,
         t: usize,
@@ -53,12 +58,37 @@ impl Party {
         R: CryptoRng + RngCore,
     {
         let private_key = random_scalar(rng);
-        let public_key = G * private_key;
+        Self::from_private_key(generators, rng, private_key, n, t, index)
+    }
+
+    /// Restores a party from a previously generated private key
+    pub fn from_private_key<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        private_key: Scalar,
+        n: usize,
+        t: usize,
+        index: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        generators.H()?;
+
+        let private_key = Secret::new(private_key);
+        let public_key = generators.G_table() * private_key.expose();
 
         if index <= n && t < n && t as f32 == ((n - 1) as f32 / 2.0).floor() {
             Ok(Self {
-                G,
-                H,
+                key_proof: KeyProof::prove(
+                    rng,
+                    generators.G_table(),
+                    &generators.session(),
+                    index,
+                    private_key.expose(),
+                    &public_key,
+                ),
+                generators: generators.clone(),
                 private_key,
                 public_key: (public_key.compress(), public_key),
                 index,
@@ -80,6 +110,33 @@ impl Party {
         }
     }
 
+    /// Restores a party from a keypair loaded from a keystore, checking that the keys match
+    pub fn from_keypair<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        keypair: &Keypair,
+        n: usize,
+        t: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let party =
+            Self::from_private_key(generators, rng, *keypair.private_key(), n, t, keypair.index)?;
+        if party.public_key.0 != keypair.public_key {
+            return Err(KeyMismatch(keypair.index).into());
+        }
+        Ok(party)
+    }
+
+    pub fn keypair(&self) -> Keypair {
+        Keypair::new(self.index, self.private_key(), self.public_key.0)
+    }
+
+    pub fn private_key(&self) -> &Scalar {
+        self.private_key.expose()
+    }
+
     pub fn ingest_encrypted_shares(
         &mut self,
         encrypted_shares: &[CompressedRistretto],
@@ -202,7 +259,7 @@ impl Party {
                 match (&self.encrypted_shares, &self.public_keys) {
                     (Some(encrypted_shares), Some(public_keys)) => {
                         verify_encrypted_shares_standalone(
-                            &self.H,
+                            self.generators.H()?,
                             hasher,
                             buf,
                             encrypted_shares,
@@ -229,10 +286,10 @@ impl Party {
     }
 
     pub fn decrypt_share(&mut self) -> Result<(), Error> {
-        let inv_private_key = self.private_key.invert();
+        let inv_private_key = Secret::new(self.private_key.expose().invert());
         match &self.encrypted_share {
             Some(encrypted_share) => {
-                self.decrypted_share = Some(encrypted_share * inv_private_key);
+                self.decrypted_share = Some(encrypted_share * inv_private_key.expose());
                 Ok(())
             }
             None => Err(UninitializedValue("party.encrypted_share").into()),
@@ -254,16 +311,16 @@ impl Party {
                 hasher.update(self.public_key.0.as_bytes());
                 hasher.update(encrypted_share.compress().as_bytes());
 
-                hasher.update((self.G * r).compress().as_bytes());
+                hasher.update((self.generators.G_table() * &r).compress().as_bytes());
                 hasher.update((decrypted_share * r).compress().as_bytes());
 
                 hasher.finalize_xof().fill(buf);
 
                 let d = Scalar::from_bytes_mod_order_wide(buf);
-                let z = r + d * self.private_key;
+                let z = r + d * self.private_key.expose();
 
                 self.share_proof = Some((d, z));
-                
+
                 hasher.reset();
                 buf.zeroize();
 
@@ -297,7 +354,7 @@ impl Party {
                                     hasher.update(public_key.compress().as_bytes());
                                     hasher.update(enc_share.compress().as_bytes());
 
-                                    hasher.update(((z * self.G) - (d * public_key)).compress().as_bytes());
+                                    hasher.update(((self.generators.G_table() * z) - (d * public_key)).compress().as_bytes());
                                     hasher.update(((z * dec_share) - (d * enc_share)).compress().as_bytes());
                                     hasher.finalize_xof().fill(buf);
 
@@ -335,13 +392,12 @@ impl Party {
                 .take(self.t + 1)
                 .map(|share_index| lambdas[*share_index] * dec_shares[*share_index])
                 .sum()),
-                None => Err(UninitializedValue("party.decrypted_shares").into()),
+            None => Err(UninitializedValue("party.decrypted_shares").into()),
         }
     }
 }
 pub fn generate_parties<R>(
-    G: RistrettoPoint,
-    H: RistrettoPoint,
+    generators: &Arc<Generators>,
     rng: &mut R,
     n: usize,
     t: usize,
@@ -350,6 +406,6 @@ where
     R: CryptoRng + RngCore,
 {
     (1..=n)
-        .map(|i| Party::new(G, H, rng, n, t, i).unwrap())
+        .map(|i| Party::new(generators, rng, n, t, i).unwrap())
         .collect()
 }
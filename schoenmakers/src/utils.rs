@@ -1,5 +1,5 @@
 use blake3::Hasher;
-use common::error::Error;
+use common::{error::Error, generators::MulBase};
 
 use curve25519_dalek::{
     Scalar,
@@ -11,12 +11,12 @@ use num_bigint::BigUint;
 use rayon::prelude::*;
 use zeroize::Zeroize;
 
-pub fn verify_encrypted_shares_standalone(
-    H: &RistrettoPoint,
+pub fn verify_encrypted_shares_standalone<B: MulBase>(
+    H: &B,
     hasher: &mut Hasher,
     buf: &mut [u8; 64],
     encrypted_shares: &(Vec<CompressedRistretto>, Vec<RistrettoPoint>),
-    public_keys: &Vec<RistrettoPoint>,
+    public_keys: &[B],
     dealer_commitments: &Vec<RistrettoPoint>,
     proof: (&Scalar, &Vec<Scalar>),
     n: usize,
@@ -58,8 +58,8 @@ pub fn verify_encrypted_shares_standalone(
             (
                 xi.compress(),
                 (
-                    ((H * z) + (xi * d)).compress(),
-                    ((public_key * z) + encrypted_share * d).compress(),
+                    (H.mul_base(z) + (xi * d)).compress(),
+                    (public_key.mul_base(z) + encrypted_share * d).compress(),
                 ),
             )
         })
@@ -0,0 +1,208 @@
+use blake3::Hasher;
+use chacha20poly1305::{
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+use common::{
+    error::{
+        Error,
+        ErrorKind::{TooFewDecryptedShares, UndecryptablePayload},
+    },
+    generators::MulBase,
+    polynomial::Polynomial,
+    secret::Secret,
+    utils::{batch_decompress_ristretto_points, lagrange_coefficients},
+};
+use curve25519_dalek::{RistrettoPoint, Scalar, ristretto::CompressedRistretto};
+use pi_s_pvss::utils::verify_encrypted_shares_standalone;
+use zeroize::Zeroize;
+
+pub(crate) const NONCE_LEN: usize = 12;
+
+/// "timelock key" | release round | G * s
+pub(crate) fn payload_key(release_round: u64, secret: &RistrettoPoint) -> Secret<[u8; 32]> {
+    let mut hasher = Hasher::new();
+    hasher.update(b"timelock key");
+    hasher.update(&release_round.to_le_bytes());
+    hasher.update(secret.compress().as_bytes());
+
+    Secret::new(*hasher.finalize().as_bytes())
+}
+
+/// "timelock release" | round | pk | encrypted share | decrypted share | G * r | decrypted share * r
+pub(crate) fn challenge(
+    hasher: &mut Hasher,
+    buf: &mut [u8; 64],
+    round: u64,
+    public_key: &RistrettoPoint,
+    encrypted_share: &RistrettoPoint,
+    decrypted_share: &RistrettoPoint,
+    commitments: (&RistrettoPoint, &RistrettoPoint),
+) -> Scalar {
+    hasher.update(b"timelock release");
+    hasher.update(&round.to_le_bytes());
+    hasher.update(public_key.compress().as_bytes());
+    hasher.update(encrypted_share.compress().as_bytes());
+    hasher.update(decrypted_share.compress().as_bytes());
+
+    hasher.update(commitments.0.compress().as_bytes());
+    hasher.update(commitments.1.compress().as_bytes());
+
+    hasher.finalize_xof().fill(buf);
+    let d = Scalar::from_bytes_mod_order_wide(buf);
+
+    hasher.reset();
+    buf.zeroize();
+
+    d
+}
+
+/// A payload encrypted under a key derived from G * s, with s dealt to the committee, which
+/// releases its shares once `release_round` is reached
+#[derive(Clone)]
+pub struct Capsule {
+    pub release_round: u64,
+    pub encrypted_shares: Vec<CompressedRistretto>,
+    pub proof: (Scalar, Polynomial),
+    pub nonce: [u8; NONCE_LEN],
+    /// the encrypted payload and the poly1305 tag
+    pub ciphertext: Vec<u8>,
+}
+
+impl Capsule {
+    /// release round | encrypted shares, authenticated together with the payload
+    pub(crate) fn associated_data(
+        release_round: u64,
+        encrypted_shares: &[CompressedRistretto],
+    ) -> Vec<u8> {
+        release_round
+            .to_le_bytes()
+            .into_iter()
+            .chain(encrypted_shares.iter().flat_map(|share| share.to_bytes()))
+            .collect()
+    }
+
+    /// Checks the dealing, the payload can only be checked once the key is recovered
+    pub fn verify<B: MulBase>(
+        &self,
+        public_keys: &[B],
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let (d, z) = &self.proof;
+        if z.len() != t + 1 {
+            return false;
+        }
+        let Ok(encrypted_shares) = batch_decompress_ristretto_points(&self.encrypted_shares) else {
+            return false;
+        };
+
+        verify_encrypted_shares_standalone(
+            &(self.encrypted_shares.clone(), encrypted_shares),
+            public_keys,
+            (d, z),
+            hasher,
+            buf,
+        )
+        .unwrap_or(false)
+    }
+
+    /// Interpolates G * s from the first t+1 valid shares of distinct parties and decrypts the
+    /// payload
+    pub fn open(
+        &self,
+        G: &RistrettoPoint,
+        public_keys: &[RistrettoPoint],
+        t: usize,
+        shares: &[ReleaseShare],
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Vec<u8>, Error> {
+        let mut valid: Vec<(usize, RistrettoPoint)> = Vec::with_capacity(t + 1);
+        for share in shares {
+            if valid.len() > t {
+                break;
+            }
+            if valid.iter().all(|(index, _)| *index != share.index)
+                && share.verify(G, public_keys, self, hasher, buf)
+            {
+                // checked in ReleaseShare::verify
+                valid.push((share.index, share.decrypted_share.decompress().unwrap()));
+            }
+        }
+        if valid.len() <= t {
+            return Err(TooFewDecryptedShares(valid.len(), t + 1).into());
+        }
+
+        let indices: Vec<usize> = valid.iter().map(|(index, _)| *index).collect();
+        let secret: RistrettoPoint = lagrange_coefficients(&indices)
+            .iter()
+            .zip(&valid)
+            .map(|(lambda, (_, share))| lambda * share)
+            .sum();
+
+        let key = payload_key(self.release_round, &secret);
+        ChaCha20Poly1305::new(Key::from_slice(key.expose()))
+            .decrypt(
+                Nonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &Self::associated_data(self.release_round, &self.encrypted_shares),
+                },
+            )
+            .map_err(|_| UndecryptablePayload.into())
+    }
+}
+
+/// A party's decrypted share of a capsule, released in `round`
+#[derive(Clone)]
+pub struct ReleaseShare {
+    /// 1-based index of the releasing party
+    pub index: usize,
+    /// as attested by the releasing party, never before the capsule's release round
+    pub round: u64,
+    pub decrypted_share: CompressedRistretto,
+    /// DLEQ proof against the party's public key, its challenge binds `round`
+    pub proof: (Scalar, Scalar),
+}
+
+impl ReleaseShare {
+    pub fn verify(
+        &self,
+        G: &RistrettoPoint,
+        public_keys: &[RistrettoPoint],
+        capsule: &Capsule,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        if self.round < capsule.release_round || self.index == 0 || self.index > public_keys.len() {
+            return false;
+        }
+        let (Some(encrypted_share), Some(decrypted_share)) = (
+            capsule
+                .encrypted_shares
+                .get(self.index - 1)
+                .and_then(|share| share.decompress()),
+            self.decrypted_share.decompress(),
+        ) else {
+            return false;
+        };
+
+        let public_key = &public_keys[self.index - 1];
+        let (d, z) = &self.proof;
+
+        *d == challenge(
+            hasher,
+            buf,
+            self.round,
+            public_key,
+            &encrypted_share,
+            &decrypted_share,
+            (
+                &(G * z - public_key * d),
+                &(decrypted_share * z - encrypted_share * d),
+            ),
+        )
+    }
+}
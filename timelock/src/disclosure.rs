@@ -0,0 +1,106 @@
+use blake3::Hasher;
+use common::{
+    error::{
+        Error,
+        ErrorKind::{InvalidProof, TooEarly},
+    },
+    generators::Generators,
+};
+use std::sync::Arc;
+
+use crate::{
+    capsule::{Capsule, ReleaseShare},
+    transcript::Transcript,
+};
+
+/// Follows one capsule from publication to disclosure: the round advances, and once it reaches
+/// the release round the committee's shares are collected until t+1 of them open the payload
+pub struct Disclosure {
+    pub t: usize,
+    /// G and the committee's registered public keys
+    pub generators: Arc<Generators>,
+    pub capsule: Capsule,
+    pub round: u64,
+    /// by increasing party index
+    pub shares: Vec<ReleaseShare>,
+}
+
+impl Disclosure {
+    /// Only takes a capsule whose dealing verifies
+    pub fn new(
+        t: usize,
+        generators: &Arc<Generators>,
+        capsule: Capsule,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> Result<Self, Error> {
+        if !capsule.verify(generators.public_key_tables(), t, hasher, buf) {
+            return Err(InvalidProof("capsule dealing".into()).into());
+        }
+
+        Ok(Self {
+            t,
+            generators: generators.clone(),
+            capsule,
+            round: 0,
+            shares: vec![],
+        })
+    }
+
+    /// The round never goes back
+    pub fn advance(&mut self, round: u64) {
+        self.round = self.round.max(round);
+    }
+
+    /// Keeps a party's share only if the release round is reached, it is not claimed for a later
+    /// round and its proof holds
+    pub fn ingest_share(
+        &mut self,
+        share: ReleaseShare,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        if self.round < self.capsule.release_round || share.round > self.round {
+            return false;
+        }
+        let Err(slot) = self
+            .shares
+            .binary_search_by_key(&share.index, |share| share.index)
+        else {
+            return false;
+        };
+        let holds = share.verify(
+            self.generators.G(),
+            self.generators.public_keys(),
+            &self.capsule,
+            hasher,
+            buf,
+        );
+        if holds {
+            self.shares.insert(slot, share);
+        }
+        holds
+    }
+
+    /// Opens the payload from the first t+1 shares
+    pub fn finalize(&self, hasher: &mut Hasher, buf: &mut [u8; 64]) -> Result<Transcript, Error> {
+        if self.round < self.capsule.release_round {
+            return Err(TooEarly(self.round, self.capsule.release_round).into());
+        }
+        let shares = self.shares[..self.shares.len().min(self.t + 1)].to_vec();
+        let payload = self.capsule.open(
+            self.generators.G(),
+            self.generators.public_keys(),
+            self.t,
+            &shares,
+            hasher,
+            buf,
+        )?;
+
+        Ok(Transcript {
+            capsule: self.capsule.clone(),
+            shares,
+            payload,
+        })
+    }
+}
@@ -0,0 +1,115 @@
+#![allow(non_snake_case)]
+
+pub mod capsule;
+pub mod disclosure;
+pub mod publisher;
+pub mod transcript;
+pub mod trustee;
+
+#[cfg(test)]
+mod tests {
+    use common::{generators::Generators, key_proof::KeyProof, random::random_scalar};
+    use curve25519_dalek::RistrettoPoint;
+    use std::sync::Arc;
+
+    use crate::{disclosure::Disclosure, publisher::Publisher, trustee::Trustee};
+
+    #[test]
+    fn timelock() {
+        const N: usize = 7;
+        const T: usize = 3;
+        const RELEASE_ROUND: u64 = 5;
+
+        let mut rng = rand::rng();
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64];
+
+        let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+
+        let generators = Arc::new(Generators::new(&G));
+        let mut trustees: Vec<Trustee> = (1..=N)
+            .map(|i| Trustee::new(&generators, &mut rng, N, T, i).unwrap())
+            .collect();
+        let public_keys: Vec<RistrettoPoint> = trustees
+            .iter()
+            .map(|trustee| trustee.party.public_key.1)
+            .collect();
+        let key_proofs: Vec<KeyProof> = trustees
+            .iter()
+            .map(|trustee| trustee.party.key_proof)
+            .collect();
+        let generators = Arc::new(
+            (*generators)
+                .clone()
+                .with_public_keys(&public_keys, &key_proofs)
+                .unwrap(),
+        );
+
+        let payload = b"opened after round five".to_vec();
+        let mut publisher = Publisher::new(N, T, &generators).unwrap();
+        let capsule = publisher
+            .seal(&mut rng, &mut hasher, &mut buf, RELEASE_ROUND, &payload)
+            .unwrap();
+
+        // the proof no longer matches the shares
+        let mut tampered = capsule.clone();
+        tampered.encrypted_shares.swap(0, 1);
+        assert!(Disclosure::new(T, &generators, tampered, &mut hasher, &mut buf).is_err());
+        let mut disclosure =
+            Disclosure::new(T, &generators, capsule.clone(), &mut hasher, &mut buf).unwrap();
+
+        assert!(
+            trustees[0]
+                .release(&mut rng, &mut hasher, &mut buf, &capsule, RELEASE_ROUND - 1)
+                .is_err()
+        );
+        let mut shares: Vec<_> = trustees
+            .iter_mut()
+            .take(T + 1)
+            .map(|trustee| {
+                trustee
+                    .release(&mut rng, &mut hasher, &mut buf, &capsule, RELEASE_ROUND)
+                    .unwrap()
+            })
+            .collect();
+
+        // too early for the collector
+        disclosure.advance(RELEASE_ROUND - 1);
+        assert!(!disclosure.ingest_share(shares[0].clone(), &mut hasher, &mut buf));
+        assert!(disclosure.finalize(&mut hasher, &mut buf).is_err());
+
+        disclosure.advance(RELEASE_ROUND);
+        // claimed for a round the collector has not reached, or before the release round
+        let mut early = shares[0].clone();
+        early.round = RELEASE_ROUND + 1;
+        assert!(!disclosure.ingest_share(early.clone(), &mut hasher, &mut buf));
+        early.round = RELEASE_ROUND - 1;
+        assert!(!disclosure.ingest_share(early, &mut hasher, &mut buf));
+
+        let last = shares.pop().unwrap();
+        for share in shares {
+            assert!(disclosure.ingest_share(share, &mut hasher, &mut buf));
+        }
+        assert!(disclosure.finalize(&mut hasher, &mut buf).is_err());
+        assert!(disclosure.ingest_share(last, &mut hasher, &mut buf));
+
+        let transcript = disclosure.finalize(&mut hasher, &mut buf).unwrap();
+        assert_eq!(transcript.payload, payload);
+        assert!(transcript.verify(&generators, T, &mut hasher, &mut buf));
+
+        let mut tampered = transcript.clone();
+        tampered.payload[0] ^= 1;
+        assert!(!tampered.verify(&generators, T, &mut hasher, &mut buf));
+        let mut tampered = transcript.clone();
+        tampered.shares[0].round = RELEASE_ROUND - 1;
+        assert!(!tampered.verify(&generators, T, &mut hasher, &mut buf));
+        // the round is bound by the proof, so a share cannot be moved to a later round either
+        let mut tampered = transcript.clone();
+        tampered.shares[0].round = RELEASE_ROUND + 1;
+        assert!(!tampered.verify(&generators, T, &mut hasher, &mut buf));
+        // the ciphertext is authenticated, so a tampered one does not open
+        let mut tampered = transcript;
+        tampered.capsule.ciphertext[0] ^= 1;
+        assert!(!tampered.verify(&generators, T, &mut hasher, &mut buf));
+    }
+}
@@ -0,0 +1,68 @@
+#![allow(non_snake_case)]
+
+use common::{generators::Generators, key_proof::KeyProof, random::random_scalar};
+use curve25519_dalek::RistrettoPoint;
+use std::sync::Arc;
+use timelock::{disclosure::Disclosure, publisher::Publisher, trustee::Trustee};
+
+fn main() {
+    const N: usize = 64;
+    const T: usize = 31;
+    const RELEASE_ROUND: u64 = 3;
+
+    let mut rng = rand::rng();
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = [0u8; 64];
+
+    let G: RistrettoPoint = RistrettoPoint::mul_base(&random_scalar(&mut rng));
+
+    let generators = Arc::new(Generators::new(&G));
+    let mut trustees: Vec<Trustee> = (1..=N)
+        .map(|i| Trustee::new(&generators, &mut rng, N, T, i).unwrap())
+        .collect();
+    let public_keys: Vec<RistrettoPoint> = trustees
+        .iter()
+        .map(|trustee| trustee.party.public_key.1)
+        .collect();
+    let key_proofs: Vec<KeyProof> = trustees
+        .iter()
+        .map(|trustee| trustee.party.key_proof)
+        .collect();
+    let generators = Arc::new(
+        (*generators)
+            .clone()
+            .with_public_keys(&public_keys, &key_proofs)
+            .unwrap(),
+    );
+
+    let mut publisher = Publisher::new(N, T, &generators).unwrap();
+    let capsule = publisher
+        .seal(
+            &mut rng,
+            &mut hasher,
+            &mut buf,
+            RELEASE_ROUND,
+            b"disclosed by the committee",
+        )
+        .unwrap();
+    let mut disclosure =
+        Disclosure::new(T, &generators, capsule.clone(), &mut hasher, &mut buf).unwrap();
+
+    for round in 1..=RELEASE_ROUND {
+        disclosure.advance(round);
+        // trustees refuse until the release round
+        for trustee in &mut trustees {
+            if let Ok(share) = trustee.release(&mut rng, &mut hasher, &mut buf, &capsule, round) {
+                disclosure.ingest_share(share, &mut hasher, &mut buf);
+            }
+        }
+    }
+
+    let transcript = disclosure.finalize(&mut hasher, &mut buf).unwrap();
+    assert!(transcript.verify(&generators, T, &mut hasher, &mut buf));
+    println!(
+        "round {}: {}",
+        disclosure.round,
+        String::from_utf8_lossy(&transcript.payload)
+    );
+}
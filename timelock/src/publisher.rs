@@ -0,0 +1,69 @@
+use blake3::Hasher;
+use chacha20poly1305::{
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+use common::{
+    error::{Error, ErrorKind::UndecryptablePayload},
+    generators::Generators,
+    random::random_scalar,
+    secret::Secret,
+};
+use pi_s_pvss::dealer::Dealer;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+use crate::capsule::{Capsule, NONCE_LEN, payload_key};
+
+/// Seals payloads to a committee, each under a fresh secret
+pub struct Publisher {
+    dealer: Dealer,
+}
+
+impl Publisher {
+    /// `generators` holds the committee's registered public keys
+    pub fn new(n: usize, t: usize, generators: &Arc<Generators>) -> Result<Self, Error> {
+        Ok(Self {
+            dealer: Dealer::new(n, t, generators)?,
+        })
+    }
+
+    pub fn seal<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        release_round: u64,
+        payload: &[u8],
+    ) -> Result<Capsule, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let secret = Secret::new(random_scalar(rng));
+        let (encrypted_shares, proof) = self.dealer.deal_secret(rng, hasher, buf, secret.expose());
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+        let key = payload_key(
+            release_round,
+            &(self.dealer.generators.G_table() * secret.expose()),
+        );
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key.expose()))
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: payload,
+                    aad: &Capsule::associated_data(release_round, &encrypted_shares),
+                },
+            )
+            .map_err(|_| Error::from(UndecryptablePayload))?;
+
+        Ok(Capsule {
+            release_round,
+            encrypted_shares,
+            proof,
+            nonce,
+            ciphertext,
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use blake3::Hasher;
+use common::generators::Generators;
+
+use crate::capsule::{Capsule, ReleaseShare};
+
+/// Everything needed to recheck a disclosure from the committee's public keys: the capsule as
+/// published, the t+1 shares released after its round and the payload they open
+#[derive(Clone)]
+pub struct Transcript {
+    pub capsule: Capsule,
+    /// by increasing party index
+    pub shares: Vec<ReleaseShare>,
+    pub payload: Vec<u8>,
+}
+
+impl Transcript {
+    /// Checks against the generators the committee's public keys were registered with
+    pub fn verify(
+        &self,
+        generators: &Generators,
+        t: usize,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+    ) -> bool {
+        let (G, public_keys) = (generators.G(), generators.public_keys());
+        let increasing = self
+            .shares
+            .windows(2)
+            .all(|pair| pair[0].index < pair[1].index);
+        if self.shares.len() != t + 1
+            || !increasing
+            || !self
+                .capsule
+                .verify(generators.public_key_tables(), t, hasher, buf)
+        {
+            return false;
+        }
+
+        // every share is checked, including its round, before the payload is opened
+        self.capsule
+            .open(G, public_keys, t, &self.shares, hasher, buf)
+            .is_ok_and(|payload| payload == self.payload)
+    }
+}
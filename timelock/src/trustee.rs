@@ -0,0 +1,81 @@
+use blake3::Hasher;
+use common::{
+    error::{Error, ErrorKind::TooEarly},
+    generators::Generators,
+    random::random_scalar,
+    secret::Secret,
+};
+use pi_s_pvss::party::Party;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+use crate::capsule::{Capsule, ReleaseShare, challenge};
+
+/// A committee member: holds a share of every capsule and releases it once its round is reached
+pub struct Trustee {
+    pub party: Party,
+}
+
+impl Trustee {
+    pub fn new<R>(
+        generators: &Arc<Generators>,
+        rng: &mut R,
+        n: usize,
+        t: usize,
+        index: usize,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        Ok(Self {
+            party: Party::new(generators, rng, n, t, index)?,
+        })
+    }
+
+    /// Decrypts this trustee's share of `capsule` with a DLEQ proof bound to `round`, refusing
+    /// before its release round
+    pub fn release<R>(
+        &mut self,
+        rng: &mut R,
+        hasher: &mut Hasher,
+        buf: &mut [u8; 64],
+        capsule: &Capsule,
+        round: u64,
+    ) -> Result<ReleaseShare, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if round < capsule.release_round {
+            return Err(TooEarly(round, capsule.release_round).into());
+        }
+        self.party
+            .ingest_encrypted_shares(&capsule.encrypted_shares)?;
+        self.party.decrypt_share()?;
+        // both set above
+        let (encrypted_share, decrypted_share) = (
+            self.party.encrypted_share.unwrap(),
+            self.party.decrypted_share.unwrap(),
+        );
+
+        let r = Secret::new(random_scalar(rng));
+        let d = challenge(
+            hasher,
+            buf,
+            round,
+            &self.party.public_key.1,
+            &encrypted_share,
+            &decrypted_share,
+            (
+                &(self.party.generators.G_table() * r.expose()),
+                &(decrypted_share * r.expose()),
+            ),
+        );
+
+        Ok(ReleaseShare {
+            index: self.party.index,
+            round,
+            decrypted_share: decrypted_share.compress(),
+            proof: (d, r.expose() + d * self.party.private_key()),
+        })
+    }
+}